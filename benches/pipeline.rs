@@ -0,0 +1,50 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use tracing_opentelemetry_setup::builder::ExportMode;
+use tracing_opentelemetry_setup::Otlp;
+use tracing_opentelemetry_setup::testing::Pipeline;
+use tracing_opentelemetry_setup::tracing;
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench(name: &str, pipeline: &Pipeline, cb: impl Fn(u32)) {
+    tracing::dispatcher::with_default(pipeline.dispatch(), || {
+        //warm up
+        for idx in 0..ITERATIONS / 10 {
+            cb(black_box(idx));
+        }
+        pipeline.flush();
+        pipeline.reset();
+
+        let started = Instant::now();
+        for idx in 0..ITERATIONS {
+            cb(black_box(idx));
+        }
+        pipeline.flush();
+        let elapsed = started.elapsed();
+        pipeline.reset();
+
+        let per_iteration = elapsed / ITERATIONS;
+        println!("{name:<32} {per_iteration:>10?}/iter ({elapsed:?} total)");
+    });
+}
+
+fn main() {
+    for (mode, pipeline) in [("batch", Otlp::builder_in_memory().with_export_mode(ExportMode::Batch).finish_in_memory()), ("simple", Otlp::builder_in_memory().with_export_mode(ExportMode::Simple).finish_in_memory())] {
+        bench(&format!("{mode}/span"), &pipeline, |idx| {
+            let _span = tracing::info_span!("span", idx).entered();
+        });
+        bench(&format!("{mode}/nested_span"), &pipeline, |idx| {
+            let _parent = tracing::info_span!("parent", idx).entered();
+            let _child = tracing::info_span!("child", idx).entered();
+        });
+        bench(&format!("{mode}/event"), &pipeline, |idx| {
+            tracing::info!(idx, "event");
+        });
+        bench(&format!("{mode}/event_in_span"), &pipeline, |idx| {
+            let _span = tracing::info_span!("span").entered();
+            tracing::info!(idx, "event");
+        });
+    }
+}
@@ -1,13 +1,15 @@
 //! Opentelemetry setup module
 
 use core::{fmt, time};
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::borrow::Cow;
+use std::sync::Arc;
 
 use opentelemetry_sdk::error::OTelSdkError;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
 
-use crate::layer::OtlpLayer;
+use crate::layer::{OtlpLayer, ReloadHandle};
 
 #[cfg(feature = "grpc")]
 fn create_metadata_map(headers: &[(String, String)]) -> tonic::metadata::MetadataMap {
@@ -31,11 +33,77 @@ fn create_metadata_map(headers: &[(String, String)]) -> tonic::metadata::Metadat
     result
 }
 
-#[cfg(all(feature = "datadog", feature = "metrics"))]
-#[cold]
-#[inline(never)]
-fn unsupported_datadog_feature() -> ! {
-    panic!("Attempt to use 'datadog' while it doesn't support metrics functionality")
+#[cfg(all(feature = "grpc", unix))]
+fn create_uds_channel(path: &str, timeout: time::Duration) -> tonic::transport::Channel {
+    let path = std::path::PathBuf::from(path);
+    //Connector ignores uri, but tonic still requires valid one
+    let endpoint = tonic::transport::Endpoint::from_static("http://[::]:4317").timeout(timeout);
+    endpoint.connect_with_connector_lazy(tower::service_fn(move |_: tonic::codegen::http::Uri| {
+        let path = path.clone();
+        async move {
+            let stream = tokio::net::UnixStream::connect(path).await?;
+            Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(stream))
+        }
+    }))
+}
+
+#[cfg(feature = "grpc")]
+type GrpcInterceptorFn = dyn Fn(tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> + Send + Sync;
+
+#[cfg(feature = "grpc")]
+#[derive(Clone)]
+#[repr(transparent)]
+struct GrpcInterceptor(std::sync::Arc<GrpcInterceptorFn>);
+
+#[cfg(feature = "grpc")]
+impl tonic::service::Interceptor for GrpcInterceptor {
+    #[inline(always)]
+    fn call(&mut self, request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+        (self.0)(request)
+    }
+}
+
+#[cfg(feature = "http")]
+type HttpClientFuture<'a> = core::pin::Pin<Box<dyn core::future::Future<Output = Result<http::Response<opentelemetry_http::Bytes>, opentelemetry_http::HttpError>> + Send + 'a>>;
+
+#[cfg(feature = "http")]
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+struct HttpClient(std::sync::Arc<dyn opentelemetry_http::HttpClient>);
+
+#[cfg(feature = "http")]
+impl opentelemetry_http::HttpClient for HttpClient {
+    #[inline(always)]
+    fn send_bytes<'a, 'b>(&'a self, request: http::Request<opentelemetry_http::Bytes>) -> HttpClientFuture<'b> where 'a: 'b, Self: 'b {
+        self.0.send_bytes(request)
+    }
+}
+
+#[cfg(any(feature = "http-reqwest", feature = "http-reqwest-blocking"))]
+fn create_proxy_http_client(proxy: &str, timeout: time::Duration) -> HttpClient {
+    let proxy = match reqwest::Proxy::all(proxy) {
+        Ok(result) => result.no_proxy(reqwest::NoProxy::from_env()),
+        Err(error) => panic!("Proxy '{proxy}' is not valid: {error}"),
+    };
+
+    #[cfg(feature = "http-reqwest-blocking")]
+    {
+        //blocking client cannot be created within async runtime so do it in separate thread same as opentelemetry-otlp
+        let client = std::thread::spawn(move || reqwest::blocking::Client::builder().proxy(proxy).timeout(timeout).build());
+        match client.join() {
+            Ok(Ok(client)) => HttpClient(std::sync::Arc::new(client)),
+            Ok(Err(error)) => panic!("Failed to create HTTP client with proxy: {error}"),
+            Err(_) => panic!("Failed to create HTTP client with proxy"),
+        }
+    }
+
+    #[cfg(not(feature = "http-reqwest-blocking"))]
+    {
+        match reqwest::Client::builder().proxy(proxy).timeout(timeout).build() {
+            Ok(client) => HttpClient(std::sync::Arc::new(client)),
+            Err(error) => panic!("Failed to create HTTP client with proxy: {error}"),
+        }
+    }
 }
 
 #[cfg(not(feature = "datadog"))]
@@ -45,6 +113,39 @@ fn missing_datadog_feature() -> ! {
     panic!("Attempt to use 'datadog' when corresponding feature is not enabled")
 }
 
+#[cfg(not(feature = "stdout"))]
+#[cold]
+#[inline(never)]
+fn missing_stdout_feature() -> ! {
+    panic!("Attempt to use 'stdout' when corresponding feature is not enabled")
+}
+
+#[cfg(not(feature = "file"))]
+#[cold]
+#[inline(never)]
+fn missing_file_feature() -> ! {
+    panic!("Attempt to use 'file' when corresponding feature is not enabled")
+}
+
+#[cfg(not(feature = "testing"))]
+#[cold]
+#[inline(never)]
+fn missing_testing_feature() -> ! {
+    panic!("Attempt to use 'in_memory' when 'testing' feature is not enabled")
+}
+
+#[cfg(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+fn missing_protocol_feature(protocol: Protocol) -> ! {
+    match protocol {
+        Protocol::Grpc => missing_grpc_feature(),
+        Protocol::HttpBinary | Protocol::HttpJson => missing_http_feature(),
+        Protocol::DatadogAgent => missing_datadog_feature(),
+        Protocol::Stdout => missing_stdout_feature(),
+        Protocol::File => missing_file_feature(),
+        Protocol::InMemory => missing_testing_feature(),
+    }
+}
+
 #[cfg(not(feature = "grpc"))]
 #[cold]
 #[inline(never)]
@@ -59,6 +160,44 @@ fn missing_http_feature() -> ! {
     panic!("Attempt to use 'http' when corresponding feature is not enabled")
 }
 
+fn host_name() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    if let Ok(name) = std::fs::read_to_string("/proc/sys/kernel/hostname") {
+        let name = name.trim();
+        if !name.is_empty() {
+            return Some(name.to_owned());
+        }
+    }
+
+    ["HOSTNAME", "COMPUTERNAME"].into_iter().filter_map(|name| std::env::var(name).ok()).find(|name| !name.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+fn container_id() -> Option<String> {
+    const ID_LEN: usize = 64;
+
+    //Container runtimes name cgroups and mounts after container ID, which is 64 hex digits
+    //(e.g. `/docker/<id>`, `/system.slice/docker-<id>.scope` or `/var/lib/docker/containers/<id>/hostname`)
+    fn find_id(line: &str) -> Option<&str> {
+        line.split(['/', '-', '.', ':', ' ']).find(|part| part.len() == ID_LEN && part.bytes().all(|byte| byte.is_ascii_hexdigit()))
+    }
+
+    if let Ok(cgroup) = std::fs::read_to_string("/proc/self/cgroup") {
+        if let Some(id) = cgroup.lines().find_map(find_id) {
+            return Some(id.to_owned());
+        }
+    }
+
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+    mountinfo.lines().filter(|line| line.contains("/containers/")).find_map(find_id).map(ToOwned::to_owned)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[inline(always)]
+fn container_id() -> Option<String> {
+    None
+}
+
 ///Opentelemetry attributes that can be put to be exported along side all records
 #[derive(Clone)]
 #[repr(transparent)]
@@ -70,22 +209,117 @@ impl Attributes {
     pub fn builder() -> AttributesBuilder {
         AttributesBuilder::new()
     }
+
+    ///Creates attributes specified by environment only
+    ///
+    ///`OTEL_RESOURCE_ATTRIBUTES` is parsed as comma separated list of `key=value` pairs, while `OTEL_SERVICE_NAME` specifies `service.name`, taking priority over `OTEL_RESOURCE_ATTRIBUTES`.
+    ///If neither specifies `service.name`, it defaults to `unknown_service`
+    pub fn from_env() -> Self {
+        let resource = opentelemetry_sdk::Resource::builder_empty().with_detectors(&[
+            Box::new(opentelemetry_sdk::resource::EnvResourceDetector::new()),
+            //Reads `OTEL_SERVICE_NAME`, falling back to `service.name` from `OTEL_RESOURCE_ATTRIBUTES`
+            Box::new(opentelemetry_sdk::resource::SdkProvidedResourceDetector),
+        ]).build();
+        Self(resource)
+    }
+
+    #[inline]
+    ///Merges attributes with `other`, returning new attributes
+    ///
+    ///Attributes of `other` take priority over attributes with the same key
+    pub fn merge(&self, other: &Self) -> Self {
+        let attrs = self.0.iter().chain(other.0.iter()).map(|(key, value)| opentelemetry::KeyValue::new(key.clone(), value.clone()));
+        Self(opentelemetry_sdk::Resource::builder_empty().with_attributes(attrs).build())
+    }
+}
+
+impl FromIterator<(Cow<'static, str>, opentelemetry::Value)> for Attributes {
+    ///Creates attributes consisting only of provided pairs
+    ///
+    ///Unlike [AttributesBuilder], no default attributes (e.g. from environment) are included, so result can be safely used with [Attributes::merge] to override specific attributes
+    fn from_iter<T: IntoIterator<Item = (Cow<'static, str>, opentelemetry::Value)>>(iter: T) -> Self {
+        let attrs = iter.into_iter().map(|(key, value)| opentelemetry::KeyValue::new(key, value));
+        Self(opentelemetry_sdk::Resource::builder_empty().with_attributes(attrs).build())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+///Build information of application, usually created via [build_info](crate::build_info)
+pub struct BuildInfo {
+    ///Version of application, recorded as `service.version`
+    pub version: &'static str,
+    ///VCS revision (e.g. git commit SHA), recorded as `vcs.revision`
+    pub revision: Option<&'static str>,
+    ///Build timestamp, recorded as `build.timestamp`
+    pub timestamp: Option<&'static str>,
+}
+
+#[macro_export]
+///Creates [BuildInfo](crate::builder::BuildInfo) of the calling crate using compile time environment
+///
+///- `version` is taken from `CARGO_PKG_VERSION`;
+///- `revision` is taken from `GIT_SHA` or `VERGEN_GIT_SHA`;
+///- `timestamp` is taken from `BUILD_TIMESTAMP` or `VERGEN_BUILD_TIMESTAMP`.
+///
+///Revision and timestamp are expected to be set by build script (e.g. `cargo:rustc-env=GIT_SHA=<sha>`) or by CI environment.
+///
+///```rust
+///use tracing_opentelemetry_setup::build_info;
+///use tracing_opentelemetry_setup::builder::Attributes;
+///
+///let attrs = Attributes::builder().with_build_info(build_info!()).finish();
+///```
+macro_rules! build_info {
+    () => {
+        $crate::builder::BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            revision: match option_env!("GIT_SHA") {
+                Some(revision) => Some(revision),
+                None => option_env!("VERGEN_GIT_SHA"),
+            },
+            timestamp: match option_env!("BUILD_TIMESTAMP") {
+                Some(timestamp) => Some(timestamp),
+                None => option_env!("VERGEN_BUILD_TIMESTAMP"),
+            },
+        }
+    };
 }
 
 ///[Attributes] builder
 pub struct AttributesBuilder {
-    inner: opentelemetry_sdk::resource::ResourceBuilder
+    inner: opentelemetry_sdk::resource::ResourceBuilder,
+    defaults: bool,
 }
 
 impl AttributesBuilder {
     #[inline]
     ///Creates new builder
+    ///
+    ///Builder starts with attributes from environment (see [Attributes::from_env]) merged by default, which are overridden by attributes specified via builder.
+    ///Use [with_env](Self::with_env) to give environment priority instead
     pub fn new() -> Self {
         Self {
-            inner: opentelemetry_sdk::resource::Resource::builder()
+            inner: opentelemetry_sdk::resource::Resource::builder_empty().with_detectors(&[
+                Box::new(opentelemetry_sdk::resource::SdkProvidedResourceDetector),
+                Box::new(opentelemetry_sdk::resource::EnvResourceDetector::new()),
+            ]),
+            defaults: true,
         }
     }
 
+    #[inline]
+    ///Specifies whether to populate default attributes on [finish](Self::finish), unless they are already specified
+    ///
+    ///Defaults are:
+    ///- `service.instance.id` - randomly generated UUID, which is the same for all attributes within the process;
+    ///- `telemetry.sdk.name`, `telemetry.sdk.version` and `telemetry.sdk.language` - describing opentelemetry SDK.
+    ///
+    ///Enabled by default
+    pub fn with_defaults(mut self, defaults: bool) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
     #[inline]
     ///Specifies `key` attribute with provided `value`
     ///
@@ -95,10 +329,261 @@ impl AttributesBuilder {
         self
     }
 
+    #[inline]
+    ///Specifies all attributes from `attrs`
+    pub fn with_attrs<K: Into<Cow<'static, str>>, V: Into<opentelemetry::Value>>(mut self, attrs: impl IntoIterator<Item = (K, V)>) -> Self {
+        self.inner = self.inner.with_attributes(attrs.into_iter().map(|(key, value)| opentelemetry::KeyValue::new(key.into(), value.into())));
+        self
+    }
+
+    ///Specifies `service.version`, `vcs.revision` and `build.timestamp` attributes from `info`
+    ///
+    ///Empty revision and timestamp are ignored
+    pub fn with_build_info(mut self, info: BuildInfo) -> Self {
+        self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::SERVICE_VERSION, info.version));
+        if let Some(revision) = info.revision.filter(|revision| !revision.is_empty()) {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::VCS_REVISION, revision));
+        }
+        if let Some(timestamp) = info.timestamp.filter(|timestamp| !timestamp.is_empty()) {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::BUILD_TIMESTAMP, timestamp));
+        }
+        self
+    }
+
+    #[inline]
+    ///Specifies attributes from environment (see [Attributes::from_env]), overriding previously specified attributes with the same key
+    ///
+    ///This allows platform to override attributes configured in code (e.g. `deployment.environment.name`)
+    pub fn with_env(mut self) -> Self {
+        self.inner = self.inner.with_detector(Box::new(opentelemetry_sdk::resource::EnvResourceDetector::new()));
+        //Default service name must not override one specified in code
+        if std::env::var_os("OTEL_SERVICE_NAME").is_some_and(|name| !name.is_empty()) {
+            self.inner = self.inner.with_detector(Box::new(opentelemetry_sdk::resource::SdkProvidedResourceDetector));
+        }
+        self
+    }
+
+    ///Specifies `process.pid`, `process.executable.name` and `process.command_args` attributes of current process
+    pub fn with_process_detection(mut self) -> Self {
+        self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::PROCESS_PID, i64::from(std::process::id())));
+
+        let args = std::env::args_os().map(|arg| arg.to_string_lossy().into_owned().into()).collect::<Vec<opentelemetry::StringValue>>();
+        let executable = std::env::current_exe().ok().and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()));
+        let executable = executable.or_else(|| {
+            let arg = args.first()?;
+            let name = std::path::Path::new(arg.as_str()).file_name()?;
+            Some(name.to_string_lossy().into_owned())
+        });
+        if let Some(executable) = executable {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::PROCESS_EXECUTABLE_NAME, executable));
+        }
+        if !args.is_empty() {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::PROCESS_COMMAND_ARGS, opentelemetry::Value::Array(args.into())));
+        }
+
+        self
+    }
+
+    ///Specifies `host.name`, `host.arch` and `os.type` attributes of current host
+    ///
+    ///`host.name` is omitted if it cannot be determined
+    pub fn with_host_detection(mut self) -> Self {
+        if let Some(name) = host_name() {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::HOST_NAME, name));
+        }
+
+        let arch = match std::env::consts::ARCH {
+            "x86_64" => "amd64",
+            "aarch64" => "arm64",
+            "arm" => "arm32",
+            "x86" => "x86",
+            "powerpc" => "ppc32",
+            "powerpc64" => "ppc64",
+            "s390x" => "s390x",
+            "ia64" => "ia64",
+            arch => arch,
+        };
+        self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::HOST_ARCH, arch));
+
+        let os = match std::env::consts::OS {
+            "macos" | "ios" => "darwin",
+            "illumos" => "solaris",
+            os => os,
+        };
+        self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::OS_TYPE, os));
+
+        self
+    }
+
+    ///Specifies `container.id` attribute, if process runs within container
+    ///
+    ///Container ID is looked up within `/proc/self/cgroup` and, for cgroup v2, within `/proc/self/mountinfo`.
+    ///Does nothing on platforms other than Linux
+    pub fn with_container_detection(mut self) -> Self {
+        if let Some(id) = container_id() {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::CONTAINER_ID, id));
+        }
+        self
+    }
+
+    ///Specifies `k8s.*` attributes from environment variables populated via Kubernetes downward API
+    ///
+    ///Following variables are read (first non-empty wins):
+    ///- `k8s.pod.name` - `K8S_POD_NAME` or `POD_NAME`
+    ///- `k8s.pod.uid` - `K8S_POD_UID` or `POD_UID`
+    ///- `k8s.namespace.name` - `K8S_NAMESPACE_NAME` or `POD_NAMESPACE`, falling back to namespace of service account
+    ///- `k8s.node.name` - `K8S_NODE_NAME` or `NODE_NAME`
+    ///
+    ///Example of pod spec:
+    ///
+    ///```yaml
+    ///env:
+    ///  - name: K8S_POD_NAME
+    ///    valueFrom:
+    ///      fieldRef:
+    ///        fieldPath: metadata.name
+    ///  - name: K8S_NAMESPACE_NAME
+    ///    valueFrom:
+    ///      fieldRef:
+    ///        fieldPath: metadata.namespace
+    ///  - name: K8S_NODE_NAME
+    ///    valueFrom:
+    ///      fieldRef:
+    ///        fieldPath: spec.nodeName
+    ///```
+    pub fn with_k8s_downward_api(mut self) -> Self {
+        let var = |names: [&str; 2]| names.into_iter().filter_map(|name| std::env::var(name).ok()).find(|value| !value.is_empty());
+
+        if let Some(name) = var(["K8S_POD_NAME", "POD_NAME"]) {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::K8S_POD_NAME, name));
+        }
+        if let Some(uid) = var(["K8S_POD_UID", "POD_UID"]) {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::K8S_POD_UID, uid));
+        }
+        let namespace = var(["K8S_NAMESPACE_NAME", "POD_NAMESPACE"]).or_else(|| {
+            let namespace = std::fs::read_to_string("/var/run/secrets/kubernetes.io/serviceaccount/namespace").ok()?;
+            let namespace = namespace.trim();
+            (!namespace.is_empty()).then(|| namespace.to_owned())
+        });
+        if let Some(namespace) = namespace {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::K8S_NAMESPACE_NAME, namespace));
+        }
+        if let Some(node) = var(["K8S_NODE_NAME", "NODE_NAME"]) {
+            self.inner = self.inner.with_attribute(opentelemetry::KeyValue::new(crate::keys::K8S_NODE_NAME, node));
+        }
+
+        self
+    }
+
+    #[cfg(feature = "aws-detector")]
+    ///Specifies AWS resource attributes (`cloud.*`, `host.*`, `faas.*`, `aws.ecs.*`) when running on AWS
+    ///
+    ///Detects following environments:
+    ///- Lambda - using environment variables;
+    ///- ECS - using task metadata endpoint v4;
+    ///- EC2 and EKS - using instance metadata service (`AWS_EC2_METADATA_SERVICE_ENDPOINT` can be used to override its address).
+    ///
+    ///Outside of AWS no attributes are added, but it may take up to couple of seconds to give up on metadata service.
+    ///Requires async runtime compatible with `reqwest` (i.e. tokio)
+    pub async fn with_aws_detection(mut self) -> Self {
+        self.inner = self.inner.with_attributes(crate::detector::aws().await);
+        self
+    }
+
+    #[cfg(feature = "gcp-detector")]
+    ///Specifies GCP resource attributes (`cloud.*`, `host.*`, `faas.*`, `k8s.cluster.name`) when running on GCP
+    ///
+    ///Detects Cloud Functions, Cloud Run, GKE and Compute Engine using metadata server (`GCE_METADATA_HOST` can be used to override its address).
+    ///
+    ///Outside of GCP no attributes are added, but it may take up to couple of seconds to give up on metadata server.
+    ///Requires async runtime compatible with `reqwest` (i.e. tokio)
+    pub async fn with_gcp_detection(mut self) -> Self {
+        self.inner = self.inner.with_attributes(crate::detector::gcp().await);
+        self
+    }
+
     #[inline]
     ///Finalize builder
     pub fn finish(self) -> Attributes {
-        Attributes(self.inner.build())
+        let resource = self.inner.build();
+        if !self.defaults {
+            return Attributes(resource);
+        }
+
+        let mut defaults = opentelemetry_sdk::Resource::builder_empty().with_detector(Box::new(opentelemetry_sdk::resource::TelemetryResourceDetector));
+        if resource.get(&crate::keys::SERVICE_INSTANCE_ID).is_none() {
+            defaults = defaults.with_attribute(opentelemetry::KeyValue::new(crate::keys::SERVICE_INSTANCE_ID, service_instance_id()));
+        }
+        let attrs = resource.iter().map(|(key, value)| opentelemetry::KeyValue::new(key.clone(), value.clone()));
+        Attributes(defaults.with_attributes(attrs).build())
+    }
+}
+
+fn service_instance_id() -> &'static str {
+    static ID: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    ID.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Telemetry signal
+pub enum Signal {
+    ///Logs
+    Logs,
+    ///Trace
+    Trace,
+    ///Metrics
+    Metrics,
+}
+
+///Default time limit for individual components to shutdown
+pub const DEFAULT_SHUTDOWN_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Describes how long to wait for individual components during [Otlp::shutdown]
+pub enum ShutdownMode {
+    ///Best-effort shutdown that doesn't wait for pending data to be exported
+    Immediate,
+    ///Waits no longer than specified duration
+    Timeout(time::Duration),
+    ///Waits until all pending data is exported
+    WaitForever,
+}
+
+impl ShutdownMode {
+    ///Waits for [DEFAULT_SHUTDOWN_TIMEOUT]
+    pub const DEFAULT: Self = Self::Timeout(DEFAULT_SHUTDOWN_TIMEOUT);
+
+    #[inline]
+    const fn limit(self) -> time::Duration {
+        match self {
+            Self::Immediate => time::Duration::ZERO,
+            Self::Timeout(limit) => limit,
+            Self::WaitForever => time::Duration::MAX,
+        }
+    }
+}
+
+impl Default for ShutdownMode {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl From<time::Duration> for ShutdownMode {
+    #[inline(always)]
+    fn from(limit: time::Duration) -> Self {
+        Self::Timeout(limit)
+    }
+}
+
+impl From<Option<time::Duration>> for ShutdownMode {
+    #[inline(always)]
+    fn from(limit: Option<time::Duration>) -> Self {
+        match limit {
+            Some(limit) => Self::Timeout(limit),
+            None => Self::DEFAULT,
+        }
     }
 }
 
@@ -112,8 +597,32 @@ pub struct ShutdownError {
 }
 
 impl fmt::Debug for ShutdownError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_debug("OtlpShutdownError", fmt)
+    }
+}
+
+impl fmt::Display for ShutdownError {
+    #[inline(always)]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut fmt = fmt.debug_struct("OtlpShutdownError");
+        fmt.write_str("Failed to shutdown Otlp:")?;
+        self.fmt_signals(fmt)
+    }
+}
+
+impl ShutdownError {
+    #[inline(always)]
+    fn into_result(self) -> Result<(), Self> {
+        let is_error = self.iter().next().is_some();
+        match is_error {
+            true => Err(self),
+            false => Ok(()),
+        }
+    }
+
+    fn fmt_debug(&self, name: &str, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut fmt = fmt.debug_struct(name);
 
         if let Some(logs) = self.logs.as_ref() {
             fmt.field("logs", logs);
@@ -130,12 +639,8 @@ impl fmt::Debug for ShutdownError {
 
         fmt.finish()
     }
-}
-
-impl fmt::Display for ShutdownError {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.write_str("Failed to shutdown Otlp:")?;
 
+    fn fmt_signals(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(logs) = self.logs.as_ref() {
             fmt.write_fmt(format_args!(" logs={logs}"))?
         }
@@ -151,22 +656,113 @@ impl fmt::Display for ShutdownError {
 
         Ok(())
     }
+
+    #[inline(always)]
+    ///Returns error of `logs` provider, if any
+    pub fn logs_error(&self) -> Option<&OTelSdkError> {
+        self.logs.as_ref()
+    }
+
+    #[inline(always)]
+    ///Returns error of `trace` provider, if any
+    pub fn trace_error(&self) -> Option<&OTelSdkError> {
+        self.trace.as_ref()
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    #[inline(always)]
+    ///Returns error of `metrics` provider, if any
+    pub fn metrics_error(&self) -> Option<&OTelSdkError> {
+        self.metrics.as_ref()
+    }
+
+    ///Iterates over all errors alongside signal that failed
+    pub fn iter(&self) -> impl Iterator<Item = (Signal, &OTelSdkError)> {
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        let metrics = self.metrics.as_ref().map(|error| (Signal::Metrics, error));
+        #[cfg(not(any(feature = "metrics", feature = "tracing-metrics")))]
+        let metrics = None;
+
+        self.logs.as_ref().map(|error| (Signal::Logs, error)).into_iter()
+                          .chain(self.trace.as_ref().map(|error| (Signal::Trace, error)))
+                          .chain(metrics)
+    }
+
+    ///Returns whether every failed signal failed due to timeout
+    ///
+    ///This allows to distinguish slow shutdown (e.g. unreachable collector) from actual failure when mapping error to exit code:
+    ///
+    ///```rust,no_run
+    ///fn exit_code(error: &tracing_opentelemetry_setup::builder::ShutdownError) -> i32 {
+    ///    match error.is_timeout() {
+    ///        true => 2,
+    ///        false => 1,
+    ///    }
+    ///}
+    ///```
+    pub fn is_timeout(&self) -> bool {
+        let mut errors = self.iter().peekable();
+        errors.peek().is_some() && errors.all(|(_, error)| matches!(error, OTelSdkError::Timeout(_)))
+    }
 }
 
 impl std::error::Error for ShutdownError {}
 
-///Opentelemetry integration wrapper
+#[derive(Debug)]
+///[Builder::try_finish] error, describing why destination cannot be used
+pub struct StartupError(String);
+
+impl fmt::Display for StartupError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StartupError {}
+
+#[derive(Default)]
+///[Otlp] flush error
 ///
-///It contains references to all exporters which allows it to shutdown on demand or on `Drop`
-pub struct Otlp {
-    logs: Option<SdkLoggerProvider>,
-    trace: Option<SdkTracerProvider>,
+///Errors of individual signals are accessible via [ShutdownError] methods
+pub struct FlushError(ShutdownError);
+
+impl core::ops::Deref for FlushError {
+    type Target = ShutdownError;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Debug for FlushError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_debug("OtlpFlushError", fmt)
+    }
+}
+
+impl fmt::Display for FlushError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Failed to flush Otlp:")?;
+        self.0.fmt_signals(fmt)
+    }
+}
+
+impl std::error::Error for FlushError {}
+
+///Flags indicating whether signal's exporter had successful export
+struct Readiness {
+    logs: Option<Arc<AtomicBool>>,
+    trace: Option<Arc<AtomicBool>>,
     #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
-    metrics: Option<opentelemetry_sdk::metrics::SdkMeterProvider>
+    metrics: Option<Arc<AtomicBool>>,
 }
 
-impl Otlp {
-    #[inline]
+impl Readiness {
+    #[inline(always)]
     const fn new() -> Self {
         Self {
             logs: None,
@@ -176,50 +772,503 @@ impl Otlp {
         }
     }
 
-    #[inline]
-    ///Starts building Opentelemetry integration
-    pub const fn builder(destination: Destination<'_>) -> Builder<'_> {
-        Builder::new(destination)
+    fn is_ready(&self) -> bool {
+        let is_signal_ready = |signal: &Option<Arc<AtomicBool>>| match signal {
+            Some(ready) => ready.load(Ordering::Acquire),
+            None => true,
+        };
+
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if !is_signal_ready(&self.metrics) {
+            return false;
+        }
+
+        is_signal_ready(&self.logs) && is_signal_ready(&self.trace)
+    }
+}
+
+///Removes credentials and query from `url`
+fn redact_url(url: &str) -> String {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+    let rest = match rest.find(['?', '#']) {
+        Some(idx) => &rest[..idx],
+        None => rest,
+    };
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    let rest = match rest[..authority_end].rfind('@') {
+        Some(idx) => &rest[idx + 1..],
+        None => rest,
+    };
+
+    match scheme {
+        Some(scheme) => format!("{scheme}://{rest}"),
+        None => rest.to_owned(),
+    }
+}
+
+#[cfg(feature = "rt-tokio")]
+///Waits for termination signal, returning exit code corresponding to it
+async fn wait_shutdown_signal() -> i32 {
+    use core::task::Poll;
+
+    const SIGINT_CODE: i32 = 130;
+
+    let mut ctrl_c = Some(Box::pin(tokio::signal::ctrl_c()));
+    #[cfg(unix)]
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).ok();
+
+    core::future::poll_fn(|ctx| {
+        #[cfg(unix)]
+        if let Some(terminate) = terminate.as_mut() {
+            const SIGTERM_CODE: i32 = 143;
+
+            if terminate.poll_recv(ctx).is_ready() {
+                return Poll::Ready(SIGTERM_CODE);
+            }
+        }
+
+        if let Some(signal) = ctrl_c.as_mut() {
+            match signal.as_mut().poll(ctx) {
+                Poll::Ready(Ok(())) => return Poll::Ready(SIGINT_CODE),
+                //Failed to listen, so there is nothing to wait for
+                Poll::Ready(Err(_)) => ctrl_c = None,
+                Poll::Pending => (),
+            }
+        }
+
+        Poll::Pending
+    }).await
+}
+
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+///Snapshot of telemetry configuration
+///
+///Secrets are never stored: endpoint is stripped of credentials and query, while only names of headers are retained
+pub struct OtlpConfig {
+    ///Destination endpoint
+    pub endpoint: String,
+    ///Destination protocol
+    pub protocol: Protocol,
+    ///Names of headers included with every export
+    pub headers: Vec<String>,
+    ///Exporters timeout
+    pub timeout: time::Duration,
+    ///Whether compression is requested
+    pub compression: bool,
+    ///Export mode of logs and traces
+    pub export_mode: ExportMode,
+    ///Max number of export attempts, if retries are enabled
+    pub retry_max_attempts: Option<u32>,
+    ///Whether logs are enabled
+    pub logs: bool,
+    ///Whether traces are enabled
+    pub trace: bool,
+    ///Whether metrics are enabled
+    pub metrics: bool,
+    ///Sample rate of traces, if traces are enabled
+    pub sample_rate: Option<f64>,
+}
+
+impl OtlpConfig {
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            endpoint: String::new(),
+            protocol: Protocol::Grpc,
+            headers: Vec::new(),
+            timeout: time::Duration::ZERO,
+            compression: false,
+            export_mode: ExportMode::Batch,
+            retry_max_attempts: None,
+            logs: false,
+            trace: false,
+            metrics: false,
+            sample_rate: None,
+        }
     }
 
-    ///Performs shutdown, limiting it to `limit` for individual components
+    ///Returns human-readable list of settings that differ in `other`, formatted as `<name>: <self> -> <other>`
     ///
-    ///If `limit` is `None` then defaults to 10 second wait
-    pub fn shutdown(&mut self, limit: Option<time::Duration>) -> Result<(), ShutdownError> {
-        let limit = match limit {
-            Some(limit) => limit,
-            None => time::Duration::from_secs(10),
-        };
+    ///Returns empty list if configurations are identical
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        macro_rules! diff_fields {
+            ($($field:ident,)+) => {{
+                let mut diff = Vec::new();
+                $(
+                    if self.$field != other.$field {
+                        diff.push(format!(concat!(stringify!($field), ": {:?} -> {:?}"), self.$field, other.$field));
+                    }
+                )+
+                diff
+            }};
+        }
+
+        diff_fields!(endpoint, protocol, headers, timeout, compression, export_mode, retry_max_attempts, logs, trace, metrics, sample_rate,)
+    }
+
+    fn emit(&self) {
+        let sdk = opentelemetry_sdk::Resource::builder_empty().with_detector(Box::new(opentelemetry_sdk::resource::TelemetryResourceDetector)).build();
+        let sdk_version = sdk.get(&crate::keys::TELEMETRY_SDK_VERSION).map(|version| version.to_string());
+        tracing::event!(
+            target: "tracing_opentelemetry_setup",
+            tracing::Level::INFO,
+            otel.endpoint = self.endpoint,
+            otel.protocol = self.protocol.as_str(),
+            otel.headers = self.headers.join(","),
+            otel.logs = self.logs,
+            otel.trace = self.trace,
+            otel.metrics = self.metrics,
+            otel.trace.sample_rate = self.sample_rate,
+            version = env!("CARGO_PKG_VERSION"),
+            otel.sdk.version = sdk_version,
+            otel.api.version = crate::version::OPENTELEMETRY_VERSION,
+            otel.tracing.version = crate::version::TRACING_OPENTELEMETRY_VERSION,
+            "OpenTelemetry is initialized"
+        );
+    }
+}
+
+///Opentelemetry integration wrapper
+///
+///It contains references to all exporters which allows it to shutdown on demand or on `Drop`
+pub struct Otlp {
+    logs: Option<SdkLoggerProvider>,
+    max_log_attributes: Option<usize>,
+    trace: Option<SdkTracerProvider>,
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    metrics: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
+    #[cfg(feature = "metrics")]
+    metrics_baggage_attrs: Vec<Cow<'static, str>>,
+    #[cfg(feature = "metrics")]
+    metrics_dropped_attrs: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    #[cfg(feature = "prometheus")]
+    prometheus: Option<crate::prometheus::PrometheusHandle>,
+    ready: Readiness,
+    config: OtlpConfig,
+    startup_summary: bool,
+    #[cfg(feature = "datadog")]
+    datadog_agent: Option<Result<DatadogAgentInfo, String>>,
+    integer_overflow: IntegerOverflow,
+}
+
+impl Otlp {
+    #[cfg(all(feature = "panic", feature = "file"))]
+    #[inline(always)]
+    pub(crate) fn logger_provider(&self) -> Option<&SdkLoggerProvider> {
+        self.logs.as_ref()
+    }
+
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            logs: None,
+            max_log_attributes: None,
+            trace: None,
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            metrics: None,
+            #[cfg(feature = "metrics")]
+            metrics_baggage_attrs: Vec::new(),
+            #[cfg(feature = "metrics")]
+            metrics_dropped_attrs: Vec::new(),
+            #[cfg(feature = "prometheus")]
+            prometheus: None,
+            ready: Readiness::new(),
+            config: OtlpConfig::new(),
+            startup_summary: false,
+            #[cfg(feature = "datadog")]
+            datadog_agent: None,
+            integer_overflow: IntegerOverflow::String,
+        }
+    }
+
+    #[inline]
+    ///Starts building Opentelemetry integration
+    pub const fn builder(destination: Destination<'_>) -> Builder<'_> {
+        Builder::new(destination)
+    }
+
+    #[inline(always)]
+    ///Returns snapshot of configuration this instance was built with
+    pub fn config(&self) -> &OtlpConfig {
+        &self.config
+    }
+
+    #[cfg(feature = "datadog")]
+    #[inline]
+    ///Returns information about Datadog agent, if it was probed successfully during [Builder::finish] or [Builder::try_finish]
+    pub fn datadog_agent_info(&self) -> Option<&DatadogAgentInfo> {
+        self.datadog_agent.as_ref().and_then(|result| result.as_ref().ok())
+    }
+
+    ///Emits diagnostics deferred until subscriber is initialized
+    fn emit_startup_diagnostics(&self) {
+        if self.startup_summary {
+            self.config.emit();
+        }
+
+        #[cfg(feature = "datadog")]
+        match self.datadog_agent.as_ref() {
+            Some(Ok(info)) => {
+                tracing::info!(target: "tracing_opentelemetry_setup", version = %info.version, endpoints = ?info.endpoints, "Datadog agent is available");
+                if !info.endpoints.iter().any(|endpoint| endpoint.trim_end_matches('/') == DATADOG_TRACES_ENDPOINT) {
+                    tracing::warn!(target: "tracing_opentelemetry_setup", version = %info.version, "Datadog agent doesn't support {DATADOG_TRACES_ENDPOINT} endpoint, traces are going to be rejected");
+                }
+            },
+            Some(Err(error)) => tracing::warn!(target: "tracing_opentelemetry_setup", %error, "Datadog agent is unreachable, traces are going to be dropped"),
+            None => (),
+        }
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[inline]
+    ///Returns handle rendering metrics for Prometheus scraping, if metrics are enabled with [MetricsSettings::with_prometheus]
+    pub fn prometheus_handle(&self) -> Option<crate::prometheus::PrometheusHandle> {
+        self.prometheus.clone()
+    }
+
+    ///Performs shutdown, limiting it according to `mode` for individual components
+    ///
+    ///`mode` can be specified as:
+    ///
+    ///- [ShutdownMode] for explicit semantics;
+    ///- `Option<Duration>` where `None` is [DEFAULT_SHUTDOWN_TIMEOUT];
+    ///- `Duration` which is the same as [ShutdownMode::Timeout]
+    pub fn shutdown(&mut self, mode: impl Into<ShutdownMode>) -> Result<(), ShutdownError> {
+        let limit = mode.into().limit();
 
-        let mut is_error = false;
         let mut errors = ShutdownError::default();
         if let Some(logs) = self.logs.take() {
-            if let Err(error) = logs.shutdown_with_timeout(limit) {
-                is_error = true;
-                errors.logs = Some(error);
-            }
+            errors.logs = logs.shutdown_with_timeout(limit).err();
         }
 
         if let Some(trace) = self.trace.take() {
-            if let Err(error) = trace.shutdown_with_timeout(limit) {
-                is_error = true;
-                errors.trace = Some(error);
-            }
+            errors.trace = trace.shutdown_with_timeout(limit).err();
         }
 
         #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
         if let Some(metrics) = self.metrics.take() {
-            if let Err(error) =  metrics.shutdown_with_timeout(limit) {
-                is_error = true;
-                errors.metrics = Some(error);
+            errors.metrics = metrics.shutdown_with_timeout(limit).err();
+        }
+
+        errors.into_result()
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    ///Performs [shutdown](Self::shutdown) on tokio's blocking thread pool, to avoid stalling runtime worker
+    ///
+    ///Providers are detached from `self` immediately, so returned future does not borrow it.
+    ///
+    ///Requires `rt-tokio` feature and must be awaited within tokio runtime
+    pub fn shutdown_async(&mut self, mode: impl Into<ShutdownMode>) -> impl Future<Output = Result<(), ShutdownError>> + Send + 'static {
+        let mode = mode.into();
+        let mut otlp = Self::new();
+        otlp.logs = self.logs.take();
+        otlp.trace = self.trace.take();
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        {
+            otlp.metrics = self.metrics.take();
+        }
+
+        async move {
+            match tokio::task::spawn_blocking(move || otlp.shutdown(mode)).await {
+                Ok(result) => result,
+                Err(error) => std::panic::resume_unwind(error.into_panic()),
             }
         }
+    }
 
-        if is_error {
-            Err(errors)
-        } else {
-            Ok(())
+    #[cfg(feature = "rt-tokio")]
+    ///Spawns task that waits for SIGTERM/SIGINT (Ctrl-C on Windows) and then shuts down all providers, flushing pending data, before exiting process
+    ///
+    ///Process exits with conventional `128 + signal number` code (130 for SIGINT and 143 for SIGTERM).
+    ///Use returned handle to abort if you no longer want it.
+    ///
+    ///Requires `rt-tokio` feature and must be called within tokio runtime
+    pub fn install_shutdown_signal_handler(&self) -> tokio::task::JoinHandle<()> {
+        //Task must not own `Otlp` as otherwise aborting it would shut down providers on drop
+        let logs = self.logs.clone();
+        let trace = self.trace.clone();
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        let metrics = self.metrics.clone();
+
+        tokio::spawn(async move {
+            let code = wait_shutdown_signal().await;
+            let mut otlp = Self::new();
+            otlp.logs = logs;
+            otlp.trace = trace;
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            {
+                otlp.metrics = metrics;
+            }
+            let _ = tokio::task::spawn_blocking(move || otlp.shutdown(ShutdownMode::DEFAULT)).await;
+            std::process::exit(code);
+        })
+    }
+
+    ///Flushes all pending data without shutting down, limiting it according to `mode`
+    ///
+    ///Unlike [shutdown](Self::shutdown) telemetry can be recorded after flush.
+    ///
+    ///SDK cannot limit duration of flush (e.g. with [ExportMode::Simple] or [ExportMode::Manual] export happens during flush),
+    ///hence unless `mode` is [ShutdownMode::WaitForever], flush is performed on separate thread.
+    ///Signals that are not flushed when limit expires are reported as timed out, while flush continues in background.
+    ///
+    ///`mode` accepts the same arguments as [shutdown](Self::shutdown)
+    pub fn force_flush(&self, mode: impl Into<ShutdownMode>) -> Result<(), FlushError> {
+        let limit = match mode.into() {
+            ShutdownMode::WaitForever => return self.force_flush_blocking(),
+            mode => mode.limit(),
+        };
+
+        let mut errors = FlushError::default();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let logs = self.logs.clone();
+        let trace = self.trace.clone();
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        let metrics = self.metrics.clone();
+        errors.0.logs = logs.as_ref().map(|_| OTelSdkError::Timeout(limit));
+        errors.0.trace = trace.as_ref().map(|_| OTelSdkError::Timeout(limit));
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        {
+            errors.0.metrics = metrics.as_ref().map(|_| OTelSdkError::Timeout(limit));
+        }
+
+        //Single thread flushes every signal in turn, reporting each result as soon as it is available
+        let flush = std::thread::Builder::new().name("otlp-flush".to_owned()).spawn(move || {
+            if let Some(logs) = logs {
+                let _ = sender.send((Signal::Logs, logs.force_flush()));
+            }
+            if let Some(trace) = trace {
+                let _ = sender.send((Signal::Trace, trace.force_flush()));
+            }
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            if let Some(metrics) = metrics {
+                let _ = sender.send((Signal::Metrics, metrics.force_flush()));
+            }
+        });
+        if flush.is_err() {
+            return self.force_flush_blocking();
+        }
+
+        let deadline = std::time::Instant::now() + limit;
+        while let Ok((signal, result)) = receiver.recv_timeout(deadline.saturating_duration_since(std::time::Instant::now())) {
+            let error = match signal {
+                Signal::Logs => &mut errors.0.logs,
+                Signal::Trace => &mut errors.0.trace,
+                #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+                Signal::Metrics => &mut errors.0.metrics,
+                #[cfg(not(any(feature = "metrics", feature = "tracing-metrics")))]
+                Signal::Metrics => continue,
+            };
+            *error = result.err();
+        }
+
+        errors.0.into_result().map_err(FlushError)
+    }
+
+    ///Flushes all signals on the calling thread, waiting until flush is complete
+    fn force_flush_blocking(&self) -> Result<(), FlushError> {
+        let mut errors = ShutdownError::default();
+        if let Some(logs) = self.logs.as_ref() {
+            errors.logs = logs.force_flush().err();
+        }
+
+        if let Some(trace) = self.trace.as_ref() {
+            errors.trace = trace.force_flush().err();
+        }
+
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if let Some(metrics) = self.metrics.as_ref() {
+            errors.metrics = metrics.force_flush().err();
+        }
+
+        errors.into_result().map_err(FlushError)
+    }
+
+    ///Exports data accumulated with [ExportMode::Manual] on the calling thread
+    ///
+    ///Intended to be called periodically by host's event loop (e.g. on timer of Windows service or GUI application).
+    ///With other export modes it is equivalent to flushing with [ShutdownMode::WaitForever], blocking until it is complete.
+    #[inline(always)]
+    pub fn tick(&self) -> Result<(), FlushError> {
+        self.force_flush_blocking()
+    }
+
+    ///Flushes all providers ignoring errors
+    pub(crate) fn flush(&self) {
+        if let Some(logs) = self.logs.as_ref() {
+            let _ = logs.force_flush();
+        }
+
+        if let Some(trace) = self.trace.as_ref() {
+            let _ = trace.force_flush();
         }
+
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if let Some(metrics) = self.metrics.as_ref() {
+            let _ = metrics.force_flush();
+        }
+    }
+
+    ///Waits until every configured signal completes at least one successful export, limited by `timeout`
+    ///
+    ///Pending data is flushed first, so make sure to emit something (e.g. startup log or span) for every signal before calling it.
+    ///Signal without any data is never exported, hence never becomes ready.
+    ///
+    ///Returns `true` if all signals are ready, `false` if `timeout` expired
+    pub fn wait_until_ready(&self, timeout: time::Duration) -> bool {
+        const POLL_INTERVAL: time::Duration = time::Duration::from_millis(50);
+
+        let started = std::time::Instant::now();
+        if self.ready.is_ready() {
+            return true;
+        }
+
+        self.flush();
+        loop {
+            if self.ready.is_ready() {
+                return true;
+            }
+
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return false;
+            }
+            std::thread::sleep(core::cmp::min(timeout - elapsed, POLL_INTERVAL));
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    ///Creates [metrics](https://crates.io/crates/metrics) recorder with meter `name` if metrics SDK is set up
+    ///
+    ///If `filter` is specified, all metrics labels are passed through it before becoming opentelemetry attributes.
+    ///
+    ///Units and descriptions specified via `describe_counter!`, `describe_gauge!` and `describe_histogram!` become metadata of opentelemetry instruments,
+    ///as long as metric is described before it is first used.
+    ///
+    ///Use it when you need to install recorder yourself (e.g. via `metrics::with_local_recorder`)
+    ///
+    ///Requires `metrics` feature
+    pub fn create_metrics_recorder(&self, name: &'static str, filter: Option<LabelFilter>) -> Option<MetricsRecorder> {
+        use crate::opentelemetry::metrics::MeterProvider;
+
+        self.metrics.as_ref().map(|metrics| {
+            let meter = metrics.meter(name);
+            let metrics = metrics_opentelemetry::OpenTelemetryMetrics::new(meter.clone());
+            MetricsRecorder {
+                inner: metrics_opentelemetry::OpenTelemetryRecorder::new(metrics),
+                meter,
+                histogram_descriptions: Default::default(),
+                histograms: Default::default(),
+                baggage_attrs: self.metrics_baggage_attrs.clone(),
+                dropped_attrs: self.metrics_dropped_attrs.clone(),
+                filter,
+            }
+        })
     }
 
     #[cfg(feature = "metrics")]
@@ -229,28 +1278,87 @@ impl Otlp {
     ///
     ///This function can only run once, subsequent calls will have no effect
     pub fn init_metrics_recorder(&self, name: &'static str) {
-        use crate::opentelemetry::metrics::MeterProvider;
+        if let Some(recorder) = self.create_metrics_recorder(name, None) {
+            let _ = crate::metrics::set_global_recorder(recorder);
+        }
+    }
 
-        if let Some(metrics) = self.metrics.as_ref() {
-            let meter = metrics.meter(name);
-            let metrics = metrics_opentelemetry::OpenTelemetryMetrics::new(meter);
-            let recorder = metrics_opentelemetry::OpenTelemetryRecorder::new(metrics);
+    #[cfg(feature = "metrics")]
+    ///Initializes [metrics](https://crates.io/crates/metrics) global recorder if metrics SDK is set up
+    ///
+    ///All metrics labels are passed through `filter` before becoming opentelemetry attributes.
+    ///
+    ///Requires `metrics` feature
+    ///
+    ///This function can only run once, subsequent calls will have no effect
+    pub fn init_metrics_recorder_with_filter(&self, name: &'static str, filter: LabelFilter) {
+        if let Some(recorder) = self.create_metrics_recorder(name, Some(filter)) {
             let _ = crate::metrics::set_global_recorder(recorder);
         }
     }
 
+    #[inline]
+    ///Creates layer aggregating all configured signals, using crate name as tracer name
+    ///
+    ///Use it when you assemble registry yourself, to place OTLP layers wherever you want in the stack
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::{Otlp, tracing_subscriber};
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+    ///use tracing_subscriber::layer::SubscriberExt;
+    ///
+    ///let destination = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:45081".into()
+    ///};
+    ///let otlp = Otlp::builder(destination).finish();
+    ///let registry = tracing_subscriber::registry().with(tracing_subscriber::filter::LevelFilter::INFO)
+    ///                                             .with(otlp.layers());
+    ///```
+    pub fn layers<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self) -> OtlpLayer<S> {
+        self.create_layer(Cow::Borrowed(env!("CARGO_PKG_NAME")))
+    }
+
     ///Creates new layer aggregating underlying SDK providers to instantiate corresponding layer with `name` for trace layer
     pub fn create_layer<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: Cow<'static, str>) -> OtlpLayer<S> {
         use opentelemetry::trace::TracerProvider;
 
         OtlpLayer {
             trace: self.trace.as_ref().map(|trace| tracing_opentelemetry::OpenTelemetryLayer::new(trace.tracer(name))),
-            logs: self.logs.as_ref().map(|logs| opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge::new(logs)),
+            logs: self.logs.as_ref().map(|logs| opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge::new(&crate::layer::BridgeLoggerProvider::new(logs.clone(), self.max_log_attributes))),
             #[cfg(feature = "tracing-metrics")]
-            metrics: self.metrics.as_ref().map(|metrics| tracing_opentelemetry::MetricsLayer::new(metrics.clone()))
+            metrics: self.metrics.as_ref().map(|metrics| tracing_opentelemetry::MetricsLayer::new(metrics.clone())),
+            native: crate::layer::native::NativeAttributesLayer::new(self.trace.as_ref().map(|_| self.integer_overflow)),
         }
     }
 
+    ///Creates standalone dispatcher with specified `name` used for tracer, without installing it anywhere
+    ///
+    ///Use it when multiple `Otlp` instances must co-exist within the same process.
+    ///For example library embedded into host application can send its telemetry to its own collector,
+    ///by executing its code within own dispatcher, while host's global subscriber is left untouched.
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::{Otlp, tracing};
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+    ///
+    ///let destination = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:45081".into()
+    ///};
+    ///let otlp = Otlp::builder(destination).finish();
+    ///let dispatch = otlp.create_dispatch("my-library");
+    ///tracing::dispatcher::with_default(&dispatch, || {
+    ///    tracing::info!("only visible to my-library's collector");
+    ///});
+    ///```
+    pub fn create_dispatch(&self, name: impl Into<Cow<'static, str>>) -> tracing::Dispatch {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let layer = self.create_layer(name.into());
+        tracing::Dispatch::new(tracing_subscriber::registry().with(layer))
+    }
+
     ///Finishes initializing `tracing_subscriber::registry::Registry` with specified `name` used for tracer
     ///
     ///Cannot be called more than once as `tracing` allows only single global instance
@@ -262,71 +1370,307 @@ impl Otlp {
 
         let layer = self.create_layer(name.into());
         registry.with(layer).init();
+        self.emit_startup_diagnostics();
     }
 
     ///Finishes initializing `tracing_subscriber::registry::Registry` with specified `name` used for tracer
     ///
-    ///Cannot be called more than once as `tracing` allows only single global instance
+    ///Unlike [init_tracing_subscriber](Self::init_tracing_subscriber) returns error if global subscriber is already set
     ///
     ///If feature `tracing-metrics` is enabled, then it shall record metrics via tracing events.
     ///For details refer to its [docs](https://docs.rs/tracing-opentelemetry/latest/tracing_opentelemetry/struct.MetricsLayer.html)
-    pub fn local_init_tracing_subscriber<R: Sync + Send + tracing::Subscriber + tracing_subscriber::layer::SubscriberExt + tracing_subscriber::util::SubscriberInitExt + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: impl Into<Cow<'static, str>>, registry: R) -> impl Drop {
+    pub fn try_init_tracing_subscriber<R: Sync + Send + tracing::Subscriber + tracing_subscriber::layer::SubscriberExt + tracing_subscriber::util::SubscriberInitExt + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: impl Into<Cow<'static, str>>, registry: R) -> Result<(), tracing_subscriber::util::TryInitError> {
         use tracing_subscriber::util::SubscriberInitExt;
 
         let layer = self.create_layer(name.into());
-        registry.with(layer).set_default()
+        registry.with(layer).try_init()?;
+        self.emit_startup_diagnostics();
+        Ok(())
     }
-}
 
-impl Drop for Otlp {
-    #[inline(always)]
-    fn drop(&mut self) {
-        let _ = self.shutdown(None);
+    ///Finishes initializing `tracing_subscriber::registry::Registry` with specified `name` used for tracer, limiting it to `level` which can be changed at runtime
+    ///
+    ///Behaves the same as [init_tracing_subscriber](Self::init_tracing_subscriber), but returns handle to change level of the subscriber (e.g. from admin endpoint)
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::{Otlp, tracing_subscriber, tracing};
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+    ///
+    ///let destination = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:45081".into()
+    ///};
+    ///let otlp = Otlp::builder(destination).with_logs(None).finish();
+    ///let handle = otlp.init_tracing_subscriber_with_reload("test", tracing_subscriber::registry(), tracing::Level::INFO);
+    ///assert!(tracing::enabled!(tracing::Level::INFO));
+    ///assert!(!tracing::enabled!(tracing::Level::DEBUG));
+    ///
+    ///handle.set_level(tracing::Level::WARN).expect("subscriber is alive");
+    ///assert!(!tracing::enabled!(tracing::Level::INFO));
+    ///```
+    pub fn init_tracing_subscriber_with_reload<R: Sync + Send + tracing::Subscriber + tracing_subscriber::layer::SubscriberExt + tracing_subscriber::util::SubscriberInitExt + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: impl Into<Cow<'static, str>>, registry: R, level: impl Into<tracing_subscriber::filter::LevelFilter>) -> ReloadHandle {
+        use tracing_subscriber::layer::SubscriberExt;
+        use tracing_subscriber::util::SubscriberInitExt;
+
+        let (level, handle) = tracing_subscriber::reload::Layer::new(level.into());
+        let layer = self.create_layer(name.into());
+        registry.with(level).with(layer).init();
+        self.emit_startup_diagnostics();
+        ReloadHandle::new(handle)
     }
-}
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-///Possible communication protocol
-pub enum Protocol {
-    ///GRPC
-    Grpc,
-    ///HTTP
-    HttpBinary,
-    ///HTTP
-    HttpJson,
-    ///Datadog agent exporter
+    #[cfg(feature = "fmt")]
+    ///Finishes initializing `tracing_subscriber::registry::Registry` with specified `name` used for tracer, printing events to stdout in specified `style`
     ///
-    ///In case of traces expects valid network address to send data
+    ///Behaves the same as [init_tracing_subscriber](Self::init_tracing_subscriber).
+    ///Use [console::layer](crate::console::layer) to assemble subscriber manually.
     ///
-    ///In case of logs it can be `file://<full path>` to specify path to append logs. Otherwise `url` is ignored and `stdout` shall be used.
-    ///Note that you're advised to disable attachment of events/logs to the span in this case
-    DatadogAgent,
-}
-
-impl Protocol {
-    #[allow(unused)]
-    #[inline]
-    const fn into_otel(self) -> opentelemetry_otlp::Protocol {
-        match self {
-            Self::Grpc => opentelemetry_otlp::Protocol::Grpc,
-            Self::HttpJson => opentelemetry_otlp::Protocol::HttpJson,
-            Self::HttpBinary => opentelemetry_otlp::Protocol::HttpBinary,
-            Self::DatadogAgent => unreachable!(),
-        }
+    ///```rust
+    ///use tracing_opentelemetry_setup::{Otlp, tracing_subscriber, tracing};
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+    ///use tracing_opentelemetry_setup::console::FmtStyle;
+    ///
+    ///let destination = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:45081".into()
+    ///};
+    ///let otlp = Otlp::builder(destination).finish();
+    ///otlp.init_tracing_subscriber_with_fmt("test", tracing_subscriber::registry(), FmtStyle::Pretty);
+    ///tracing::info!("printed to stdout and exported");
+    ///```
+    pub fn init_tracing_subscriber_with_fmt<R: Sync + Send + tracing::Subscriber + tracing_subscriber::layer::SubscriberExt + tracing_subscriber::util::SubscriberInitExt + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: impl Into<Cow<'static, str>>, registry: R, style: crate::console::FmtStyle) {
+        use tracing_subscriber::layer::SubscriberExt;
+        use tracing_subscriber::util::SubscriberInitExt;
 
+        let layer = self.create_layer(name.into());
+        registry.with(crate::console::layer(style)).with(layer).init();
+        self.emit_startup_diagnostics();
     }
-}
 
-///Describes destination configuration
-pub struct Destination<'a> {
+    ///Initializes `tracing_subscriber::registry::Registry` with specified `name` used for tracer as default subscriber of the current thread
+    ///
+    ///Subscriber remains default until returned guard is dropped, without touching global default.
+    ///This is useful to scope telemetry to a thread or test.
+    ///
+    ///If feature `tracing-metrics` is enabled, then it shall record metrics via tracing events.
+    ///For details refer to its [docs](https://docs.rs/tracing-opentelemetry/latest/tracing_opentelemetry/struct.MetricsLayer.html)
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::{Otlp, tracing_subscriber, tracing};
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+    ///
+    ///let destination = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:45081".into()
+    ///};
+    ///let otlp = Otlp::builder(destination).finish();
+    ///let guard = otlp.local_init_tracing_subscriber("test", tracing_subscriber::registry());
+    ///tracing::info!("scoped to current thread");
+    ///drop(guard);
+    ///```
+    pub fn local_init_tracing_subscriber<R: Sync + Send + tracing::Subscriber + tracing_subscriber::layer::SubscriberExt + tracing_subscriber::util::SubscriberInitExt + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, name: impl Into<Cow<'static, str>>, registry: R) -> tracing::subscriber::DefaultGuard {
+        use tracing_subscriber::util::SubscriberInitExt;
+
+        let layer = self.create_layer(name.into());
+        let guard = registry.with(layer).set_default();
+        self.emit_startup_diagnostics();
+        guard
+    }
+}
+
+impl Otlp {
+    ///Shuts down on separate thread, waiting for it no longer than 1 second
+    ///
+    ///Used when thread is panicking as collector might be the reason of panic, in which case regular shutdown can block for too long
+    fn emergency_shutdown(&mut self) {
+        const LIMIT: time::Duration = time::Duration::from_secs(1);
+
+        let logs = self.logs.take();
+        let trace = self.trace.take();
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        let metrics = self.metrics.take();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let shutdown = std::thread::Builder::new().name("otlp-emergency-shutdown".to_owned()).spawn(move || {
+            let mut otlp = Self::new();
+            otlp.logs = logs;
+            otlp.trace = trace;
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            {
+                otlp.metrics = metrics;
+            }
+
+            let _ = otlp.shutdown(ShutdownMode::Timeout(LIMIT));
+            let _ = sender.send(());
+        });
+
+        if shutdown.is_ok() {
+            let _ = receiver.recv_timeout(LIMIT);
+        }
+    }
+}
+
+impl Drop for Otlp {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.emergency_shutdown();
+        } else {
+            let _ = self.shutdown(ShutdownMode::DEFAULT);
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Possible communication protocol
+pub enum Protocol {
+    ///GRPC
+    Grpc,
+    ///HTTP
+    HttpBinary,
+    ///HTTP
+    HttpJson,
+    ///Datadog agent exporter
+    ///
+    ///In case of traces expects valid network address to send data
+    ///
+    ///In case of logs it can be `file://<full path>` to specify path to append logs or `tcp://<host>[:<port>]` to send logs to agent's TCP intake (port 10518 by default).
+    ///Log file can be rotated by size or age using `LogsSettings::with_file_rotation`.
+    ///Otherwise `url` is ignored and `stdout` shall be used.
+    ///Note that you're advised to disable attachment of events/logs to the span in this case
+    ///
+    ///Datadog unified service tagging is applied to resource of every signal from `DD_SERVICE`, `DD_ENV`, `DD_VERSION` and `DD_TAGS`,
+    ///taking priority over provided [Attributes]. Log records are additionally annotated with `service`, `ddsource` and `ddtags` fields.
+    ///
+    ///In case of metrics, data is sent to DogStatsD server over UDP using host of `url` and port `DD_DOGSTATSD_PORT` (8125 by default).
+    ///`url` can be `udp://<host>:<port>` or `unix://<full path>` to specify DogStatsD address directly, while `DD_DOGSTATSD_URL` overrides it.
+    ///Histograms are sent as distributions and metrics temporality is ignored as DogStatsD expects counters to be deltas.
+    DatadogAgent,
+    ///Human readable output for local development, which doesn't require running collector
+    ///
+    ///`url` can be `file://<full path>` to specify path to append output or `stderr://` to write into `stderr`. Otherwise `url` is ignored and `stdout` shall be used.
+    ///
+    ///Logs can be written as JSON lines instead, see `LogsSettings::with_stdout_format`, to be tailed by collector
+    Stdout,
+    ///OTLP file exporter, appending every export as single line of OTLP/JSON encoded request
+    ///
+    ///`url` is path to the file, optionally prefixed with `file://`.
+    ///Lines follow OpenTelemetry file exporter specification, hence can be replayed to collector later.
+    File,
+    ///In-memory exporter to inspect emitted telemetry in tests, requires `testing` feature
+    ///
+    ///`url` is ignored. Use `Otlp::builder_in_memory` to access captured data
+    InMemory,
+}
+
+impl Protocol {
+    #[inline]
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Grpc => "grpc",
+            Self::HttpBinary => "http/protobuf",
+            Self::HttpJson => "http/json",
+            Self::DatadogAgent => "datadog",
+            Self::Stdout => "stdout",
+            Self::File => "file",
+            Self::InMemory => "in_memory",
+        }
+    }
+
+    #[allow(unused)]
+    #[inline]
+    const fn into_otel(self) -> opentelemetry_otlp::Protocol {
+        match self {
+            Self::Grpc => opentelemetry_otlp::Protocol::Grpc,
+            Self::HttpJson => opentelemetry_otlp::Protocol::HttpJson,
+            Self::HttpBinary => opentelemetry_otlp::Protocol::HttpBinary,
+            Self::DatadogAgent | Self::Stdout | Self::File | Self::InMemory => unreachable!(),
+        }
+
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Describes how `logs` and `trace` data is handed over to exporter
+pub enum ExportMode {
+    ///Data is accumulated and exported in batches by background thread
+    ///
+    ///This is default mode
+    Batch,
+    ///Data is exported synchronously as soon as it is produced
+    ///
+    ///Suitable for short-lived processes (e.g. CLI or AWS Lambda) where process can exit before background thread flushes data.
+    ///Note that every span/log record will block on export, so it is not suitable for high-throughput applications
+    Simple,
+    ///Data is accumulated until [Otlp::tick] is called, which exports it on the calling thread
+    ///
+    ///No background threads are spawned (including for metrics), which is suitable for Windows services and GUI applications,
+    ///where threads and event loop are managed by host framework.
+    ///Data is dropped once queue is full, so make sure to call [Otlp::tick] periodically (e.g. on timer)
+    ///
+    ///Combine it with [Otlp::local_init_tracing_subscriber] or [Otlp::init_tracing_subscriber], which never install signal handlers,
+    ///and call [Otlp::shutdown] when host framework stops application.
+    ///Note that `grpc`, `http-reqwest` (async reqwest client) and `http-hyper` exporters still require to run `tick` within tokio runtime context,
+    ///use `http-reqwest-blocking` if there is no runtime
+    Manual,
+}
+
+///Describes destination configuration
+pub struct Destination<'a> {
     ///protocol to use
     pub protocol: Protocol,
     ///destination URL
     ///
     ///When `Http*` protocol is used, assumes `<url>/metrics` | `<url>/logs` | `<url>/traces` to be available
+    ///
+    ///When `Grpc` protocol is used, `unix://<full path>` can be specified to connect via Unix domain socket (requires tokio runtime)
     pub url: Cow<'a, str>,
 }
 
+///[Destination] with its own settings, used by [Builder::with_additional_destination] and [Builder::with_fallback_destination]
+///
+///Headers specified via [Builder::with_header] apply only to primary destination, hence other destinations must specify their own
+///
+///```rust
+///use tracing_opentelemetry_setup::builder::{Destination, Protocol, Target};
+///
+///let saas = Destination {
+///    protocol: Protocol::HttpBinary,
+///    url: "https://otlp.example.com".into()
+///};
+///let saas = Target::new(saas).with_header("api-key", "secret");
+///```
+pub struct Target<'a> {
+    #[cfg_attr(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")), allow(unused))]
+    destination: Destination<'a>,
+    headers: Vec<(String, String)>,
+}
+
+impl<'a> Target<'a> {
+    #[inline(always)]
+    ///Creates new target without headers
+    pub const fn new(destination: Destination<'a>) -> Self {
+        Self {
+            destination,
+            headers: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Specify header to be included when exporting to this destination
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+}
+
+impl<'a> From<Destination<'a>> for Target<'a> {
+    #[inline(always)]
+    fn from(destination: Destination<'a>) -> Self {
+        Self::new(destination)
+    }
+}
+
 ///Opentelemetry integration builder
 pub struct Builder<'a> {
     destination: Destination<'a>,
@@ -334,8 +1678,42 @@ pub struct Builder<'a> {
     headers: Vec<(String, String)>,
     timeout: time::Duration,
     compression: bool,
+    retry: Option<RetrySettings>,
+    export_mode: ExportMode,
+    startup_summary: bool,
+    #[cfg(feature = "datadog")]
+    datadog_probe: bool,
+    logs: Option<(Option<Attributes>, LogsSettings)>,
+    trace: Option<(Option<Attributes>, TraceSettings)>,
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    metrics: Option<(Option<Attributes>, MetricsSettings)>,
+    kill_switch: Option<std::path::PathBuf>,
+    paused: Option<Arc<AtomicBool>>,
+    self_telemetry: bool,
+    additional_destinations: Vec<(Signal, Target<'a>)>,
+    fallback_destination: Option<Target<'a>>,
+    failover: FailoverSettings,
+    span_exporter_map: Option<Box<dyn Fn(crate::exporter::SpanExporter) -> crate::exporter::SpanExporter + Send + Sync>>,
+    log_exporter_map: Option<Box<dyn Fn(crate::exporter::LogExporter) -> crate::exporter::LogExporter + Send + Sync>>,
+    log_processor_hook: Option<LogHook>,
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    metric_exporter_map: Option<Box<dyn Fn(crate::exporter::MetricExporter) -> crate::exporter::MetricExporter + Send + Sync>>,
+    #[cfg(feature = "grpc")]
+    grpc_channel: Option<tonic::transport::Channel>,
+    #[cfg(feature = "grpc")]
+    grpc_interceptor: Option<GrpcInterceptor>,
+    #[cfg(feature = "http")]
+    http_client: Option<HttpClient>,
+    #[cfg(any(feature = "http-reqwest", feature = "http-reqwest-blocking"))]
+    http_proxy: Option<String>,
+    #[cfg(feature = "testing")]
+    in_memory: std::sync::OnceLock<crate::testing::InMemoryExporters>,
 }
 
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+///Exporter with all builder's wrappers applied
+type WrappedExporter<T> = crate::pause::PauseExporter<crate::telemetry::SelfTelemetryExporter<crate::ready::ReadyExporter<crate::retry::RetryExporter<T>>>>;
+
 macro_rules! declare_trace_limits {
     ({$($name:ident,)+}) => {
         struct SpanLimits {
@@ -418,6 +1796,381 @@ impl opentelemetry_sdk::trace::ShouldSample for AlwaysOffSampler {
     }
 }
 
+#[derive(Clone, Debug)]
+///Handle to adjust trace sample rate at runtime
+///
+///Created via [TraceSettings::with_dynamic_sampling]
+pub struct SamplerHandle {
+    rate: Arc<AtomicU64>,
+}
+
+impl SamplerHandle {
+    #[inline(always)]
+    fn new(sample_rate: f64) -> Self {
+        Self {
+            rate: Arc::new(AtomicU64::new(sample_rate.clamp(0.0, 1.0).to_bits())),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns currently used sample rate
+    pub fn sample_rate(&self) -> f64 {
+        f64::from_bits(self.rate.load(Ordering::Relaxed))
+    }
+
+    #[inline(always)]
+    ///Sets new sample rate, applied to all new root spans (and to every span if parent sampling decision is not respected)
+    ///
+    ///Value is clamped to range `0.0..=1.0`
+    pub fn set_sample_rate(&self, sample_rate: f64) {
+        self.rate.store(sample_rate.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+#[derive(Clone, Debug)]
+struct DynamicSampler(SamplerHandle);
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl opentelemetry_sdk::trace::ShouldSample for DynamicSampler {
+    #[inline(always)]
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        let sampler = opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(self.0.sample_rate());
+        sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+#[derive(Clone, Debug)]
+///Type erased user provided sampler
+struct CustomSampler(Box<dyn opentelemetry_sdk::trace::ShouldSample>);
+
+impl opentelemetry_sdk::trace::ShouldSample for CustomSampler {
+    #[inline(always)]
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        self.0.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+#[derive(Clone, Debug)]
+///Sampler respecting decision of parent, unless remote parent deferred decision to receiver (e.g. B3 or X-Ray header without sampling state)
+struct ParentBasedSampler {
+    root: Box<dyn opentelemetry_sdk::trace::ShouldSample>,
+    parent_based: opentelemetry_sdk::trace::Sampler,
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl ParentBasedSampler {
+    ///Trace flag set by propagators when sampling decision is deferred
+    const DEFERRED: opentelemetry::trace::TraceFlags = opentelemetry::trace::TraceFlags::new(0x02);
+
+    #[inline(always)]
+    fn new(root: Box<dyn opentelemetry_sdk::trace::ShouldSample>) -> Self {
+        Self {
+            parent_based: opentelemetry_sdk::trace::Sampler::ParentBased(root.clone()),
+            root,
+        }
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl opentelemetry_sdk::trace::ShouldSample for ParentBasedSampler {
+    #[inline]
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        use opentelemetry::trace::TraceContextExt;
+
+        let is_deferred = parent_context.is_some_and(|ctx| {
+            let span = ctx.span();
+            let span = span.span_context();
+            span.is_remote() && !span.is_sampled() && span.trace_flags() & Self::DEFERRED == Self::DEFERRED
+        });
+        match is_deferred {
+            true => self.root.should_sample(parent_context, trace_id, name, span_kind, attributes, links),
+            false => self.parent_based.should_sample(parent_context, trace_id, name, span_kind, attributes, links),
+        }
+    }
+}
+
+type SamplingHook = Arc<dyn Fn(&str, bool) + Send + Sync>;
+pub(crate) type LogHook = Arc<dyn Fn(&mut opentelemetry_sdk::logs::SdkLogRecord) + Send + Sync>;
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+#[derive(Clone)]
+struct HookSampler<T> {
+    inner: T,
+    hook: SamplingHook,
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl<T: fmt::Debug> fmt::Debug for HookSampler<T> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("HookSampler").field("inner", &self.inner).finish()
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl<T: opentelemetry_sdk::trace::ShouldSample + Clone + 'static> opentelemetry_sdk::trace::ShouldSample for HookSampler<T> {
+    #[inline]
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        use opentelemetry::trace::TraceContextExt;
+
+        let result = self.inner.should_sample(parent_context, trace_id, name, span_kind, attributes, links);
+        let is_root = match parent_context {
+            Some(ctx) => !ctx.has_active_span() || ctx.span().span_context().is_remote(),
+            None => true,
+        };
+        if is_root {
+            (self.hook)(name, result.decision == opentelemetry::trace::SamplingDecision::RecordAndSample);
+        }
+        result
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+#[inline(always)]
+fn with_sampler<T: opentelemetry_sdk::trace::ShouldSample + Clone + 'static>(builder: opentelemetry_sdk::trace::TracerProviderBuilder, sampler: T, hook: Option<SamplingHook>) -> opentelemetry_sdk::trace::TracerProviderBuilder {
+    match hook {
+        Some(hook) => builder.with_sampler(HookSampler {
+            inner: sampler,
+            hook,
+        }),
+        None => builder.with_sampler(sampler),
+    }
+}
+
+///Spawns thread watching kill switch `path`, returning flag that is set while it exists
+fn spawn_kill_switch_watcher(path: std::path::PathBuf) -> Option<Arc<AtomicBool>> {
+    const INTERVAL: time::Duration = time::Duration::from_secs(1);
+
+    let result = Arc::new(AtomicBool::new(path.exists()));
+    let paused = Arc::downgrade(&result);
+    let thread_path = path.clone();
+
+    let spawn = std::thread::Builder::new().name("otlp-kill-switch".to_owned()).spawn(move || {
+        let path = thread_path;
+        //Watcher stops once all exporters are gone
+        while let Some(paused) = paused.upgrade() {
+            let is_paused = path.exists();
+            if paused.swap(is_paused, Ordering::AcqRel) != is_paused {
+                match is_paused {
+                    true => tracing::warn!(target: "tracing_opentelemetry_setup", path = %path.display(), "Kill switch is on: telemetry export is paused"),
+                    false => tracing::info!(target: "tracing_opentelemetry_setup", path = %path.display(), "Kill switch is off: telemetry export is resumed"),
+                }
+            }
+            drop(paused);
+            std::thread::sleep(INTERVAL);
+        }
+    });
+    match spawn {
+        Ok(_) => Some(result),
+        Err(error) => {
+            tracing::warn!(target: "tracing_opentelemetry_setup", path = %path.display(), %error, "Failed to spawn kill switch watcher: kill switch is not applied");
+            None
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct BatchSettings {
+    max_queue_size: Option<usize>,
+    max_export_batch_size: Option<usize>,
+    scheduled_delay: Option<time::Duration>,
+    #[cfg(feature = "rt-tokio")]
+    max_concurrent_exports: Option<usize>,
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+///Common interface of SDK's batch configuration builders for traces and logs
+trait BatchConfigBuilder: Default {
+    fn with_max_queue_size(self, max_queue_size: usize) -> Self;
+    fn with_max_export_batch_size(self, max_export_batch_size: usize) -> Self;
+    fn with_scheduled_delay(self, scheduled_delay: time::Duration) -> Self;
+}
+
+macro_rules! impl_batch_config_builder {
+    ($builder:ty) => {
+        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+        impl BatchConfigBuilder for $builder {
+            #[inline(always)]
+            fn with_max_queue_size(self, max_queue_size: usize) -> Self {
+                <$builder>::with_max_queue_size(self, max_queue_size)
+            }
+
+            #[inline(always)]
+            fn with_max_export_batch_size(self, max_export_batch_size: usize) -> Self {
+                <$builder>::with_max_export_batch_size(self, max_export_batch_size)
+            }
+
+            #[inline(always)]
+            fn with_scheduled_delay(self, scheduled_delay: time::Duration) -> Self {
+                <$builder>::with_scheduled_delay(self, scheduled_delay)
+            }
+        }
+    };
+}
+
+impl_batch_config_builder!(opentelemetry_sdk::trace::BatchConfigBuilder);
+impl_batch_config_builder!(opentelemetry_sdk::logs::BatchConfigBuilder);
+
+impl BatchSettings {
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Default max number of items buffered for export
+    const DEFAULT_MAX_QUEUE_SIZE: usize = 2048;
+
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            max_queue_size: None,
+            max_export_batch_size: None,
+            scheduled_delay: None,
+            #[cfg(feature = "rt-tokio")]
+            max_concurrent_exports: None,
+        }
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    #[inline(always)]
+    const fn max_queue_size(&self) -> usize {
+        match self.max_queue_size {
+            Some(max_queue_size) => max_queue_size,
+            None => Self::DEFAULT_MAX_QUEUE_SIZE,
+        }
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Creates SDK's batch configuration builder of either traces or logs
+    fn builder<B: BatchConfigBuilder>(&self) -> B {
+        let mut builder = B::default();
+        if let Some(max_queue_size) = self.max_queue_size {
+            builder = builder.with_max_queue_size(max_queue_size);
+        }
+        if let Some(max_export_batch_size) = self.max_export_batch_size {
+            builder = builder.with_max_export_batch_size(max_export_batch_size);
+        }
+        if let Some(scheduled_delay) = self.scheduled_delay {
+            builder = builder.with_scheduled_delay(scheduled_delay);
+        }
+        builder
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    #[inline(always)]
+    ///Returns whether exports are performed concurrently on async runtime instead of dedicated thread
+    const fn is_concurrent(&self) -> bool {
+        #[cfg(feature = "rt-tokio")]
+        if let Some(max_concurrent_exports) = self.max_concurrent_exports {
+            return max_concurrent_exports > 1;
+        }
+        false
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+///Representation of integer span attributes that do not fit into OpenTelemetry's `i64`
+///
+///Integer fields (including `u64`, `i128` and `u128`) are always exported as native integers when value fits into `i64`
+pub enum IntegerOverflow {
+    #[default]
+    ///Exports value as decimal string
+    String,
+    ///Exports value as double, potentially losing precision
+    Double,
+    ///Exports value clamped to range of `i64`
+    Saturate,
+}
+
+#[cfg(feature = "datadog")]
+#[derive(Clone, Debug)]
+///Information reported by `/info` endpoint of Datadog agent, see [Builder::with_datadog_probe]
+pub struct DatadogAgentInfo {
+    ///Agent version
+    pub version: String,
+    ///Endpoints supported by agent (e.g. `/v0.5/traces`)
+    pub endpoints: Vec<String>,
+}
+
+#[cfg(feature = "datadog")]
+///Endpoint used by trace exporter
+const DATADOG_TRACES_ENDPOINT: &str = "/v0.5/traces";
+
+#[cfg(feature = "datadog")]
+type DatadogMapping = Arc<opentelemetry_datadog::FieldMappingFn>;
+
+#[cfg(feature = "datadog")]
+#[inline(always)]
+fn datadog_mapping(mapping: DatadogMapping) -> impl for<'a> Fn(&'a opentelemetry_sdk::trace::SpanData, &'a opentelemetry_datadog::ModelConfig) -> &'a str + Send + Sync + 'static {
+    move |span, config| mapping(span, config)
+}
+
+#[cfg(feature = "datadog")]
+#[derive(Clone, Default)]
+///Datadog agent trace exporter configuration, used with [Protocol::DatadogAgent]
+///
+///Mapping callbacks customize `name`, `resource` and `service` fields of Datadog spans.
+///See [FieldMappingFn](opentelemetry_datadog::FieldMappingFn) for details
+///
+///```rust
+///use tracing_opentelemetry_setup::builder::{DatadogSettings, TraceSettings};
+///
+///let datadog = DatadogSettings::new().with_resource_mapping(|span, _| {
+///    //Use route of HTTP spans as resource
+///    let route = span.attributes.iter().find(|attr| attr.key.as_str() == "http.route");
+///    match route.map(|attr| &attr.value) {
+///        Some(tracing_opentelemetry_setup::opentelemetry::Value::String(route)) => route.as_str(),
+///        _ => span.name.as_ref(),
+///    }
+///});
+///let settings = TraceSettings::new(1.0).with_datadog(datadog);
+///```
+pub struct DatadogSettings {
+    name: Option<DatadogMapping>,
+    resource: Option<DatadogMapping>,
+    service_name: Option<DatadogMapping>,
+    measured: bool,
+}
+
+#[cfg(feature = "datadog")]
+impl DatadogSettings {
+    #[inline(always)]
+    ///Creates new instance with default mapping of exporter
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            resource: None,
+            service_name: None,
+            measured: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Marks every span as measured (`_dd.measured`), making Datadog agent to compute trace metrics for all spans rather than only for service entry spans
+    ///
+    ///Sampling priority (`_sampling_priority_v1`) is always set according to sampling decision.
+    pub const fn with_measured(mut self, measured: bool) -> Self {
+        self.measured = measured;
+        self
+    }
+
+    ///Specifies how to derive `name` of Datadog span
+    pub fn with_name_mapping(mut self, mapping: impl for<'a> Fn(&'a opentelemetry_sdk::trace::SpanData, &'a opentelemetry_datadog::ModelConfig) -> &'a str + Send + Sync + 'static) -> Self {
+        self.name = Some(Arc::new(mapping));
+        self
+    }
+
+    ///Specifies how to derive `resource` of Datadog span, which is span name by default
+    pub fn with_resource_mapping(mut self, mapping: impl for<'a> Fn(&'a opentelemetry_sdk::trace::SpanData, &'a opentelemetry_datadog::ModelConfig) -> &'a str + Send + Sync + 'static) -> Self {
+        self.resource = Some(Arc::new(mapping));
+        self
+    }
+
+    ///Specifies how to derive `service` of Datadog span, which is service name by default
+    pub fn with_service_name_mapping(mut self, mapping: impl for<'a> Fn(&'a opentelemetry_sdk::trace::SpanData, &'a opentelemetry_datadog::ModelConfig) -> &'a str + Send + Sync + 'static) -> Self {
+        self.service_name = Some(Arc::new(mapping));
+        self
+    }
+}
+
 ///Trace configuration
 pub struct TraceSettings {
     #[allow(unused)]
@@ -427,6 +2180,18 @@ pub struct TraceSettings {
     limits: SpanLimits,
     #[allow(unused)]
     respect_parent: bool,
+    batch: BatchSettings,
+    max_span_depth: Option<u32>,
+    dynamic_sampling: Option<SamplerHandle>,
+    sampling_hook: Option<SamplingHook>,
+    sampler: Option<CustomSampler>,
+    sampling_rules: Vec<(crate::sampling::SamplingMatcher, f64)>,
+    #[cfg(feature = "jaeger-remote-sampler")]
+    remote_sampler: Option<(String, time::Duration)>,
+    #[cfg(feature = "datadog")]
+    datadog: DatadogSettings,
+    tail_sampling: Option<crate::sampling::TailSampling>,
+    integer_overflow: IntegerOverflow,
 }
 
 macro_rules! set_trace_limit {
@@ -442,9 +2207,108 @@ impl TraceSettings {
             sample_rate,
             limits: SpanLimits::new(),
             respect_parent: true,
+            batch: BatchSettings::new(),
+            max_span_depth: None,
+            dynamic_sampling: None,
+            sampling_hook: None,
+            sampler: None,
+            sampling_rules: Vec::new(),
+            #[cfg(feature = "jaeger-remote-sampler")]
+            remote_sampler: None,
+            #[cfg(feature = "datadog")]
+            datadog: DatadogSettings::new(),
+            tail_sampling: None,
+            integer_overflow: IntegerOverflow::String,
         }
     }
 
+    ///Specifies how to export integer span attributes that do not fit into `i64`. Defaults to [IntegerOverflow::String]
+    pub const fn with_integer_overflow(mut self, integer_overflow: IntegerOverflow) -> Self {
+        self.integer_overflow = integer_overflow;
+        self
+    }
+
+    #[cfg(feature = "datadog")]
+    ///Specifies Datadog agent exporter configuration, which only applies to [Protocol::DatadogAgent]
+    pub fn with_datadog(mut self, datadog: DatadogSettings) -> Self {
+        self.datadog = datadog;
+        self
+    }
+
+    #[cfg(feature = "jaeger-remote-sampler")]
+    ///Uses Jaeger remote sampler, fetching per operation sampling strategies from `url` every `poll_interval`
+    ///
+    ///Sampler that would be used otherwise, is used until strategies are fetched or when remote is unavailable.
+    ///Strategies are fetched for `service.name` attribute.
+    ///
+    ///Requires `jaeger-remote-sampler` feature. Trace must be configured within tokio runtime
+    pub fn with_remote_sampler(mut self, url: impl Into<String>, poll_interval: time::Duration) -> Self {
+        self.remote_sampler = Some((url.into(), poll_interval));
+        self
+    }
+
+    ///Adds rule to sample spans matching `matcher` with `sample_rate` instead of default sampling
+    ///
+    ///Rules are evaluated in order of addition and the first matching rule wins.
+    ///If parent sampling decision is respected, rules apply only to root spans
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::builder::TraceSettings;
+    ///use tracing_opentelemetry_setup::sampling::SamplingMatcher;
+    ///
+    ///let settings = TraceSettings::new(0.1).with_sampling_rule(SamplingMatcher::span_name("GET /healthz"), 0.0)
+    ///                                      .with_sampling_rule(SamplingMatcher::target("checkout"), 1.0);
+    ///```
+    pub fn with_sampling_rule(mut self, matcher: crate::sampling::SamplingMatcher, sample_rate: f64) -> Self {
+        self.sampling_rules.push((matcher, sample_rate.clamp(0.0, 1.0)));
+        self
+    }
+
+    ///Sets custom `sampler` to be used instead of ratio based sampling (e.g. per tenant or per route sampling)
+    ///
+    ///If parent sampling decision is respected, `sampler` is used only for root spans.
+    ///Takes precedence over [with_dynamic_sampling](Self::with_dynamic_sampling)
+    pub fn with_sampler(mut self, sampler: impl opentelemetry_sdk::trace::ShouldSample + 'static) -> Self {
+        self.sampler = Some(CustomSampler(Box::new(sampler)));
+        self
+    }
+
+    ///Enables tail sampling, exporting only traces that contain errors or slow spans
+    ///
+    ///See [TailSampling](crate::sampling::TailSampling) for details
+    pub const fn with_tail_sampling(mut self, tail_sampling: crate::sampling::TailSampling) -> Self {
+        self.tail_sampling = Some(tail_sampling);
+        self
+    }
+
+    ///Sets `hook` to be invoked with sampling decision of every root span
+    ///
+    ///Hook receives span name and whether span is sampled. Span is considered root when it has no parent within the process.
+    ///Use it for ingestion cost accounting or to validate sampling rules. Hook is called on hot path so it should be cheap
+    pub fn with_sampling_hook(mut self, hook: impl Fn(&str, bool) + Send + Sync + 'static) -> Self {
+        self.sampling_hook = Some(Arc::new(hook));
+        self
+    }
+
+    ///Enables adjusting sample rate at runtime, returning handle to change it
+    ///
+    ///Initial sample rate is the one provided in [TraceSettings::new]
+    pub fn with_dynamic_sampling(mut self) -> (Self, SamplerHandle) {
+        let handle = SamplerHandle::new(self.sample_rate);
+        self.dynamic_sampling = Some(handle.clone());
+        (self, handle)
+    }
+
+    ///Limits nesting depth of spans within single process to `max_span_depth`, dropping deeper spans
+    ///
+    ///Number of dropped spans is recorded as `otel.dropped_descendant_spans` attribute of the deepest retained ancestor,
+    ///which is exported only once its dropped descendants finish.
+    ///Use it to protect exporter and backend against runaway recursion. Not limited by default
+    pub const fn with_max_span_depth(mut self, max_span_depth: u32) -> Self {
+        self.max_span_depth = Some(max_span_depth);
+        self
+    }
+
     ///Specifies whether to respect parent trace's sampling decision. Defaults to `true`
     pub const fn with_respect_parent_sampling(mut self, value: bool) -> Self {
         self.respect_parent = value;
@@ -469,326 +2333,2177 @@ impl TraceSettings {
         self
     }
 
-    ///The max attributes that can be added into an Event. Defaults to 128
-    pub const fn with_max_attributes_per_event(mut self, with_max_attributes_per_event: u32) -> Self {
-        set_trace_limit!(self.limits, with_max_attributes_per_event);
-        self
+    ///The max attributes that can be added into an Event. Defaults to 128
+    pub const fn with_max_attributes_per_event(mut self, with_max_attributes_per_event: u32) -> Self {
+        set_trace_limit!(self.limits, with_max_attributes_per_event);
+        self
+    }
+
+    ///The max attributes that can be added into a Link. Defaults to 128
+    pub const fn with_max_attributes_per_link(mut self, with_max_attributes_per_link: u32) -> Self {
+        set_trace_limit!(self.limits, with_max_attributes_per_link);
+        self
+    }
+
+    ///The max number of spans buffered for export. Spans are dropped once queue is full. Defaults to 2048
+    pub const fn with_max_queue_size(mut self, max_queue_size: usize) -> Self {
+        self.batch.max_queue_size = Some(max_queue_size);
+        self
+    }
+
+    ///The max number of spans exported in single batch. Defaults to 512
+    pub const fn with_max_export_batch_size(mut self, max_export_batch_size: usize) -> Self {
+        self.batch.max_export_batch_size = Some(max_export_batch_size);
+        self
+    }
+
+    ///The delay between two consecutive exports. Defaults to 5 seconds
+    pub const fn with_scheduled_delay(mut self, scheduled_delay: time::Duration) -> Self {
+        self.batch.scheduled_delay = Some(scheduled_delay);
+        self
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    ///The max number of batches exported concurrently. Defaults to 1
+    ///
+    ///When greater than 1, batches are exported by tasks spawned on tokio runtime instead of dedicated thread,
+    ///hence `Otlp` must be built within tokio runtime and [retries](Builder::with_retry) are not performed.
+    pub const fn with_max_concurrent_exports(mut self, max_concurrent_exports: usize) -> Self {
+        self.batch.max_concurrent_exports = Some(max_concurrent_exports);
+        self
+    }
+}
+
+const DEFAULT_SEVERITY: [opentelemetry::logs::Severity; 5] = [
+    opentelemetry::logs::Severity::Trace,
+    opentelemetry::logs::Severity::Debug,
+    opentelemetry::logs::Severity::Info,
+    opentelemetry::logs::Severity::Warn,
+    opentelemetry::logs::Severity::Error,
+];
+
+const fn level_index(level: tracing::Level) -> usize {
+    match level {
+        tracing::Level::TRACE => 0,
+        tracing::Level::DEBUG => 1,
+        tracing::Level::INFO => 2,
+        tracing::Level::WARN => 3,
+        tracing::Level::ERROR => 4,
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+fn truncate_log_body(record: &mut opentelemetry_sdk::logs::SdkLogRecord, max: usize) {
+    use opentelemetry::logs::LogRecord;
+
+    let body = match record.body() {
+        Some(opentelemetry::logs::AnyValue::String(body)) if body.as_str().len() > max => body.as_str(),
+        _ => return,
+    };
+    let mut len = max;
+    while !body.is_char_boundary(len) {
+        len -= 1;
+    }
+    let body = body[..len].to_owned();
+    record.set_body(body.into());
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+//Creates hook applying settings to records created by `opentelemetry-appender-tracing`, unless settings are default
+//
+//Attributes are limited by `crate::layer::BridgeLoggerProvider` instead, as SDK provides no way to remove them from record
+fn settings_hook(settings: &LogsSettings) -> Option<LogHook> {
+    let mapping = settings.severity;
+    let max_body_length = settings.max_body_length;
+    if mapping == DEFAULT_SEVERITY && max_body_length.is_none() {
+        return None;
+    }
+
+    Some(Arc::new(move |record: &mut opentelemetry_sdk::logs::SdkLogRecord| {
+        use opentelemetry::logs::LogRecord;
+
+        if let Some(max) = max_body_length {
+            truncate_log_body(record, max);
+        }
+
+        let level = match record.severity_number() {
+            Some(opentelemetry::logs::Severity::Trace) => tracing::Level::TRACE,
+            Some(opentelemetry::logs::Severity::Debug) => tracing::Level::DEBUG,
+            Some(opentelemetry::logs::Severity::Info) => tracing::Level::INFO,
+            Some(opentelemetry::logs::Severity::Warn) => tracing::Level::WARN,
+            Some(opentelemetry::logs::Severity::Error) => tracing::Level::ERROR,
+            _ => return,
+        };
+        let severity = mapping[level_index(level)];
+        record.set_severity_number(severity);
+        record.set_severity_text(severity.name());
+    }))
+}
+
+#[cfg(feature = "datadog")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Rotation of log file written by [Protocol::DatadogAgent] exporter
+///
+///Once file needs rotation, it is renamed to `<path>.1`, while previously rotated files are shifted (`<path>.1` to `<path>.2` etc).
+///Files beyond retention count are deleted.
+pub struct FileRotation {
+    pub(crate) max_size: Option<u64>,
+    pub(crate) interval: Option<time::Duration>,
+    pub(crate) keep: usize,
+}
+
+#[cfg(feature = "datadog")]
+impl FileRotation {
+    #[inline(always)]
+    ///Creates new instance retaining up to `keep` rotated files, without any rotation trigger
+    ///
+    ///`keep` of `0` means file is deleted instead of rotation
+    pub const fn new(keep: usize) -> Self {
+        Self {
+            max_size: None,
+            interval: None,
+            keep,
+        }
+    }
+
+    ///Rotates file once its size reaches `max_size` bytes
+    pub const fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    ///Rotates file once `interval` passed since its creation
+    pub const fn with_interval(mut self, interval: time::Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+}
+
+#[cfg(feature = "stdout")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+///Format of log records written by [Protocol::Stdout] exporter
+pub enum StdoutFormat {
+    #[default]
+    ///Human readable line per record
+    Text,
+    ///JSON object per line, using [Elastic Common Schema](https://www.elastic.co/guide/en/ecs/current/index.html) field names
+    ///
+    ///Contains `@timestamp`, `log.level`, `log.logger` (target of event), `message`, `trace.id`, `span.id`, `service.*` fields derived from resource and attributes of record
+    Ecs,
+    ///OTLP/JSON encoded `ExportLogsServiceRequest` per line, containing every record of exported batch
+    ///
+    ///Output is the same as written by [Protocol::File], hence it can be tailed by collector's `otlpjsonfile` receiver
+    Otlp,
+}
+
+///Logs configuration
+pub struct LogsSettings {
+    batch: BatchSettings,
+    severity: [opentelemetry::logs::Severity; 5],
+    max_attributes_per_record: Option<usize>,
+    max_body_length: Option<usize>,
+    #[cfg(feature = "datadog")]
+    file_rotation: Option<FileRotation>,
+    #[cfg(feature = "stdout")]
+    stdout_format: StdoutFormat,
+}
+
+impl LogsSettings {
+    #[inline]
+    ///Creates new instance with SDK defaults
+    pub const fn new() -> Self {
+        Self {
+            batch: BatchSettings::new(),
+            severity: DEFAULT_SEVERITY,
+            max_attributes_per_record: None,
+            max_body_length: None,
+            #[cfg(feature = "datadog")]
+            file_rotation: None,
+            #[cfg(feature = "stdout")]
+            stdout_format: StdoutFormat::Text,
+        }
+    }
+
+    ///The max attributes recorded on single record created from `tracing` event, excess attributes are dropped. Unlimited by default
+    pub const fn with_max_attributes_per_record(mut self, max_attributes_per_record: usize) -> Self {
+        self.max_attributes_per_record = Some(max_attributes_per_record);
+        self
+    }
+
+    ///The max length of string body in bytes, longer bodies are truncated at character boundary. Unlimited by default
+    pub const fn with_max_body_length(mut self, max_body_length: usize) -> Self {
+        self.max_body_length = Some(max_body_length);
+        self
+    }
+
+    #[cfg(feature = "datadog")]
+    ///Specifies rotation of log file, which only applies to [Protocol::DatadogAgent] with `file://<full path>` url. Disabled by default
+    pub const fn with_file_rotation(mut self, rotation: FileRotation) -> Self {
+        self.file_rotation = Some(rotation);
+        self
+    }
+
+    #[cfg(feature = "stdout")]
+    ///Specifies format of log records, which only applies to [Protocol::Stdout]. Defaults to [StdoutFormat::Text]
+    pub const fn with_stdout_format(mut self, format: StdoutFormat) -> Self {
+        self.stdout_format = format;
+        self
+    }
+
+    ///Maps `tracing` `level` to OTel `severity` of exported records (e.g. `TRACE` to `Debug4`)
+    ///
+    ///By default every level is mapped to severity of the same name (`TRACE` to `Trace`, `INFO` to `Info` etc).
+    ///To clamp levels below `INFO`, map both `TRACE` and `DEBUG` to `Info`.
+    ///Note that mapping applies only to export, so level filtering still uses original `tracing` level
+    pub const fn with_severity_mapping(mut self, level: tracing::Level, severity: opentelemetry::logs::Severity) -> Self {
+        self.severity[level_index(level)] = severity;
+        self
+    }
+
+    ///The max number of records buffered for export. Records are dropped once queue is full. Defaults to 2048
+    pub const fn with_max_queue_size(mut self, max_queue_size: usize) -> Self {
+        self.batch.max_queue_size = Some(max_queue_size);
+        self
+    }
+
+    ///The max number of records exported in single batch. Defaults to 512
+    pub const fn with_max_export_batch_size(mut self, max_export_batch_size: usize) -> Self {
+        self.batch.max_export_batch_size = Some(max_export_batch_size);
+        self
+    }
+
+    ///The delay between two consecutive exports. Defaults to 1 second
+    pub const fn with_scheduled_delay(mut self, scheduled_delay: time::Duration) -> Self {
+        self.batch.scheduled_delay = Some(scheduled_delay);
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+///Retry configuration of exporters
+///
+///Backoff between attempts grows exponentially with random jitter applied.
+///Retries are stopped once total time spent on export would exceed exporter's timeout.
+pub struct RetrySettings {
+    ///Max number of export attempts, including initial one
+    pub max_attempts: u32,
+    ///Backoff before first retry
+    pub initial_backoff: time::Duration,
+    ///Upper limit of backoff between attempts
+    pub max_backoff: time::Duration,
+}
+
+impl RetrySettings {
+    #[inline]
+    ///Creates new instance with provided `max_attempts` and following defaults:
+    ///
+    ///- initial backoff is 100ms
+    ///- max backoff is 5s
+    pub const fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: time::Duration::from_millis(100),
+            max_backoff: time::Duration::from_secs(5),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+///Failover configuration of exporters, used with [Builder::with_fallback_destination]
+pub struct FailoverSettings {
+    ///Number of consecutive failed export attempts to primary destination before switching to fallback
+    pub max_failures: u32,
+    ///Interval between attempts to export to primary destination while fallback is used
+    pub probe_interval: time::Duration,
+}
+
+impl FailoverSettings {
+    #[inline]
+    ///Creates new instance with provided `max_failures` and probe interval of 30s
+    pub const fn new(max_failures: u32) -> Self {
+        Self {
+            max_failures,
+            probe_interval: time::Duration::from_secs(30),
+        }
+    }
+
+    #[inline]
+    ///Specify interval between attempts to export to primary destination while fallback is used
+    pub const fn with_probe_interval(mut self, probe_interval: time::Duration) -> Self {
+        self.probe_interval = probe_interval;
+        self
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Histogram boundaries for latency measured in seconds, as recommended by semantic conventions for HTTP durations
+pub const LATENCY_SECONDS_BOUNDARIES: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0];
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Histogram boundaries for latency measured in milliseconds, equivalent to [LATENCY_SECONDS_BOUNDARIES]
+pub const LATENCY_MILLISECONDS_BOUNDARIES: &[f64] = &[5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0, 7500.0, 10000.0];
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+#[derive(Clone, Debug)]
+///Metrics view, customizing how instruments with matching name are exported
+///
+///`instrument_glob` supports `*` (any sequence of characters) and `?` (any single character) wildcards.
+///
+///```rust
+///use tracing_opentelemetry_setup::builder::{MetricView, MetricsSettings};
+///
+///let settings = MetricsSettings::new().with_view(MetricView::new("http.server.request.duration").with_histogram_boundaries([0.005, 0.05, 0.5, 5.0]))
+///                                     .with_view(MetricView::new("legacy_requests").with_name("http.server.requests").with_allowed_attributes(["http.request.method"]));
+///```
+pub struct MetricView {
+    #[cfg_attr(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing")), allow(unused))]
+    instrument_glob: Cow<'static, str>,
+    name: Option<Cow<'static, str>>,
+    description: Option<Cow<'static, str>>,
+    allowed_attributes: Option<Vec<opentelemetry::Key>>,
+    histogram_boundaries: Option<Vec<f64>>,
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl MetricView {
+    #[inline]
+    ///Creates view applied to instruments with name matching `instrument_glob`, which keeps instruments unchanged by default
+    pub fn new(instrument_glob: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            instrument_glob: instrument_glob.into(),
+            name: None,
+            description: None,
+            allowed_attributes: None,
+            histogram_boundaries: None,
+        }
+    }
+
+    #[inline]
+    ///Renames instrument to `name`
+    ///
+    ///Should be used only with glob matching single instrument, as otherwise multiple instruments would be exported under the same name
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    #[inline]
+    ///Overrides instrument's description
+    pub fn with_description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    #[inline]
+    ///Restricts attributes to only specified `keys`, dropping everything else
+    pub fn with_allowed_attributes<K: Into<opentelemetry::Key>>(mut self, keys: impl IntoIterator<Item = K>) -> Self {
+        self.allowed_attributes = Some(keys.into_iter().map(Into::into).collect());
+        self
+    }
+
+    #[inline]
+    ///Specifies bucket boundaries of histogram instruments, which must be sorted in increasing order without duplicates
+    ///
+    ///Ignored for instruments other than histogram
+    pub fn with_histogram_boundaries(mut self, boundaries: impl Into<Vec<f64>>) -> Self {
+        self.histogram_boundaries = Some(boundaries.into());
+        self
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+    fn stream(&self, instrument: &opentelemetry_sdk::metrics::Instrument, cardinality_limit: Option<usize>, namespace: Option<&str>) -> Option<opentelemetry_sdk::metrics::Stream> {
+        let mut stream = opentelemetry_sdk::metrics::Stream::builder();
+        if let Some(limit) = cardinality_limit {
+            stream = stream.with_cardinality_limit(limit);
+        }
+        match (namespace, self.name.as_ref()) {
+            (Some(namespace), Some(name)) => stream = stream.with_name(format!("{namespace}{name}")),
+            (Some(namespace), None) => stream = stream.with_name(format!("{namespace}{}", instrument.name())),
+            (None, Some(name)) => stream = stream.with_name(name.clone()),
+            (None, None) => (),
+        }
+        if let Some(description) = self.description.as_ref() {
+            stream = stream.with_description(description.clone());
+        }
+        if let Some(keys) = self.allowed_attributes.as_ref() {
+            stream = stream.with_allowed_attribute_keys(keys.iter().cloned());
+        }
+        if let Some(boundaries) = self.histogram_boundaries.as_ref() {
+            if let opentelemetry_sdk::metrics::InstrumentKind::Histogram = instrument.kind() {
+                stream = stream.with_aggregation(opentelemetry_sdk::metrics::Aggregation::ExplicitBucketHistogram {
+                    boundaries: boundaries.clone(),
+                    record_min_max: true,
+                });
+            }
+        }
+
+        match stream.build() {
+            Ok(stream) => Some(stream),
+            Err(error) => {
+                tracing::warn!(target: "tracing_opentelemetry_setup", instrument = instrument.name(), %error, "Invalid metric view is ignored");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Metrics settings
+pub struct MetricsSettings {
+    temporality: opentelemetry_sdk::metrics::Temporality,
+    kind_temporality: [Option<opentelemetry_sdk::metrics::Temporality>; INSTRUMENT_KIND_NUM],
+    views: Vec<MetricView>,
+    cardinality_limit: Option<usize>,
+    interval: Option<time::Duration>,
+    export_timeout: Option<time::Duration>,
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+    process_metrics: bool,
+    #[cfg(feature = "metrics")]
+    baggage_attrs: Vec<Cow<'static, str>>,
+    #[cfg(feature = "metrics")]
+    dropped_attrs: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    #[cfg(feature = "tracing-metrics")]
+    tracing_prefixes: Vec<Cow<'static, str>>,
+    #[cfg(feature = "tracing-metrics")]
+    tracing_namespace: Option<Cow<'static, str>>,
+    #[cfg(feature = "prometheus")]
+    prometheus: bool,
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl MetricsSettings {
+    #[inline]
+    ///Creates new instance with following defaults:
+    ///
+    ///- temporality is Cumulative
+    pub const fn new() -> Self {
+        Self {
+            temporality: opentelemetry_sdk::metrics::Temporality::Cumulative,
+            kind_temporality: [None; INSTRUMENT_KIND_NUM],
+            views: Vec::new(),
+            cardinality_limit: None,
+            interval: None,
+            export_timeout: None,
+            #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+            process_metrics: false,
+            #[cfg(feature = "metrics")]
+            baggage_attrs: Vec::new(),
+            #[cfg(feature = "metrics")]
+            dropped_attrs: Vec::new(),
+            #[cfg(feature = "tracing-metrics")]
+            tracing_prefixes: Vec::new(),
+            #[cfg(feature = "tracing-metrics")]
+            tracing_namespace: None,
+            #[cfg(feature = "prometheus")]
+            prometheus: false,
+        }
+    }
+
+    #[inline]
+    ///Metrics are measured in cycles
+    pub const fn with_delta(mut self) -> Self {
+        self.temporality = opentelemetry_sdk::metrics::Temporality::Delta;
+        self
+    }
+
+    #[inline]
+    ///Optimizes delta measured metrics for low memory usage
+    pub const fn with_low_memory(mut self) -> Self {
+        self.temporality = opentelemetry_sdk::metrics::Temporality::LowMemory;
+        self
+    }
+
+    #[inline]
+    ///Overrides temporality for specific instrument `kind`, regardless of overall temporality preference
+    ///
+    ///This is required by some backends (e.g. Dynatrace expects Delta counters while accepting Cumulative up down counters)
+    ///
+    ///`LowMemory` is not meaningful for single instrument kind and is treated as `Delta`
+    ///
+    ///Note that [metrics](https://crates.io/crates/metrics) counters are recorded as `ObservableCounter` and gauges as `ObservableGauge`
+    pub const fn with_kind_temporality(mut self, kind: opentelemetry_sdk::metrics::InstrumentKind, temporality: opentelemetry_sdk::metrics::Temporality) -> Self {
+        self.kind_temporality[instrument_kind_index(kind)] = Some(match temporality {
+            opentelemetry_sdk::metrics::Temporality::LowMemory => opentelemetry_sdk::metrics::Temporality::Delta,
+            temporality => temporality,
+        });
+        self
+    }
+
+    ///Restricts attributes of instruments with name matching `instrument_glob` to only specified `keys`, dropping everything else
+    ///
+    ///This is the way to strip high-cardinality attributes (e.g. `url.path`) from specific instruments.
+    ///Note that opentelemetry views can only express list of attributes to retain, hence you must list attributes you want to keep.
+    ///
+    ///`instrument_glob` supports `*` (any sequence of characters) and `?` (any single character) wildcards.
+    ///If multiple views match the same instrument, only the first one is applied.
+    ///
+    ///Shortcut for [with_view](Self::with_view) using [MetricView::with_allowed_attributes]
+    pub fn with_retained_attributes<K: Into<opentelemetry::Key>>(self, instrument_glob: impl Into<Cow<'static, str>>, keys: impl IntoIterator<Item = K>) -> Self {
+        self.with_view(MetricView::new(instrument_glob).with_allowed_attributes(keys))
+    }
+
+    #[cfg(feature = "metrics")]
+    ///Drops attribute `key` from instruments with name matching `instrument_glob`, keeping everything else
+    ///
+    ///This is the way to strip high-cardinality attributes (e.g. `url.path`) without listing attributes to keep.
+    ///Opentelemetry views can only express list of attributes to retain, hence attribute is dropped before measurement reaches SDK,
+    ///which means it applies only to metrics recorded via [metrics](https://crates.io/crates/metrics) facade (see [Otlp::init_metrics_recorder]).
+    ///Use [with_retained_attributes](Self::with_retained_attributes) for instruments created directly via opentelemetry meter.
+    ///
+    ///`instrument_glob` supports `*` (any sequence of characters) and `?` (any single character) wildcards.
+    ///
+    ///Requires `metrics` feature
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::builder::MetricsSettings;
+    ///
+    ///let settings = MetricsSettings::new().drop_attribute("http.server.*", "url.path");
+    ///```
+    pub fn drop_attribute(mut self, instrument_glob: impl Into<Cow<'static, str>>, key: impl Into<Cow<'static, str>>) -> Self {
+        self.dropped_attrs.push((instrument_glob.into(), key.into()));
+        self
+    }
+
+    #[inline]
+    ///Specifies bucket `boundaries` of histograms with name starting with `name_prefix`
+    ///
+    ///Shortcut for [with_view](Self::with_view) using [MetricView::with_histogram_boundaries].
+    ///See [LATENCY_SECONDS_BOUNDARIES] and [LATENCY_MILLISECONDS_BOUNDARIES] for common latency boundaries.
+    ///If multiple views match the same instrument, only the first one is applied.
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::builder::{MetricsSettings, LATENCY_MILLISECONDS_BOUNDARIES};
+    ///
+    ///let settings = MetricsSettings::new().with_histogram_boundaries("http.client.", LATENCY_MILLISECONDS_BOUNDARIES)
+    ///                                     .with_histogram_boundaries("db.", [1.0, 10.0, 100.0]);
+    ///```
+    pub fn with_histogram_boundaries(self, name_prefix: &str, boundaries: impl Into<Vec<f64>>) -> Self {
+        self.with_view(MetricView::new(format!("{name_prefix}*")).with_histogram_boundaries(boundaries))
+    }
+
+    #[inline]
+    ///Specifies interval between periodic exports. Defaults to 60 seconds (or `OTEL_METRIC_EXPORT_INTERVAL` if set)
+    ///
+    ///Ignored with [ExportMode::Manual]
+    pub const fn with_interval(mut self, interval: time::Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    #[inline]
+    ///Specifies timeout of single metrics export, including retries, overriding [Builder::with_timeout] for metrics only
+    pub const fn with_export_timeout(mut self, timeout: time::Duration) -> Self {
+        self.export_timeout = Some(timeout);
+        self
+    }
+
+    #[inline]
+    ///Limits number of distinct attribute sets tracked by every instrument
+    ///
+    ///Once limit is reached, measurements with new attribute sets are aggregated into single data point with `otel.metric.overflow=true` attribute,
+    ///protecting process from unbounded memory growth caused by high-cardinality labels. SDK default limit is 2000
+    pub const fn with_cardinality_limit(mut self, limit: usize) -> Self {
+        self.cardinality_limit = Some(limit);
+        self
+    }
+
+    #[inline]
+    ///Adds `view` customizing instruments with matching name (e.g. renaming it, restricting attributes or overriding histogram buckets)
+    ///
+    ///If multiple views match the same instrument, only the first one is applied.
+    pub fn with_view(mut self, view: MetricView) -> Self {
+        self.views.push(view);
+        self
+    }
+
+    #[inline]
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Reports resource usage of current process
+    ///
+    ///Following instruments are sampled on every collection, hence their interval is controlled by [with_interval](Self::with_interval):
+    ///
+    ///- `process.cpu.time` - CPU time in seconds, split by `cpu.mode` (`user` and `system`);
+    ///- `process.memory.usage` - Resident memory in bytes;
+    ///- `process.open_file_descriptor.count` - Number of open file descriptors;
+    ///- `process.thread.count` - Number of threads.
+    ///
+    ///Currently only Linux is supported, on other platforms instruments report nothing
+    pub const fn with_process_metrics(mut self) -> Self {
+        self.process_metrics = true;
+        self
+    }
+
+    #[cfg(feature = "metrics")]
+    ///Adds baggage entries with specified `keys` as attributes of metrics recorded via [metrics](https://crates.io/crates/metrics) facade
+    ///
+    ///Baggage is taken from current span (requires `propagation` feature) and current opentelemetry context at the moment metric handle is obtained (e.g. via `counter!`).
+    ///Hence handles retained for later use keep attributes of the moment they were created.
+    ///
+    ///Baggage of incoming request is only retained if span's context enables it via `propagation::Context::with_baggage`,
+    ///as `propagation::Context::set_parent_from` discards extracted baggage by default.
+    ///
+    ///Labels specified explicitly take precedence over baggage entries
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::builder::MetricsSettings;
+    ///
+    ///let settings = MetricsSettings::new().with_baggage_attrs(&["tenant.id"]);
+    ///```
+    pub fn with_baggage_attrs(mut self, keys: &[&str]) -> Self {
+        self.baggage_attrs.extend(keys.iter().map(|key| Cow::Owned(String::from(*key))));
+        self
+    }
+
+    #[cfg(feature = "tracing-metrics")]
+    ///Restricts metrics recorded via tracing events to only those with name starting with one of `prefixes`, dropping everything else
+    ///
+    ///Prefix is matched against metric name without kind prefix, i.e. `myapp.` allows both `monotonic_counter.myapp.requests` and `histogram.myapp.latency`.
+    ///This prevents dependencies from silently exporting metrics via their own events.
+    ///
+    ///Requires `tracing-metrics` feature
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::builder::MetricsSettings;
+    ///
+    ///let settings = MetricsSettings::new().with_tracing_metrics_prefixes(["myapp."]).with_tracing_metrics_namespace("prod_");
+    ///```
+    pub fn with_tracing_metrics_prefixes<T: Into<Cow<'static, str>>>(mut self, prefixes: impl IntoIterator<Item = T>) -> Self {
+        self.tracing_prefixes.extend(prefixes.into_iter().map(Into::into));
+        self
+    }
+
+    #[cfg(feature = "tracing-metrics")]
+    #[inline]
+    ///Prepends `namespace` (e.g. `myapp_`) to names of metrics recorded via tracing events
+    ///
+    ///Namespace is applied on top of name specified by matching [MetricView].
+    ///
+    ///Requires `tracing-metrics` feature
+    pub fn with_tracing_metrics_namespace(mut self, namespace: impl Into<Cow<'static, str>>) -> Self {
+        self.tracing_namespace = Some(namespace.into());
+        self
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[inline]
+    ///Exposes metrics via Prometheus pull exporter instead of pushing them to destination
+    ///
+    ///Metrics are rendered on demand by [PrometheusHandle](crate::prometheus::PrometheusHandle) available via [Otlp::prometheus_handle].
+    ///Prometheus requires cumulative temporality, hence temporality preferences, interval and export timeout are ignored
+    pub const fn with_prometheus(mut self) -> Self {
+        self.prometheus = true;
+        self
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+    fn with_views(&self, mut builder: opentelemetry_sdk::metrics::MeterProviderBuilder) -> opentelemetry_sdk::metrics::MeterProviderBuilder {
+        #[cfg(feature = "tracing-metrics")]
+        let has_tracing_config = !self.tracing_prefixes.is_empty() || self.tracing_namespace.is_some();
+        #[cfg(not(feature = "tracing-metrics"))]
+        let has_tracing_config = false;
+
+        if !self.views.is_empty() || self.cardinality_limit.is_some() || has_tracing_config {
+            let views = self.views.clone();
+            let cardinality_limit = self.cardinality_limit;
+            #[cfg(feature = "tracing-metrics")]
+            let tracing_prefixes = self.tracing_prefixes.clone();
+            #[cfg(feature = "tracing-metrics")]
+            let tracing_namespace = self.tracing_namespace.clone();
+            builder = builder.with_view(move |instrument: &opentelemetry_sdk::metrics::Instrument| {
+                #[cfg(feature = "tracing-metrics")]
+                let namespace = match instrument.scope().name() == TRACING_METRICS_SCOPE {
+                    true => {
+                        if !tracing_prefixes.is_empty() && !tracing_prefixes.iter().any(|prefix| instrument.name().starts_with(prefix.as_ref())) {
+                            return opentelemetry_sdk::metrics::Stream::builder().with_aggregation(opentelemetry_sdk::metrics::Aggregation::Drop).build().ok();
+                        }
+                        tracing_namespace.as_deref()
+                    },
+                    false => None,
+                };
+                #[cfg(not(feature = "tracing-metrics"))]
+                let namespace = None;
+
+                match views.iter().find(|view| glob_match(&view.instrument_glob, instrument.name())) {
+                    Some(view) => view.stream(instrument, cardinality_limit, namespace),
+                    None if cardinality_limit.is_some() || namespace.is_some() => MetricView::new("*").stream(instrument, cardinality_limit, namespace),
+                    None => None,
+                }
+            });
+        }
+        builder
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn periodic_reader<E: opentelemetry_sdk::metrics::exporter::PushMetricExporter>(&self, exporter: E) -> opentelemetry_sdk::metrics::PeriodicReader<E> {
+        let mut reader = opentelemetry_sdk::metrics::PeriodicReader::builder(exporter);
+        if let Some(interval) = self.interval {
+            reader = reader.with_interval(interval);
+        }
+        reader.build()
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    #[inline(always)]
+    fn has_kind_temporality(&self) -> bool {
+        self.kind_temporality.iter().any(Option::is_some)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+const INSTRUMENT_KIND_NUM: usize = 7;
+
+#[cfg(all(feature = "tracing-metrics", any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing")))]
+///Instrumentation scope of meter used by `tracing_opentelemetry::MetricsLayer`
+const TRACING_METRICS_SCOPE: &str = "tracing/tracing-opentelemetry";
+
+#[cfg(any(feature = "metrics", all(feature = "tracing-metrics", any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))))]
+///Matches `text` against `pattern` with `*` and `?` wildcards
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let text = text.as_bytes();
+    let mut pattern_idx = 0;
+    let mut text_idx = 0;
+    //Position of last `*` in pattern and text position it started to match at
+    let mut backtrack = None;
+
+    while text_idx < text.len() {
+        match pattern.get(pattern_idx) {
+            Some(b'*') => {
+                pattern_idx += 1;
+                backtrack = Some((pattern_idx, text_idx));
+                continue;
+            },
+            Some(ch) if *ch == b'?' || *ch == text[text_idx] => {
+                pattern_idx += 1;
+                text_idx += 1;
+                continue;
+            },
+            _ => (),
+        }
+
+        match backtrack.as_mut() {
+            Some((star_pattern_idx, star_text_idx)) => {
+                *star_text_idx += 1;
+                pattern_idx = *star_pattern_idx;
+                text_idx = *star_text_idx;
+            },
+            None => return false,
+        }
+    }
+
+    pattern[pattern_idx..].iter().all(|ch| *ch == b'*')
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+#[inline(always)]
+const fn instrument_kind_index(kind: opentelemetry_sdk::metrics::InstrumentKind) -> usize {
+    use opentelemetry_sdk::metrics::InstrumentKind;
+
+    match kind {
+        InstrumentKind::Counter => 0,
+        InstrumentKind::UpDownCounter => 1,
+        InstrumentKind::Histogram => 2,
+        InstrumentKind::ObservableCounter => 3,
+        InstrumentKind::ObservableUpDownCounter => 4,
+        InstrumentKind::Gauge => 5,
+        InstrumentKind::ObservableGauge => 6,
+    }
+}
+
+#[cfg(all(any(feature = "metrics", feature = "tracing-metrics"), any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+#[derive(Debug)]
+///Periodic reader which overrides temporality of the exporter for individual instrument kinds
+struct KindTemporalityReader<R> {
+    inner: R,
+    kind_temporality: [Option<opentelemetry_sdk::metrics::Temporality>; INSTRUMENT_KIND_NUM],
+}
+
+#[cfg(all(any(feature = "metrics", feature = "tracing-metrics"), any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+impl<R: opentelemetry_sdk::metrics::reader::MetricReader> opentelemetry_sdk::metrics::reader::MetricReader for KindTemporalityReader<R> {
+    #[inline(always)]
+    fn register_pipeline(&self, pipeline: std::sync::Weak<opentelemetry_sdk::metrics::Pipeline>) {
+        self.inner.register_pipeline(pipeline)
+    }
+
+    #[inline(always)]
+    fn collect(&self, rm: &mut opentelemetry_sdk::metrics::data::ResourceMetrics) -> opentelemetry_sdk::error::OTelSdkResult {
+        self.inner.collect(rm)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> opentelemetry_sdk::error::OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> opentelemetry_sdk::error::OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline]
+    fn temporality(&self, kind: opentelemetry_sdk::metrics::InstrumentKind) -> opentelemetry_sdk::metrics::Temporality {
+        match self.kind_temporality[instrument_kind_index(kind)] {
+            Some(temporality) => temporality,
+            None => self.inner.temporality(kind),
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Clone)]
+///Filter of [metrics](https://crates.io/crates/metrics) labels applied before they become opentelemetry attributes
+///
+///Requires `metrics` feature
+pub enum LabelFilter {
+    ///Retains only labels with specified names
+    Allow(Vec<Cow<'static, str>>),
+    ///Removes labels with specified names
+    Deny(Vec<Cow<'static, str>>),
+}
+
+#[cfg(feature = "metrics")]
+impl LabelFilter {
+    #[inline]
+    ///Creates filter retaining only labels with specified `names`
+    pub fn allow<T: Into<Cow<'static, str>>>(names: impl IntoIterator<Item = T>) -> Self {
+        Self::Allow(names.into_iter().map(Into::into).collect())
+    }
+
+    #[inline]
+    ///Creates filter removing labels with specified `names`
+    pub fn deny<T: Into<Cow<'static, str>>>(names: impl IntoIterator<Item = T>) -> Self {
+        Self::Deny(names.into_iter().map(Into::into).collect())
+    }
+
+    #[inline]
+    fn is_allowed(&self, label: &str) -> bool {
+        match self {
+            Self::Allow(names) => names.iter().any(|name| name == label),
+            Self::Deny(names) => !names.iter().any(|name| name == label),
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+///[metrics](https://crates.io/crates/metrics) recorder backed by [Otlp] metrics provider
+///
+///Created via [Otlp::create_metrics_recorder]
+///
+///Requires `metrics` feature
+pub struct MetricsRecorder {
+    inner: metrics_opentelemetry::OpenTelemetryRecorder,
+    meter: opentelemetry::metrics::Meter,
+    //`metrics-opentelemetry` stores histogram descriptions alongside counters, hence described histograms are created here
+    histogram_descriptions: std::sync::RwLock<std::collections::HashMap<crate::metrics::KeyName, (Option<crate::metrics::Unit>, crate::metrics::SharedString)>>,
+    histograms: std::sync::RwLock<std::collections::HashMap<crate::metrics::Key, Arc<DescribedHistogram>>>,
+    baggage_attrs: Vec<Cow<'static, str>>,
+    dropped_attrs: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    filter: Option<LabelFilter>,
+}
+
+#[cfg(feature = "metrics")]
+///Returns baggage of current opentelemetry context, merged with baggage of current span
+fn current_baggage() -> opentelemetry::baggage::Baggage {
+    use opentelemetry::baggage::BaggageExt;
+
+    let mut baggage = opentelemetry::baggage::Baggage::new();
+    let mut merge = |entries: &opentelemetry::baggage::Baggage| {
+        for (key, (value, metadata)) in entries.iter() {
+            baggage.insert_with_metadata(key.clone(), value.clone(), metadata.clone());
+        }
+    };
+    merge(opentelemetry::Context::current().baggage());
+    #[cfg(feature = "propagation")]
+    merge(&crate::propagation::Context::current().baggage());
+    baggage
+}
+
+#[cfg(feature = "metrics")]
+struct DescribedHistogram {
+    otel: opentelemetry::metrics::Histogram<f64>,
+    labels: Vec<opentelemetry::KeyValue>,
+}
+
+#[cfg(feature = "metrics")]
+impl crate::metrics::HistogramFn for DescribedHistogram {
+    #[inline(always)]
+    fn record(&self, value: f64) {
+        self.otel.record(value, &self.labels);
+    }
+}
+
+#[cfg(feature = "metrics")]
+///Converts unit into [UCUM](https://ucum.org) code, as expected by opentelemetry
+const fn metrics_unit_to_ucum(unit: crate::metrics::Unit) -> &'static str {
+    use crate::metrics::Unit;
+
+    match unit {
+        Unit::Count => "1",
+        Unit::Percent => "%",
+        Unit::Seconds => "s",
+        Unit::Milliseconds => "ms",
+        Unit::Microseconds => "us",
+        Unit::Nanoseconds => "ns",
+        Unit::Tebibytes => "TiBy",
+        Unit::Gibibytes => "GiBy",
+        Unit::Mebibytes => "MiBy",
+        Unit::Kibibytes => "KiBy",
+        Unit::Bytes => "By",
+        Unit::TerabitsPerSecond => "Tbit/s",
+        Unit::GigabitsPerSecond => "Gbit/s",
+        Unit::MegabitsPerSecond => "Mbit/s",
+        Unit::KilobitsPerSecond => "kbit/s",
+        Unit::BitsPerSecond => "bit/s",
+        Unit::CountPerSecond => "1/s",
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder {
+    fn filter_key<'a>(&self, key: &'a crate::metrics::Key) -> Cow<'a, crate::metrics::Key> {
+        let key = self.filter_labels(key);
+        if self.baggage_attrs.is_empty() {
+            return key;
+        }
+
+        let baggage = current_baggage();
+        let mut labels = Vec::new();
+        for name in self.baggage_attrs.iter() {
+            if key.labels().any(|label| label.key() == name) {
+                continue;
+            }
+            if let Some(value) = baggage.get(name.as_ref()) {
+                labels.push(crate::metrics::Label::new(name.clone(), value.to_string()));
+            }
+        }
+
+        if labels.is_empty() {
+            key
+        } else {
+            labels.extend(key.labels().cloned());
+            Cow::Owned(crate::metrics::Key::from_parts(key.name_shared(), labels))
+        }
+    }
+
+    fn filter_labels<'a>(&self, key: &'a crate::metrics::Key) -> Cow<'a, crate::metrics::Key> {
+        if self.filter.is_none() && self.dropped_attrs.is_empty() {
+            return Cow::Borrowed(key);
+        }
+
+        let is_allowed = |label: &crate::metrics::Label| {
+            if let Some(filter) = self.filter.as_ref() {
+                if !filter.is_allowed(label.key()) {
+                    return false;
+                }
+            }
+            !self.dropped_attrs.iter().any(|(instrument_glob, name)| name == label.key() && glob_match(instrument_glob, key.name()))
+        };
+
+        if key.labels().all(is_allowed) {
+            Cow::Borrowed(key)
+        } else {
+            let labels = key.labels().filter(|label| is_allowed(label)).cloned().collect::<Vec<_>>();
+            Cow::Owned(crate::metrics::Key::from_parts(key.name_shared(), labels))
+        }
+    }
+
+    fn described_histogram(&self, key: &crate::metrics::Key) -> Option<Arc<DescribedHistogram>> {
+        if let Some(histogram) = self.histograms.read().unwrap_or_else(|error| error.into_inner()).get(key) {
+            return Some(histogram.clone());
+        }
+
+        let mut histogram = self.meter.f64_histogram(key.name().to_owned());
+        {
+            let descriptions = self.histogram_descriptions.read().unwrap_or_else(|error| error.into_inner());
+            let (unit, description) = descriptions.get(key.name())?;
+            histogram = histogram.with_description(description.clone());
+            if let Some(unit) = unit {
+                histogram = histogram.with_unit(metrics_unit_to_ucum(*unit));
+            }
+        }
+        let histogram = Arc::new(DescribedHistogram {
+            otel: histogram.build(),
+            labels: key.labels().map(|label| opentelemetry::KeyValue::new(label.key().to_owned(), label.value().to_owned())).collect(),
+        });
+
+        let mut histograms = self.histograms.write().unwrap_or_else(|error| error.into_inner());
+        Some(histograms.entry(key.clone()).or_insert(histogram).clone())
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl crate::metrics::Recorder for MetricsRecorder {
+    #[inline(always)]
+    fn describe_counter(&self, key: crate::metrics::KeyName, unit: Option<crate::metrics::Unit>, description: crate::metrics::SharedString) {
+        self.inner.describe_counter(key, unit, description)
+    }
+
+    #[inline(always)]
+    fn describe_gauge(&self, key: crate::metrics::KeyName, unit: Option<crate::metrics::Unit>, description: crate::metrics::SharedString) {
+        self.inner.describe_gauge(key, unit, description)
+    }
+
+    #[inline]
+    fn describe_histogram(&self, key: crate::metrics::KeyName, unit: Option<crate::metrics::Unit>, description: crate::metrics::SharedString) {
+        self.histogram_descriptions.write().unwrap_or_else(|error| error.into_inner()).insert(key, (unit, description));
+    }
+
+    #[inline]
+    fn register_counter(&self, key: &crate::metrics::Key, metadata: &crate::metrics::Metadata<'_>) -> crate::metrics::Counter {
+        self.inner.register_counter(&self.filter_key(key), metadata)
+    }
+
+    #[inline]
+    fn register_gauge(&self, key: &crate::metrics::Key, metadata: &crate::metrics::Metadata<'_>) -> crate::metrics::Gauge {
+        self.inner.register_gauge(&self.filter_key(key), metadata)
+    }
+
+    #[inline]
+    fn register_histogram(&self, key: &crate::metrics::Key, metadata: &crate::metrics::Metadata<'_>) -> crate::metrics::Histogram {
+        let key = self.filter_key(key);
+        match self.described_histogram(&key) {
+            Some(histogram) => crate::metrics::Histogram::from_arc(histogram),
+            None => self.inner.register_histogram(&key, metadata),
+        }
+    }
+}
+
+impl<'a> Builder<'a> {
+    #[inline]
+    ///Starts building Opentelemetry integration
+    pub const fn new(destination: Destination<'a>) -> Self {
+        Self {
+            destination,
+            otlp: Otlp::new(),
+            headers: Vec::new(),
+            timeout: time::Duration::from_secs(5),
+            compression: true,
+            retry: None,
+            export_mode: ExportMode::Batch,
+            startup_summary: false,
+            #[cfg(feature = "datadog")]
+            datadog_probe: false,
+            logs: None,
+            trace: None,
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            metrics: None,
+            kill_switch: None,
+            paused: None,
+            self_telemetry: false,
+            additional_destinations: Vec::new(),
+            fallback_destination: None,
+            failover: FailoverSettings::new(3),
+            span_exporter_map: None,
+            log_exporter_map: None,
+            log_processor_hook: None,
+            #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+            metric_exporter_map: None,
+            #[cfg(feature = "grpc")]
+            grpc_channel: None,
+            #[cfg(feature = "grpc")]
+            grpc_interceptor: None,
+            #[cfg(feature = "http")]
+            http_client: None,
+            #[cfg(any(feature = "http-reqwest", feature = "http-reqwest-blocking"))]
+            http_proxy: None,
+            #[cfg(feature = "testing")]
+            in_memory: std::sync::OnceLock::new(),
+        }
+    }
+
+    #[inline]
+    ///Specify whether to use compression by all OTLP exporters
+    ///
+    ///Defaults to `true`
+    ///
+    ///Has no effect if relevant `*-compression` are enabled
+    pub fn with_compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    #[inline]
+    ///Specify common timeout to be used by all OTLP exporters
+    ///
+    ///Defaults to 5 seconds
+    pub fn with_timeout(mut self, timeout: time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[inline]
+    ///Specify retry policy to be used by all exporters
+    ///
+    ///Backoff between attempts blocks exporting thread, hence retries are only performed by exporters running on dedicated thread:
+    ///logs and traces in [ExportMode::Batch], and metrics unless [ExportMode::Manual] is used.
+    ///
+    ///By default failed exports are not retried
+    pub fn with_retry(mut self, retry: RetrySettings) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    #[inline]
+    ///Specify whether to emit single event describing telemetry configuration on subscriber initialization
+    ///
+    ///Event contains endpoint (without credentials or query), protocol, names of headers, enabled signals, sample rate and crate versions:
+    ///
+    ///- `version` - version of this crate;
+    ///- `otel.sdk.version` - exact version of `opentelemetry_sdk`;
+    ///- `otel.api.version` - version series of `opentelemetry` crates;
+    ///- `otel.tracing.version` - version series of `tracing-opentelemetry`.
+    ///
+    ///It is emitted with `tracing_opentelemetry_setup` target and INFO level.
+    ///
+    ///Defaults to `false`
+    pub fn with_startup_summary(mut self, startup_summary: bool) -> Self {
+        self.startup_summary = startup_summary;
+        self
+    }
+
+    #[cfg(feature = "datadog")]
+    #[inline]
+    ///Specify whether to probe Datadog agent's `/info` endpoint during [finish](Self::finish), when traces are exported with [Protocol::DatadogAgent]
+    ///
+    ///Outcome (agent version and supported endpoints or reason agent is unreachable) is logged with `tracing_opentelemetry_setup` target on subscriber initialization.
+    ///Use [try_finish](Self::try_finish) to fail instead when agent is unreachable.
+    ///
+    ///Defaults to `false`
+    pub fn with_datadog_probe(mut self, probe: bool) -> Self {
+        self.datadog_probe = probe;
+        self
+    }
+
+    ///Enables kill switch, pausing export of all signals while file at `path` exists (e.g. `/etc/telemetry/disabled`)
+    ///
+    ///File is checked by background thread every second, so export is paused or resumed within seconds after file is created or removed.
+    ///While paused, telemetry is dropped instead of being exported.
+    ///
+    ///Watcher is started by [finish](Self::finish). If it cannot be spawned, warning is logged and kill switch is not applied
+    pub fn with_kill_switch(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.kill_switch = Some(path.into());
+        self
+    }
+
+    ///Interns attribute keys with `names` ahead of time, so that [keys::intern](crate::keys::intern) never allocates for them on hot path
+    pub fn with_interned_keys<I: IntoIterator<Item = T>, T: AsRef<str>>(self, names: I) -> Self {
+        for name in names {
+            crate::keys::intern(name.as_ref());
+        }
+        self
+    }
+
+    #[inline]
+    ///Enables instrumentation of exports, wrapping every exported batch into `otel.export` span
+    ///
+    ///Span is created with target `tracing_opentelemetry_setup::export` and records signal, batch size and outcome,
+    ///while its duration reflects export latency including retries.
+    ///Batches consisting only of such spans are not instrumented, so that they are not exported recursively.
+    ///
+    ///Exports are performed by background threads, hence spans are recorded only by global subscriber (see [Otlp::init_tracing_subscriber]).
+    ///Traces are not instrumented with [ExportMode::Simple].
+    pub fn with_self_telemetry(mut self, self_telemetry: bool) -> Self {
+        self.self_telemetry = self_telemetry;
+        self
+    }
+
+    ///Exports `signal` to `destination` in addition to primary destination, e.g. to ship traces to both old and new backend during migration
+    ///
+    ///Can be called multiple times to add more destinations. Common settings (timeout, retries etc) apply to every destination,
+    ///while the signal is considered ready once any destination completes successful export.
+    ///Headers are not shared with primary destination, use [Target::with_header] to specify them.
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::Otlp;
+    ///use tracing_opentelemetry_setup::builder::{Destination, Protocol, Signal, TraceSettings};
+    ///
+    ///let jaeger = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:4318".into()
+    ///};
+    ///let saas = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "https://otlp.example.com".into()
+    ///};
+    ///let otlp = Otlp::builder(jaeger).with_trace(None, TraceSettings::new(1.0))
+    ///                                .with_additional_destination(Signal::Trace, saas)
+    ///                                .finish();
+    ///```
+    pub fn with_additional_destination(mut self, signal: Signal, destination: impl Into<Target<'a>>) -> Self {
+        self.additional_destinations.push((signal, destination.into()));
+        self
+    }
+
+    ///Exports to `destination` once primary destination keeps failing, so that telemetry is not lost during collector outage
+    ///
+    ///After [FailoverSettings::max_failures] consecutive failed export attempts (including retries) to primary destination,
+    ///exporters switch to `destination`, re-sending data of the last failed attempt.
+    ///While fallback is used, primary destination is probed with real data every [FailoverSettings::probe_interval] and used again once it succeeds.
+    ///
+    ///Fallback applies to every signal, but not to additional destinations. Use [Builder::with_failover] to tweak default settings.
+    ///
+    ///Metrics are aggregated according to temporality of primary destination, which is also used for fallback destination
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::Otlp;
+    ///use tracing_opentelemetry_setup::builder::{Destination, FailoverSettings, Protocol, TraceSettings};
+    ///
+    ///let collector = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://localhost:4318".into()
+    ///};
+    ///let secondary = Destination {
+    ///    protocol: Protocol::HttpBinary,
+    ///    url: "http://collector-2:4318".into()
+    ///};
+    ///let otlp = Otlp::builder(collector).with_fallback_destination(secondary)
+    ///                                   .with_failover(FailoverSettings::new(5))
+    ///                                   .with_trace(None, TraceSettings::new(1.0))
+    ///                                   .finish();
+    ///```
+    pub fn with_fallback_destination(mut self, destination: impl Into<Target<'a>>) -> Self {
+        self.fallback_destination = Some(destination.into());
+        self
+    }
+
+    #[inline]
+    ///Specify when to switch to fallback destination and back
+    ///
+    ///Defaults to `FailoverSettings::new(3)`. Has no effect without [Builder::with_fallback_destination]
+    pub fn with_failover(mut self, failover: FailoverSettings) -> Self {
+        self.failover = failover;
+        self
+    }
+
+    #[inline]
+    ///Specify how `logs` and `trace` data is exported
+    ///
+    ///Defaults to [ExportMode::Batch]. Metrics are exported periodically, unless [ExportMode::Manual] is used
+    pub fn with_export_mode(mut self, mode: ExportMode) -> Self {
+        self.export_mode = mode;
+        self
+    }
+
+    #[inline]
+    ///Wraps every span exporter using `map`
+    ///
+    ///Allows to add own middleware (e.g. audit logging or encryption) around exporter before it is attached to processor.
+    ///Builder's own retry and readiness tracking are applied on top of exporter returned by `map`
+    pub fn map_span_exporter(mut self, map: impl Fn(crate::exporter::SpanExporter) -> crate::exporter::SpanExporter + Send + Sync + 'static) -> Self {
+        self.span_exporter_map = Some(Box::new(map));
+        self
+    }
+
+    #[inline]
+    ///Wraps every log exporter using `map`
+    ///
+    ///Allows to add own middleware (e.g. audit logging or encryption) around exporter before it is attached to processor.
+    ///Builder's own retry and readiness tracking are applied on top of exporter returned by `map`
+    pub fn map_log_exporter(mut self, map: impl Fn(crate::exporter::LogExporter) -> crate::exporter::LogExporter + Send + Sync + 'static) -> Self {
+        self.log_exporter_map = Some(Box::new(map));
+        self
+    }
+
+    #[inline]
+    ///Invokes `hook` on every log record before it is passed to log processor
+    ///
+    ///Allows to rewrite record (e.g. redact body or add tenant ID attribute) without building own logger provider.
+    ///Hook runs synchronously on the thread emitting log, so it should be cheap
+    pub fn with_log_processor_hook(mut self, hook: impl Fn(&mut opentelemetry_sdk::logs::SdkLogRecord) + Send + Sync + 'static) -> Self {
+        self.log_processor_hook = Some(Arc::new(hook));
+        self
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    #[inline]
+    ///Wraps every metric exporter using `map`
+    ///
+    ///Allows to add own middleware (e.g. audit logging or encryption) around exporter before it is attached to reader.
+    ///Builder's own retry and readiness tracking are applied on top of exporter returned by `map`
+    pub fn map_metric_exporter(mut self, map: impl Fn(crate::exporter::MetricExporter) -> crate::exporter::MetricExporter + Send + Sync + 'static) -> Self {
+        self.metric_exporter_map = Some(Box::new(map));
+        self
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn span_exporter<T: opentelemetry_sdk::trace::SpanExporter + 'static>(&self, exporter: T) -> crate::exporter::SpanExporter {
+        let exporter = crate::exporter::SpanExporter::new(exporter);
+        match self.span_exporter_map.as_ref() {
+            Some(map) => map(exporter),
+            None => exporter,
+        }
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn log_exporter<T: opentelemetry_sdk::logs::LogExporter + 'static>(&self, exporter: T) -> crate::exporter::LogExporter {
+        let exporter = crate::exporter::LogExporter::new(exporter);
+        match self.log_exporter_map.as_ref() {
+            Some(map) => map(exporter),
+            None => exporter,
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[inline(always)]
+    pub(crate) fn has_logs(&self) -> bool {
+        self.logs.is_some()
+    }
+
+    #[cfg(feature = "testing")]
+    #[inline(always)]
+    pub(crate) fn has_trace(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    #[cfg(all(feature = "testing", any(feature = "metrics", feature = "tracing-metrics")))]
+    #[inline(always)]
+    pub(crate) fn has_metrics(&self) -> bool {
+        self.metrics.is_some()
+    }
+
+    #[cfg(feature = "testing")]
+    #[inline(always)]
+    ///Returns in-memory exporters shared by every [Protocol::InMemory] destination of this builder
+    pub(crate) fn in_memory_exporters(&self) -> &crate::testing::InMemoryExporters {
+        self.in_memory.get_or_init(Default::default)
+    }
+
+    #[cfg(all(any(feature = "metrics", feature = "tracing-metrics"), any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+    fn metric_exporter<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter + fmt::Debug>(&self, exporter: T) -> crate::exporter::MetricExporter {
+        let exporter = crate::exporter::MetricExporter::new(exporter);
+        match self.metric_exporter_map.as_ref() {
+            Some(map) => map(exporter),
+            None => exporter,
+        }
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    #[inline(always)]
+    fn wrap_exporter<T>(&self, exporter: T, dedicated_thread: bool, self_telemetry: bool, ready: &Arc<AtomicBool>) -> WrappedExporter<T> {
+        self.wrap_exporter_with_timeout(exporter, dedicated_thread, self_telemetry, self.timeout, ready)
     }
 
-    ///The max attributes that can be added into a Link. Defaults to 128
-    pub const fn with_max_attributes_per_link(mut self, with_max_attributes_per_link: u32) -> Self {
-        set_trace_limit!(self.limits, with_max_attributes_per_link);
-        self
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Wraps exporter, applying retry policy only if exporter runs on `dedicated_thread`, as backoff blocks exporting thread
+    fn wrap_exporter_with_timeout<T>(&self, exporter: T, dedicated_thread: bool, self_telemetry: bool, timeout: time::Duration, ready: &Arc<AtomicBool>) -> WrappedExporter<T> {
+        let retry = match dedicated_thread {
+            true => self.retry,
+            false => None,
+        };
+        let exporter = crate::retry::RetryExporter::new(exporter, retry, timeout);
+        let exporter = crate::ready::ReadyExporter::new(exporter, ready.clone());
+        let exporter = crate::telemetry::SelfTelemetryExporter::new(exporter, self.self_telemetry && self_telemetry);
+        crate::pause::PauseExporter::new(exporter, self.paused.clone())
     }
-}
 
-#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
-///Metrics settings
-pub struct MetricsSettings {
-    temporality: opentelemetry_sdk::metrics::Temporality,
-}
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn span_processor(&self, exporter: crate::exporter::SpanExporter, batch: BatchSettings, ready: &Arc<AtomicBool>) -> crate::processor::SpanProcessor {
+        //Export span would end while simple processor is still busy exporting, deadlocking it
+        let exporter = self.wrap_exporter(exporter, matches!(self.export_mode, ExportMode::Batch) && !batch.is_concurrent(), matches!(self.export_mode, ExportMode::Batch), ready);
+        match self.export_mode {
+            #[cfg(feature = "rt-tokio")]
+            ExportMode::Batch if batch.is_concurrent() => {
+                let mut config = batch.builder::<opentelemetry_sdk::trace::BatchConfigBuilder>();
+                if let Some(max_concurrent_exports) = batch.max_concurrent_exports {
+                    config = config.with_max_concurrent_exports(max_concurrent_exports);
+                }
+                let config = config.build();
+                let processor = opentelemetry_sdk::trace::span_processor_with_async_runtime::BatchSpanProcessor::builder(exporter, opentelemetry_sdk::runtime::Tokio).with_batch_config(config).build();
+                crate::processor::SpanProcessor::new(processor)
+            },
+            ExportMode::Batch => crate::processor::SpanProcessor::new(opentelemetry_sdk::trace::BatchSpanProcessor::new(exporter, batch.builder::<opentelemetry_sdk::trace::BatchConfigBuilder>().build())),
+            ExportMode::Simple => crate::processor::SpanProcessor::new(opentelemetry_sdk::trace::SimpleSpanProcessor::new(exporter)),
+            ExportMode::Manual => crate::processor::SpanProcessor::new(crate::processor::ManualSpanProcessor::new(exporter, batch.max_queue_size())),
+        }
+    }
 
-#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
-impl MetricsSettings {
-    #[inline]
-    ///Creates new instance with following defaults:
-    ///
-    ///- temporality is Cumulative
-    pub const fn new() -> Self {
-        Self {
-            temporality: opentelemetry_sdk::metrics::Temporality::Cumulative
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn log_processor(&self, exporter: crate::exporter::LogExporter, settings: &LogsSettings, ready: &Arc<AtomicBool>) -> crate::processor::LogProcessor {
+        let batch = settings.batch;
+        let exporter = self.wrap_exporter(exporter, matches!(self.export_mode, ExportMode::Batch), true, ready);
+        match self.export_mode {
+            ExportMode::Batch => crate::processor::LogProcessor::new(opentelemetry_sdk::logs::BatchLogProcessor::builder(exporter).with_batch_config(batch.builder::<opentelemetry_sdk::logs::BatchConfigBuilder>().build()).build()),
+            ExportMode::Simple => crate::processor::LogProcessor::new(opentelemetry_sdk::logs::SimpleLogProcessor::new(exporter)),
+            ExportMode::Manual => crate::processor::LogProcessor::new(crate::processor::ManualLogProcessor::new(exporter, batch.max_queue_size())),
         }
     }
 
-    #[inline]
-    ///Metrics are measured in cycles
-    pub const fn with_delta(mut self) -> Self {
-        self.temporality = opentelemetry_sdk::metrics::Temporality::Delta;
-        self
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Applies record hooks before `processor`, which is done once regardless of number of destinations
+    fn hook_log_processor(&self, processor: crate::processor::LogProcessor, settings: &LogsSettings) -> crate::processor::LogProcessor {
+        //Settings are applied first, so that user's hook observes exported record
+        let hook: Option<LogHook> = match (settings_hook(settings), self.log_processor_hook.as_ref()) {
+            (Some(settings), Some(hook)) => {
+                let hook = hook.clone();
+                Some(Arc::new(move |record: &mut opentelemetry_sdk::logs::SdkLogRecord| {
+                    settings(record);
+                    hook(record);
+                }))
+            },
+            (settings, hook) => settings.or_else(|| hook.cloned()),
+        };
+        match hook {
+            Some(hook) => crate::processor::LogProcessor::new(crate::processor::HookLogProcessor::new(hook, processor)),
+            None => processor,
+        }
     }
 
     #[inline]
-    ///Optimizes delta measured metrics for low memory usage
-    pub const fn with_low_memory(mut self) -> Self {
-        self.temporality = opentelemetry_sdk::metrics::Temporality::LowMemory;
+    ///Specify header to be included when exporting to primary destination
+    ///
+    ///Additional and fallback destinations do not inherit it, see [Target::with_header]
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
         self
     }
-}
 
-impl<'a> Builder<'a> {
+    #[cfg(feature = "grpc")]
     #[inline]
-    ///Starts building Opentelemetry integration
-    pub const fn new(destination: Destination<'a>) -> Self {
-        Self {
-            destination,
-            otlp: Otlp::new(),
-            headers: Vec::new(),
-            timeout: time::Duration::from_secs(5),
-            compression: true,
-        }
+    ///Specify existing `channel` to be used by all gRPC exporters instead of creating new one per exporter
+    ///
+    ///Destination's `url` and TLS configuration are ignored in this case.
+    ///Make sure that channel's timeout matches exporter's timeout.
+    ///
+    ///Requires `grpc` feature
+    pub fn with_grpc_channel(mut self, channel: tonic::transport::Channel) -> Self {
+        self.grpc_channel = Some(channel);
+        self
     }
 
+    #[cfg(feature = "grpc")]
     #[inline]
-    ///Specify whether to use compression by all OTLP exporters
+    ///Specify `interceptor` to modify each outgoing request of all gRPC exporters
     ///
-    ///Defaults to `true`
+    ///Subsequent calls replace previously set interceptor.
     ///
-    ///Has no effect if relevant `*-compression` are enabled
-    pub fn with_compression(mut self, compression: bool) -> Self {
-        self.compression = compression;
+    ///Requires `grpc` feature
+    pub fn with_grpc_interceptor(mut self, interceptor: impl Fn(tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> + Send + Sync + 'static) -> Self {
+        self.grpc_interceptor = Some(GrpcInterceptor(std::sync::Arc::new(interceptor)));
         self
     }
 
+    #[cfg(feature = "http")]
     #[inline]
-    ///Specify common timeout to be used by all OTLP exporters
+    ///Specify pre-configured `client` to be used by all HTTP exporters
     ///
-    ///Defaults to 5 seconds
-    pub fn with_timeout(mut self, timeout: time::Duration) -> Self {
-        self.timeout = timeout;
+    ///This allows to configure proxies, connection pools or custom root certificates of the client.
+    ///When not specified, client is selected according to enabled `http-*` features.
+    ///
+    ///Requires `http` feature
+    pub fn with_http_client(mut self, client: impl opentelemetry_http::HttpClient + 'static) -> Self {
+        self.http_client = Some(HttpClient(std::sync::Arc::new(client)));
         self
     }
 
+    #[cfg(any(feature = "http-reqwest", feature = "http-reqwest-blocking"))]
     #[inline]
-    ///Specify common header to be included for all OTLP destinations
-    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.headers.push((key.into(), value.into()));
+    ///Specify proxy `url` to be used by all HTTP exporters
+    ///
+    ///Hosts listed in `NO_PROXY` environment variable are excluded from proxying.
+    ///When proxy is not specified, reqwest client still honors `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment variables.
+    ///
+    ///Has no effect if custom client is provided via `with_http_client`
+    ///
+    ///Requires `http-reqwest` or `http-reqwest-blocking` feature, with blocking client being preferred when both are enabled.
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.http_proxy = Some(url.into());
         self
     }
 
-    ///Enables `logs` exporter with provided `attrs` annotating logs
-    ///
-    ///Panics if called more than once
-    pub fn with_logs(self, _attrs: Option<&Attributes>) -> Self {
-        if self.otlp.logs.is_some() {
-            panic!("Logs is already initialized")
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Returns resource to use for provider, which includes Datadog unified service tags in case `signal` is exported with [Protocol::DatadogAgent]
+    fn resource(&self, _signal: Signal, attrs: Option<&Attributes>) -> Option<opentelemetry_sdk::Resource> {
+        #[cfg(feature = "datadog")]
+        if self.is_datadog_agent(_signal) {
+            return Some(crate::datadog::with_unified_service_tags(attrs.map(|attrs| &attrs.0)));
+        }
+
+        attrs.map(|attrs| attrs.0.clone())
+    }
+
+    #[cfg(feature = "grpc")]
+    fn create_grpc_channel(&self, destination: &Destination<'_>) -> Option<tonic::transport::Channel> {
+        if self.grpc_channel.is_some() {
+            return self.grpc_channel.clone();
+        }
+
+        #[cfg(unix)]
+        if let Some(path) = destination.url.strip_prefix("unix://") {
+            return Some(create_uds_channel(path, self.timeout));
         }
 
-        let _exporter = match self.destination.protocol {
+        None
+    }
+
+    #[cfg(feature = "http")]
+    fn create_http_client(&self) -> Option<HttpClient> {
+        #[cfg(any(feature = "http-reqwest", feature = "http-reqwest-blocking"))]
+        if self.http_client.is_none() {
+            if let Some(proxy) = self.http_proxy.as_deref() {
+                return Some(create_proxy_http_client(proxy, self.timeout));
+            }
+        }
+
+        self.http_client.clone()
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Returns additional destinations of `signal` alongside their headers
+    fn additional_destinations(&self, signal: Signal) -> impl Iterator<Item = (&Destination<'a>, &[(String, String)])> {
+        self.additional_destinations.iter().filter(move |(kind, _)| *kind == signal).map(|(_, target)| (&target.destination, target.headers.as_slice()))
+    }
+
+    #[cfg(feature = "datadog")]
+    ///Returns whether any destination of `signal`, including fallback, is [Protocol::DatadogAgent]
+    fn is_datadog_agent(&self, signal: Signal) -> bool {
+        let fallback = self.fallback_destination.as_ref().map(|target| &target.destination);
+        core::iter::once(&self.destination).chain(fallback)
+                                           .chain(self.additional_destinations(signal).map(|(destination, _)| destination))
+                                           .any(|destination| destination.protocol == Protocol::DatadogAgent)
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Creates exporter for every destination of `signal`, wrapping primary one with failover if fallback destination is specified
+    fn create_exporters<T>(&self, signal: Signal, create: impl Fn(&Destination<'_>, &[(String, String)]) -> T, failover: impl FnOnce(crate::failover::FailoverExporter<T>) -> T) -> Vec<T> {
+        let primary = create(&self.destination, &self.headers);
+        let primary = match self.fallback_destination.as_ref() {
+            Some(fallback) => failover(crate::failover::FailoverExporter::new(primary, create(&fallback.destination, &fallback.headers), self.failover)),
+            None => primary,
+        };
+        core::iter::once(primary).chain(self.additional_destinations(signal).map(|(destination, headers)| create(destination, headers))).collect()
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Creates log exporter exporting to `destination`
+    fn create_log_exporter(&self, destination: &Destination<'_>, _headers: &[(String, String)], _settings: &LogsSettings) -> crate::exporter::LogExporter {
+        match destination.protocol {
             #[cfg(feature = "grpc")]
             Protocol::Grpc => {
                 use opentelemetry_otlp::{WithTonicConfig, WithExportConfig};
-                let mut builder = opentelemetry_otlp::LogExporter::builder().with_tonic().with_endpoint(self.destination.url.clone().into_owned());
+                let mut builder = opentelemetry_otlp::LogExporter::builder().with_tonic().with_endpoint(destination.url.clone().into_owned());
 
                 if cfg!(feature = "grpc-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = create_metadata_map(&self.headers);
+                if !_headers.is_empty() {
+                    let headers = create_metadata_map(_headers);
                     builder = builder.with_metadata(headers);
                 }
 
+                if let Some(channel) = self.create_grpc_channel(destination) {
+                    builder = builder.with_channel(channel);
+                }
+
+                if let Some(interceptor) = self.grpc_interceptor.as_ref() {
+                    builder = builder.with_interceptor(interceptor.clone());
+                }
+
 
                 let exporter = builder.with_timeout(self.timeout).build().expect("Failed to initialize logs grpc exporter");
-                opentelemetry_sdk::logs::BatchLogProcessor::builder(exporter).build()
+                self.log_exporter(exporter)
             },
             #[cfg(not(feature = "grpc"))]
             Protocol::Grpc => missing_grpc_feature(),
 
             #[cfg(feature = "datadog")]
             Protocol::DatadogAgent => {
-                if let Some(file_path) = self.destination.url.strip_prefix("file://") {
-                    opentelemetry_sdk::logs::BatchLogProcessor::builder(crate::datadog::file_exporter(file_path.to_owned().into())).build()
+                if let Some(file_path) = destination.url.strip_prefix("file://") {
+                    match _settings.file_rotation {
+                        Some(rotation) => self.log_exporter(crate::datadog::file_exporter_rotating(file_path.to_owned().into(), rotation)),
+                        None => self.log_exporter(crate::datadog::file_exporter(file_path.to_owned().into())),
+                    }
+                } else if let Some(address) = destination.url.strip_prefix("tcp://") {
+                    self.log_exporter(crate::datadog::TcpLogExporter::new(address, self.timeout))
                 } else {
-                    opentelemetry_sdk::logs::BatchLogProcessor::builder(crate::datadog::stdout_exporter()).build()
+                    self.log_exporter(crate::datadog::stdout_exporter())
                 }
             }
             #[cfg(not(feature = "datadog"))]
             Protocol::DatadogAgent => missing_datadog_feature(),
 
+            #[cfg(feature = "stdout")]
+            Protocol::Stdout => match _settings.stdout_format {
+                StdoutFormat::Text => self.log_exporter(crate::stdout::LogExporter::new(&destination.url)),
+                format => self.log_exporter(crate::stdout::JsonLogExporter::new(&destination.url, format)),
+            },
+            #[cfg(not(feature = "stdout"))]
+            Protocol::Stdout => missing_stdout_feature(),
+            #[cfg(feature = "file")]
+            Protocol::File => self.log_exporter(crate::file::LogExporter::new(&destination.url)),
+            #[cfg(not(feature = "file"))]
+            Protocol::File => missing_file_feature(),
+            #[cfg(feature = "testing")]
+            Protocol::InMemory => self.log_exporter(self.in_memory_exporters().logs.clone()),
+            #[cfg(not(feature = "testing"))]
+            Protocol::InMemory => missing_testing_feature(),
+
             #[cfg(feature = "http")]
             http => {
                 use opentelemetry_otlp::{WithHttpConfig, WithExportConfig};
-                let url = format!("{}/logs", self.destination.url.trim_end_matches('/'));
+                let url = format!("{}/logs", destination.url.trim_end_matches('/'));
                 let mut builder = opentelemetry_otlp::LogExporter::builder().with_http().with_protocol(http.into_otel()).with_endpoint(url);
 
                 if cfg!(feature = "http-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = self.headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+                if !_headers.is_empty() {
+                    let headers = _headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
                     builder = builder.with_headers(headers);
                 }
+
+                if let Some(client) = self.create_http_client() {
+                    builder = builder.with_http_client(client);
+                }
                 let exporter = builder.with_timeout(self.timeout).build().expect("Failed to initialize logs http exporter");
-                opentelemetry_sdk::logs::BatchLogProcessor::builder(exporter).build()
+                self.log_exporter(exporter)
             },
             #[cfg(not(feature = "http"))]
             _ => missing_http_feature(),
-        };
-
-        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog"))]
-        {
-            let mut this = self;
-            let mut builder = SdkLoggerProvider::builder();
-            if let Some(attrs) = _attrs {
-                builder = builder.with_resource(attrs.0.clone());
-            }
-
-            this.otlp.logs = Some(builder.with_log_processor(_exporter).build());
-            return this;
         }
     }
 
-    ///Enables `trace` exporter with provided `attrs` annotating traces
-    ///
-    ///Panics if called more than once
-    pub fn with_trace(self, _attrs: Option<&Attributes>, _settings: TraceSettings) -> Self {
-        if self.otlp.trace.is_some() {
-            panic!("Trace is already initialized")
-        }
-
-        let _batch_config = opentelemetry_sdk::trace::BatchConfigBuilder::default().build();
-        let _exporter = match self.destination.protocol {
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    ///Creates span exporter exporting to `destination`
+    fn create_span_exporter(&self, destination: &Destination<'_>, _headers: &[(String, String)], _settings: &TraceSettings) -> crate::exporter::SpanExporter {
+        match destination.protocol {
             #[cfg(feature = "grpc")]
             Protocol::Grpc => {
                 use opentelemetry_otlp::{WithTonicConfig, WithExportConfig};
-                let mut builder = opentelemetry_otlp::SpanExporter::builder().with_tonic().with_endpoint(self.destination.url.clone().into_owned());
+                let mut builder = opentelemetry_otlp::SpanExporter::builder().with_tonic().with_endpoint(destination.url.clone().into_owned());
 
                 if cfg!(feature = "grpc-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = create_metadata_map(&self.headers);
+                if !_headers.is_empty() {
+                    let headers = create_metadata_map(_headers);
                     builder = builder.with_metadata(headers);
                 }
 
+                if let Some(channel) = self.create_grpc_channel(destination) {
+                    builder = builder.with_channel(channel);
+                }
+
+                if let Some(interceptor) = self.grpc_interceptor.as_ref() {
+                    builder = builder.with_interceptor(interceptor.clone());
+                }
+
 
                 let exporter = builder.with_timeout(self.timeout).build().expect("Failed to initialize trace grpc exporter");
-                opentelemetry_sdk::trace::BatchSpanProcessor::new(exporter, _batch_config)
+                self.span_exporter(exporter)
             },
             #[cfg(not(feature = "grpc"))]
             Protocol::Grpc => missing_grpc_feature(),
 
             #[cfg(feature = "datadog")]
             Protocol::DatadogAgent => {
-                let exporter = opentelemetry_datadog::new_pipeline().with_agent_endpoint(self.destination.url.clone()).build_exporter().expect("Failed to initialize datadog exporter");
-                opentelemetry_sdk::trace::BatchSpanProcessor::new(exporter, _batch_config)
+                let mut pipeline = opentelemetry_datadog::new_pipeline().with_agent_endpoint(destination.url.clone());
+                if let Some(mapping) = _settings.datadog.name.clone() {
+                    pipeline = pipeline.with_name_mapping(datadog_mapping(mapping));
+                }
+                if let Some(mapping) = _settings.datadog.resource.clone() {
+                    pipeline = pipeline.with_resource_mapping(datadog_mapping(mapping));
+                }
+                if let Some(mapping) = _settings.datadog.service_name.clone() {
+                    pipeline = pipeline.with_service_name_mapping(datadog_mapping(mapping));
+                }
+                let exporter = pipeline.build_exporter().expect("Failed to initialize datadog exporter");
+                self.span_exporter(exporter)
             },
             #[cfg(not(feature = "datadog"))]
             Protocol::DatadogAgent => missing_datadog_feature(),
 
+            #[cfg(feature = "stdout")]
+            Protocol::Stdout => self.span_exporter(crate::stdout::SpanExporter::new(&destination.url)),
+            #[cfg(not(feature = "stdout"))]
+            Protocol::Stdout => missing_stdout_feature(),
+            #[cfg(feature = "file")]
+            Protocol::File => self.span_exporter(crate::file::SpanExporter::new(&destination.url)),
+            #[cfg(not(feature = "file"))]
+            Protocol::File => missing_file_feature(),
+            #[cfg(feature = "testing")]
+            Protocol::InMemory => self.span_exporter(self.in_memory_exporters().spans.clone()),
+            #[cfg(not(feature = "testing"))]
+            Protocol::InMemory => missing_testing_feature(),
+
             #[cfg(feature = "http")]
             http => {
                 use opentelemetry_otlp::{WithHttpConfig, WithExportConfig};
-                let url = format!("{}/traces", self.destination.url.trim_end_matches('/'));
+                let url = format!("{}/traces", destination.url.trim_end_matches('/'));
                 let mut builder = opentelemetry_otlp::SpanExporter::builder().with_http().with_protocol(http.into_otel()).with_endpoint(url);
 
                 if cfg!(feature = "http-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = self.headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+                if !_headers.is_empty() {
+                    let headers = _headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
                     builder = builder.with_headers(headers);
                 }
+
+                if let Some(client) = self.create_http_client() {
+                    builder = builder.with_http_client(client);
+                }
                 let exporter = builder.with_timeout(self.timeout).build().expect("Failed to initialize trace http exporter");
-                opentelemetry_sdk::trace::BatchSpanProcessor::new(exporter, _batch_config)
+                self.span_exporter(exporter)
             },
             #[cfg(not(feature = "http"))]
             _ => missing_http_feature(),
-        };
-
-        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog"))]
-        {
-            let mut this = self;
-            let sample_rate = _settings.sample_rate.clamp(0.0, 1.0);
-            let mut builder = SdkTracerProvider::builder().with_id_generator(opentelemetry_sdk::trace::RandomIdGenerator::default());
-            if _settings.respect_parent {
-                let sampler = opentelemetry_sdk::trace::Sampler::ParentBased(Box::new(opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(sample_rate)));
-                builder = builder.with_sampler(sampler);
-            } else {
-                if sample_rate == 0.0 {
-                    builder = builder.with_sampler(AlwaysOffSampler);
-                } else if sample_rate == 1.0 {
-                    builder = builder.with_sampler(AlwaysOnSampler);
-                } else {
-                    let sampler = opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(sample_rate);
-                    builder = builder.with_sampler(sampler);
-                }
-            }
-            builder = _settings.limits.apply_to(builder);
-            if let Some(attrs) = _attrs {
-                builder = builder.with_resource(attrs.0.clone());
-            }
-
-            this.otlp.trace = Some(builder.with_span_processor(_exporter).build());
-            return this;
         }
     }
 
-    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
-    ///Enables `metrics` exporter with provided `attrs` annotating metrics
-    ///
-    ///Panics if called more than once
-    pub fn with_metrics(self, _attrs: Option<&Attributes>, _settings: MetricsSettings) -> Self {
-        if self.otlp.metrics.is_some() {
-            panic!("Trace is already initialized")
-        }
-
-        let _exporter = match self.destination.protocol {
+    #[cfg(all(any(feature = "metrics", feature = "tracing-metrics"), any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+    ///Creates metric exporter exporting to `destination`
+    fn create_metric_exporter(&self, destination: &Destination<'_>, _headers: &[(String, String)], _settings: &MetricsSettings, _timeout: time::Duration) -> crate::exporter::MetricExporter {
+        match destination.protocol {
             #[cfg(feature = "grpc")]
             Protocol::Grpc => {
                 use opentelemetry_otlp::{WithTonicConfig, WithExportConfig};
-                let mut builder = opentelemetry_otlp::MetricExporter::builder().with_tonic().with_endpoint(self.destination.url.clone().into_owned()).with_temporality(_settings.temporality);
+                let mut builder = opentelemetry_otlp::MetricExporter::builder().with_tonic().with_endpoint(destination.url.clone().into_owned()).with_temporality(_settings.temporality);
 
                 if cfg!(feature = "grpc-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = create_metadata_map(&self.headers);
+                if !_headers.is_empty() {
+                    let headers = create_metadata_map(_headers);
                     builder = builder.with_metadata(headers);
                 }
 
+                if let Some(channel) = self.create_grpc_channel(destination) {
+                    builder = builder.with_channel(channel);
+                }
+
+                if let Some(interceptor) = self.grpc_interceptor.as_ref() {
+                    builder = builder.with_interceptor(interceptor.clone());
+                }
+
 
-                builder.with_timeout(self.timeout).build().expect("Failed to initialize metrics grpc exporter")
+                let exporter = builder.with_timeout(_timeout).build().expect("Failed to initialize metrics grpc exporter");
+                self.metric_exporter(exporter)
             },
             #[cfg(not(feature = "grpc"))]
             Protocol::Grpc => missing_grpc_feature(),
 
             #[cfg(feature = "datadog")]
-            Protocol::DatadogAgent => unsupported_datadog_feature(),
+            Protocol::DatadogAgent => {
+                let exporter = crate::dogstatsd::MetricExporter::new(&destination.url);
+                self.metric_exporter(exporter)
+            },
             #[cfg(not(feature = "datadog"))]
             Protocol::DatadogAgent => missing_datadog_feature(),
 
+            #[cfg(feature = "stdout")]
+            Protocol::Stdout => {
+                let exporter = crate::stdout::MetricExporter::new(&destination.url, _settings.temporality);
+                self.metric_exporter(exporter)
+            },
+            #[cfg(not(feature = "stdout"))]
+            Protocol::Stdout => missing_stdout_feature(),
+            #[cfg(feature = "file")]
+            Protocol::File => {
+                let exporter = crate::file::MetricExporter::new(&destination.url, _settings.temporality);
+                self.metric_exporter(exporter)
+            },
+            #[cfg(not(feature = "file"))]
+            Protocol::File => missing_file_feature(),
+            #[cfg(feature = "testing")]
+            Protocol::InMemory => self.metric_exporter(self.in_memory_exporters().metrics(_settings.temporality)),
+            #[cfg(not(feature = "testing"))]
+            Protocol::InMemory => missing_testing_feature(),
+
             #[cfg(feature = "http")]
             http => {
                 use opentelemetry_otlp::{WithHttpConfig, WithExportConfig};
-                let url = format!("{}/metrics", self.destination.url.trim_end_matches('/'));
+                let url = format!("{}/metrics", destination.url.trim_end_matches('/'));
                 let mut builder = opentelemetry_otlp::MetricExporter::builder().with_http().with_protocol(http.into_otel()).with_endpoint(url).with_temporality(_settings.temporality);
 
                 if cfg!(feature = "http-compression") && self.compression {
                     builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip)
                 }
 
-                if !self.headers.is_empty() {
-                    let headers = self.headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+                if !_headers.is_empty() {
+                    let headers = _headers.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
                     builder = builder.with_headers(headers);
                 }
-                builder.with_timeout(self.timeout).build().expect("Failed to initialize metrics http exporter")
+
+                if let Some(client) = self.create_http_client() {
+                    builder = builder.with_http_client(client);
+                }
+                let exporter = builder.with_timeout(_timeout).build().expect("Failed to initialize metrics http exporter");
+                self.metric_exporter(exporter)
             },
             #[cfg(not(feature = "http"))]
             _ => missing_http_feature(),
-        };
+        }
+    }
+
+    #[inline(always)]
+    ///Enables `logs` exporter with provided `attrs` annotating logs, using default [LogsSettings]
+    ///
+    ///Panics if called more than once
+    pub fn with_logs(self, attrs: Option<&Attributes>) -> Self {
+        self.with_logs_settings(attrs, LogsSettings::new())
+    }
+
+    ///Enables `logs` exporter with provided `attrs` annotating logs and `settings`
+    ///
+    ///Panics if called more than once
+    pub fn with_logs_settings(mut self, attrs: Option<&Attributes>, settings: LogsSettings) -> Self {
+        if self.logs.is_some() {
+            panic!("Logs is already initialized")
+        }
+        self.logs = Some((attrs.cloned(), settings));
+        self
+    }
+
+    fn build_logs(&mut self, _attrs: Option<Attributes>, _settings: LogsSettings) {
+        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+        {
+            let this = self;
+            let _ready = Arc::new(AtomicBool::new(false));
+            let exporters = this.create_exporters(Signal::Logs, |destination, headers| this.create_log_exporter(destination, headers, &_settings), crate::exporter::LogExporter::new);
+            let mut processors = exporters.into_iter().map(|exporter| this.log_processor(exporter, &_settings, &_ready)).collect::<Vec<_>>();
+            let processor = match processors.len() {
+                1 => processors.remove(0),
+                _ => crate::processor::LogProcessor::new(crate::processor::FanOutLogProcessor::new(processors)),
+            };
+            let processor = this.hook_log_processor(processor, &_settings);
+            let mut builder = SdkLoggerProvider::builder();
+            if let Some(resource) = this.resource(Signal::Logs, _attrs.as_ref()) {
+                builder = builder.with_resource(resource);
+            }
+
+            this.otlp.logs = Some(builder.with_log_processor(processor).build());
+            this.otlp.max_log_attributes = _settings.max_attributes_per_record;
+            this.otlp.ready.logs = Some(_ready);
+            return;
+        }
+
+        #[cfg(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+        missing_protocol_feature(self.destination.protocol)
+    }
+
+    ///Enables `trace` exporter with provided `attrs` annotating traces
+    ///
+    ///Panics if called more than once
+    pub fn with_trace(mut self, attrs: Option<&Attributes>, settings: TraceSettings) -> Self {
+        if self.trace.is_some() {
+            panic!("Trace is already initialized")
+        }
+        self.trace = Some((attrs.cloned(), settings));
+        self
+    }
+
+    fn build_trace(&mut self, _attrs: Option<Attributes>, _settings: TraceSettings) {
+        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+        {
+            let this = self;
+            let _attrs = _attrs.as_ref();
+            let _ready = Arc::new(AtomicBool::new(false));
+            let exporters = this.create_exporters(Signal::Trace, |destination, headers| this.create_span_exporter(destination, headers, &_settings), crate::exporter::SpanExporter::new);
+            let mut processors = exporters.into_iter().map(|exporter| this.span_processor(exporter, _settings.batch, &_ready)).collect::<Vec<_>>();
+            let _exporter = match processors.len() {
+                1 => processors.remove(0),
+                _ => crate::processor::SpanProcessor::new(crate::processor::FanOutSpanProcessor::new(processors)),
+            };
+            let sample_rate = _settings.sample_rate.clamp(0.0, 1.0);
+            let mut builder = SdkTracerProvider::builder().with_id_generator(opentelemetry_sdk::trace::RandomIdGenerator::default());
+            let hook = _settings.sampling_hook;
+            let sampler = if let Some(sampler) = _settings.sampler {
+                sampler
+            } else if let Some(handle) = _settings.dynamic_sampling {
+                CustomSampler(Box::new(DynamicSampler(handle)))
+            } else if sample_rate == 0.0 {
+                CustomSampler(Box::new(AlwaysOffSampler))
+            } else if sample_rate == 1.0 {
+                CustomSampler(Box::new(AlwaysOnSampler))
+            } else {
+                CustomSampler(Box::new(opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(sample_rate)))
+            };
+            #[cfg(feature = "jaeger-remote-sampler")]
+            let sampler = match _settings.remote_sampler {
+                Some((url, poll_interval)) => {
+                    let service_name = match _attrs {
+                        Some(attrs) => attrs.0.get(&crate::keys::SERVICE_NAME),
+                        None => opentelemetry_sdk::Resource::builder().build().get(&crate::keys::SERVICE_NAME),
+                    };
+                    let service_name = service_name.map(|name| name.to_string()).unwrap_or_else(|| "unknown_service".to_owned());
+                    let client = this.create_http_client().unwrap_or_else(|| HttpClient(Arc::new(reqwest::Client::new())));
+                    let remote = opentelemetry_sdk::trace::Sampler::jaeger_remote(opentelemetry_sdk::runtime::Tokio, client, sampler, service_name).with_endpoint(url)
+                                                                                                                                                  .with_update_interval(poll_interval)
+                                                                                                                                                  .build();
+                    match remote {
+                        Ok(remote) => CustomSampler(Box::new(remote)),
+                        Err(error) => panic!("Failed to initialize jaeger remote sampler: {error}"),
+                    }
+                },
+                None => sampler,
+            };
+            let sampler = match _settings.sampling_rules.is_empty() {
+                true => sampler,
+                false => CustomSampler(Box::new(crate::sampling::RuleSampler {
+                    rules: _settings.sampling_rules,
+                    fallback: sampler,
+                })),
+            };
+            let sampler = match _settings.respect_parent {
+                true => CustomSampler(Box::new(ParentBasedSampler::new(sampler.0))),
+                false => sampler,
+            };
+            #[cfg(feature = "datadog")]
+            let sampler = match this.is_datadog_agent(Signal::Trace) {
+                true => CustomSampler(Box::new(crate::datadog::Sampler::new(sampler, _settings.datadog.measured))),
+                false => sampler,
+            };
+            builder = with_sampler(builder, sampler, hook);
+            builder = _settings.limits.apply_to(builder);
+            if let Some(resource) = this.resource(Signal::Trace, _attrs) {
+                builder = builder.with_resource(resource);
+            }
+
+            let processor = match _settings.tail_sampling {
+                Some(tail_sampling) => crate::processor::SpanProcessor::new(crate::sampling::TailSamplingProcessor::new(_exporter, tail_sampling)),
+                None => _exporter,
+            };
+            let processor = match _settings.max_span_depth {
+                Some(max_depth) => crate::processor::SpanProcessor::new(crate::processor::DepthLimitProcessor::new(processor, max_depth)),
+                None => processor,
+            };
+            let processor = crate::processor::DedupAttributesProcessor::new(processor);
+            this.otlp.trace = Some(builder.with_span_processor(processor).build());
+            this.otlp.integer_overflow = _settings.integer_overflow;
+            this.otlp.ready.trace = Some(_ready);
+            return;
+        }
+
+        #[cfg(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+        missing_protocol_feature(self.destination.protocol)
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    ///Enables `metrics` exporter with provided `attrs` annotating metrics
+    ///
+    ///Panics if called more than once
+    pub fn with_metrics(mut self, attrs: Option<&Attributes>, settings: MetricsSettings) -> Self {
+        if self.metrics.is_some() {
+            panic!("Metrics is already initialized")
+        }
+        self.metrics = Some((attrs.cloned(), settings));
+        self
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    fn build_metrics(&mut self, _attrs: Option<Attributes>, _settings: MetricsSettings) {
+        let _attrs = _attrs.as_ref();
+        #[cfg(feature = "prometheus")]
+        if _settings.prometheus {
+            let this = self;
+            let mut builder = opentelemetry_sdk::metrics::SdkMeterProvider::builder();
+            if let Some(resource) = this.resource(Signal::Metrics, _attrs) {
+                builder = builder.with_resource(resource);
+            }
+            let (reader, handle) = crate::prometheus::reader();
+            let provider = _settings.with_views(builder).with_reader(reader).build();
+            if _settings.process_metrics {
+                crate::process::register(&provider);
+            }
+            this.otlp.metrics = Some(provider);
+            this.otlp.prometheus = Some(handle);
+            #[cfg(feature = "metrics")]
+            {
+                this.otlp.metrics_baggage_attrs = _settings.baggage_attrs;
+                this.otlp.metrics_dropped_attrs = _settings.dropped_attrs;
+            }
+            return;
+        }
 
-        #[cfg(any(feature = "grpc", feature = "http"))]
+        #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
         {
-            let mut this = self;
+            let this = self;
+            let _ready = Arc::new(AtomicBool::new(false));
+            let _timeout = _settings.export_timeout.unwrap_or(this.timeout);
+            let exporters = this.create_exporters(Signal::Metrics, |destination, headers| this.create_metric_exporter(destination, headers, &_settings, _timeout), |failover| crate::exporter::MetricExporter::new(failover));
             let mut builder = opentelemetry_sdk::metrics::SdkMeterProvider::builder();
-            if let Some(attrs) = _attrs {
-                builder = builder.with_resource(attrs.0.clone());
+            if let Some(resource) = this.resource(Signal::Metrics, _attrs) {
+                builder = builder.with_resource(resource);
+            }
+
+            let has_kind_temporality = _settings.has_kind_temporality();
+            builder = _settings.with_views(builder);
+            //Every destination gets own reader, as readers cannot be shared
+            for _exporter in exporters {
+                //Periodic reader exports on its own thread, while manual reader exports on the thread calling `Otlp::tick`
+                let _exporter = this.wrap_exporter_with_timeout(_exporter, !matches!(this.export_mode, ExportMode::Manual), true, _timeout, &_ready);
+                if let ExportMode::Manual = this.export_mode {
+                    let reader = KindTemporalityReader {
+                        inner: crate::processor::ManualMetricReader::new(_exporter),
+                        kind_temporality: _settings.kind_temporality,
+                    };
+                    builder = builder.with_reader(reader);
+                } else if has_kind_temporality {
+                    let reader = KindTemporalityReader {
+                        inner: _settings.periodic_reader(_exporter),
+                        kind_temporality: _settings.kind_temporality,
+                    };
+                    builder = builder.with_reader(reader);
+                } else {
+                    builder = builder.with_reader(_settings.periodic_reader(_exporter));
+                }
+            }
+            let provider = builder.build();
+            if _settings.process_metrics {
+                crate::process::register(&provider);
+            }
+            this.otlp.metrics = Some(provider);
+            this.otlp.ready.metrics = Some(_ready);
+            #[cfg(feature = "metrics")]
+            {
+                this.otlp.metrics_baggage_attrs = _settings.baggage_attrs;
+                this.otlp.metrics_dropped_attrs = _settings.dropped_attrs;
             }
+            return;
+        }
+
+        #[cfg(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")))]
+        missing_protocol_feature(self.destination.protocol)
+    }
+
+    ///Returns snapshot of current configuration
+    pub fn config(&self) -> OtlpConfig {
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        let metrics = self.metrics.is_some();
+        #[cfg(not(any(feature = "metrics", feature = "tracing-metrics")))]
+        let metrics = false;
+
+        OtlpConfig {
+            endpoint: redact_url(&self.destination.url),
+            protocol: self.destination.protocol,
+            headers: self.headers.iter().map(|(key, _)| key.clone()).collect(),
+            timeout: self.timeout,
+            compression: self.compression,
+            export_mode: self.export_mode,
+            retry_max_attempts: self.retry.map(|retry| retry.max_attempts),
+            logs: self.logs.is_some(),
+            trace: self.trace.is_some(),
+            metrics,
+            sample_rate: self.trace.as_ref().map(|(_, settings)| settings.sample_rate.clamp(0.0, 1.0)),
+        }
+    }
 
-            this.otlp.metrics = Some(builder.with_periodic_exporter(_exporter).build());
-            return this;
+    #[cfg(feature = "datadog")]
+    ///Probes Datadog agent if traces are exported to it
+    fn probe_datadog_agent(&self) -> Option<Result<DatadogAgentInfo, String>> {
+        match (self.destination.protocol, self.trace.is_some()) {
+            (Protocol::DatadogAgent, true) => Some(crate::datadog::probe_agent(&self.destination.url, self.timeout)),
+            _ => None,
+        }
+    }
+
+    ///Finalizes building otlp integration, verifying that Datadog agent is reachable
+    ///
+    ///Only Datadog agent is verified (when traces are exported with [Protocol::DatadogAgent]) by requesting its `/info` endpoint,
+    ///other protocols and destinations are not checked, making it the same as [finish](Self::finish)
+    pub fn try_finish(self) -> Result<Otlp, StartupError> {
+        #[cfg(feature = "datadog")]
+        {
+            let datadog_agent = match self.probe_datadog_agent() {
+                Some(Err(error)) => return Err(StartupError(format!("Datadog agent '{}' is unreachable: {error}", self.destination.url))),
+                result => result,
+            };
+            let mut otlp = Self {
+                datadog_probe: false,
+                ..self
+            }.finish();
+            otlp.datadog_agent = datadog_agent;
+            Ok(otlp)
         }
+
+        #[cfg(not(feature = "datadog"))]
+        Ok(self.finish())
     }
 
     #[inline]
     ///Finalizes building otlp integration
-    pub fn finish(self) -> Otlp {
-        self.otlp
+    pub fn finish(mut self) -> Otlp {
+        #[cfg(feature = "datadog")]
+        let datadog_agent = match self.datadog_probe {
+            true => self.probe_datadog_agent(),
+            false => None,
+        };
+
+        let config = self.config();
+        if let Some(path) = self.kill_switch.take() {
+            self.paused = spawn_kill_switch_watcher(path);
+        }
+        if let Some((attrs, settings)) = self.logs.take() {
+            self.build_logs(attrs, settings);
+        }
+        if let Some((attrs, settings)) = self.trace.take() {
+            self.build_trace(attrs, settings);
+        }
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if let Some((attrs, settings)) = self.metrics.take() {
+            self.build_metrics(attrs, settings);
+        }
+
+        let mut otlp = self.otlp;
+        otlp.config = config;
+        otlp.startup_summary = self.startup_summary;
+        #[cfg(feature = "datadog")]
+        {
+            otlp.datadog_agent = datadog_agent;
+        }
+        otlp
+    }
+}
+
+#[cfg(all(test, any(feature = "metrics", all(feature = "tracing-metrics", any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing")))))]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn should_match_glob_without_wildcards() {
+        assert!(glob_match("http.server.duration", "http.server.duration"));
+        assert!(!glob_match("http.server.duration", "http.server.durations"));
+        assert!(!glob_match("http.server.duration", "http.server"));
+    }
+
+    #[test]
+    fn should_match_glob_with_star() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("http.*", "http."));
+        assert!(glob_match("http.*", "http.server.duration"));
+        assert!(glob_match("*.duration", "http.server.duration"));
+        assert!(glob_match("http.**", "http.server"));
+        assert!(!glob_match("http.*", "grpc.server"));
+    }
+
+    #[test]
+    fn should_match_glob_with_question_mark() {
+        assert!(glob_match("http.server.request?", "http.server.requests"));
+        assert!(glob_match("???", "abc"));
+        assert!(!glob_match("???", "ab"));
+        assert!(!glob_match("???", "abcd"));
+        assert!(!glob_match("?", ""));
+    }
+
+    #[test]
+    fn should_match_empty_glob() {
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "a"));
+        assert!(!glob_match("a", ""));
+    }
+
+    #[test]
+    fn should_match_glob_with_backtracking() {
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(glob_match("a*bc", "abcbc"));
+        assert!(glob_match("*a?c", "abcaxc"));
+        assert!(glob_match("*.*.duration", "http.server.request.duration"));
+        assert!(!glob_match("a*b*c", "aXbYbZ"));
+        assert!(!glob_match("*ab", "aba"));
     }
 }
@@ -0,0 +1,58 @@
+//! Human readable console output alongside OpenTelemetry
+//!
+//!Provides `tracing_subscriber::fmt` layer in one of [FmtStyle], to be composed with OTLP layers.
+//!Use [Otlp::init_tracing_subscriber_with_fmt](crate::Otlp::init_tracing_subscriber_with_fmt) to install both at once.
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::{Otlp, tracing_subscriber, tracing};
+//!use tracing_opentelemetry_setup::console::{self, FmtStyle};
+//!use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+//!use tracing_subscriber::layer::SubscriberExt;
+//!
+//!let destination = Destination {
+//!    protocol: Protocol::HttpBinary,
+//!    url: "http://localhost:45081".into()
+//!};
+//!let otlp = Otlp::builder(destination).finish();
+//!let subscriber = tracing_subscriber::registry().with(console::layer(FmtStyle::Compact)).with(otlp.create_layer("test".into()));
+//!tracing::subscriber::with_default(subscriber, || {
+//!    tracing::info!("printed to stdout and exported");
+//!});
+//!```
+
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///Style of console output
+pub enum FmtStyle {
+    ///Verbose multi-line output, including source location and thread name, suitable for local development
+    Pretty,
+    ///Single line output with fields of spans appended to event
+    Compact,
+    ///Single line JSON object per event, suitable for log collectors
+    ///
+    ///Object contains `timestamp`, `level`, `target`, `fields` of event, current `span` and all `spans` starting from root
+    Json,
+}
+
+///Creates console layer writing to stdout in specified `style`
+///
+///Output is colored if stdout is terminal
+pub fn layer<S: tracing::Subscriber + for<'a> LookupSpan<'a>>(style: FmtStyle) -> Box<dyn tracing_subscriber::Layer<S> + Send + Sync> {
+    create_layer(style, std::io::stdout, std::io::IsTerminal::is_terminal(&std::io::stdout()))
+}
+
+///Creates console layer writing to `writer` in specified `style`, without colors
+pub fn layer_with_writer<S: tracing::Subscriber + for<'a> LookupSpan<'a>, W: for<'w> MakeWriter<'w> + Send + Sync + 'static>(style: FmtStyle, writer: W) -> Box<dyn tracing_subscriber::Layer<S> + Send + Sync> {
+    create_layer(style, writer, false)
+}
+
+fn create_layer<S: tracing::Subscriber + for<'a> LookupSpan<'a>, W: for<'w> MakeWriter<'w> + Send + Sync + 'static>(style: FmtStyle, writer: W, ansi: bool) -> Box<dyn tracing_subscriber::Layer<S> + Send + Sync> {
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(ansi);
+    match style {
+        FmtStyle::Pretty => Box::new(layer.pretty().with_thread_names(true)),
+        FmtStyle::Compact => Box::new(layer.compact()),
+        FmtStyle::Json => Box::new(layer.json().with_current_span(true).with_span_list(true)),
+    }
+}
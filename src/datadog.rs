@@ -1,106 +1,85 @@
 use std::{fs, io};
 use std::borrow::Cow;
-use core::{fmt, cmp};
+use core::fmt;
 use core::sync::atomic::{self, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 use opentelemetry_sdk::logs::LogBatch;
 use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
-use serde::ser::{SerializeSeq, SerializeMap};
+use serde::ser::SerializeMap;
 
-struct AnyValueSerde<'a>(&'a opentelemetry::logs::AnyValue);
+use crate::json::{AnyValueSerde, Buffer};
 
-impl serde::Serialize for AnyValueSerde<'_> {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        use opentelemetry::logs::AnyValue;
-
-        #[cold]
-        #[inline(never)]
-        fn unexpected_value<E: serde::ser::Error>(unexpected: &AnyValue) -> E {
-            E::custom(format_args!("Unsupported value: {:?}", unexpected))
-        }
-
-        match self.0 {
-            AnyValue::Boolean(value) => serializer.serialize_bool(*value),
-            AnyValue::Int(value) => serializer.serialize_i64(*value),
-            AnyValue::Double(value) => serializer.serialize_f64(*value),
-            AnyValue::String(value) => serializer.serialize_str(value.as_str()),
-            AnyValue::Bytes(value) => serializer.serialize_bytes(value),
-            AnyValue::ListAny(values) => {
-                let mut seq = serializer.serialize_seq(Some(values.len()))?;
-                for value in values.iter() {
-                    seq.serialize_element(&AnyValueSerde(value))?
-                }
-                seq.end()
-            },
-            AnyValue::Map(values) => {
-                let mut map = serializer.serialize_map(Some(values.len()))?;
-                for (key, value) in values.iter() {
-                    map.serialize_entry(key.as_str(), &AnyValueSerde(value))?
-                }
-                map.end()
-            },
-            //They use non exhaust for no reason so have to add this branch...
-            value => Err(unexpected_value(value)),
-        }
-    }
-}
+const SERVICE_NAME: opentelemetry::Key = opentelemetry::Key::from_static_str("service.name");
+const SERVICE_VERSION: opentelemetry::Key = opentelemetry::Key::from_static_str("service.version");
+const DEPLOYMENT_ENVIRONMENT: opentelemetry::Key = opentelemetry::Key::from_static_str("deployment.environment.name");
+///Value of `ddsource` field of log records
+const SOURCE: &str = "rust";
 
-pub struct Buffer {
-    inner: [u8; 1024],
-    len: usize,
+///Returns content of `DD_TAGS` as `key:value` pairs separated by comma or whitespace
+pub fn dd_tags() -> Vec<String> {
+    match std::env::var("DD_TAGS") {
+        Ok(tags) => tags.split([',', ' ']).map(str::trim).filter(|tag| !tag.is_empty()).map(ToOwned::to_owned).collect(),
+        Err(_) => Vec::new(),
+    }
 }
 
-impl Buffer {
-    pub const fn new() -> Self {
-        Self {
-            inner: [0; 1024],
-            len: 0,
+///Applies Datadog unified service tagging to `resource` (or default resource if `None`)
+///
+///`DD_SERVICE`, `DD_ENV` and `DD_VERSION` set `service.name`, `deployment.environment.name` and `service.version` respectively,
+///while every `key:value` of `DD_TAGS` is added as attribute. Environment variables take priority over attributes of `resource`
+pub fn with_unified_service_tags(resource: Option<&opentelemetry_sdk::Resource>) -> opentelemetry_sdk::Resource {
+    let mut attrs = Vec::new();
+    for tag in dd_tags() {
+        if let Some((key, value)) = tag.split_once(':') {
+            attrs.push(opentelemetry::KeyValue::new(key.to_owned(), value.to_owned()));
         }
     }
-
-    #[inline(always)]
-    pub fn as_str_with(&mut self, cb: impl FnOnce(&mut Self) -> bool) -> Option<&'_ str> {
-        if (cb)(self) {
-            self.as_str()
-        } else {
-            self.clear();
-            None
+    for (name, key) in [("DD_SERVICE", SERVICE_NAME), ("DD_ENV", DEPLOYMENT_ENVIRONMENT), ("DD_VERSION", SERVICE_VERSION)] {
+        match std::env::var(name) {
+            Ok(value) if !value.is_empty() => attrs.push(opentelemetry::KeyValue::new(key, value)),
+            _ => (),
         }
     }
 
-    #[inline(always)]
-    pub fn clear(&mut self) {
-        self.len = 0;
-    }
-
-    #[inline(always)]
-    pub fn as_str(&self) -> Option<&'_ str> {
-        core::str::from_utf8(&self.inner[..self.len]).ok()
+    let resource = match resource {
+        Some(resource) => resource.clone(),
+        None => opentelemetry_sdk::Resource::builder().build(),
+    };
+    if attrs.is_empty() {
+        return resource;
     }
+    let attrs = resource.iter().map(|(key, value)| opentelemetry::KeyValue::new(key.clone(), value.clone())).chain(attrs);
+    opentelemetry_sdk::Resource::builder_empty().with_attributes(attrs).build()
+}
 
-    pub fn push_bytes(&mut self, buf: &[u8]) -> usize {
-        let output = &mut self.inner[self.len..];
-        let written = cmp::min(output.len(), buf.len());
-        output[..written].copy_from_slice(buf);
-        self.len = self.len.saturating_add(written);
-        written
-    }
+#[derive(Default)]
+///Datadog reserved attributes of log records, derived from resource
+struct LogTags {
+    service: Option<String>,
+    tags: String,
 }
 
-impl io::Write for Buffer {
-    #[inline(always)]
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        Ok(self.push_bytes(buf))
-    }
+impl LogTags {
+    fn new(resource: &opentelemetry_sdk::Resource) -> Self {
+        let mut tags = Vec::new();
+        if let Some(env) = resource.get(&DEPLOYMENT_ENVIRONMENT) {
+            tags.push(format!("env:{env}"));
+        }
+        if let Some(version) = resource.get(&SERVICE_VERSION) {
+            tags.push(format!("version:{version}"));
+        }
+        tags.extend(dd_tags());
 
-    #[inline(always)]
-    fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+        Self {
+            service: resource.get(&SERVICE_NAME).map(|service| service.to_string()),
+            tags: tags.join(","),
+        }
     }
 }
 
-#[repr(transparent)]
-struct LogRecord<'a>(&'a opentelemetry_sdk::logs::SdkLogRecord);
+struct LogRecord<'a>(&'a opentelemetry_sdk::logs::SdkLogRecord, &'a LogTags);
 
 impl<'a> serde::Serialize for LogRecord<'a> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -111,9 +90,7 @@ impl<'a> serde::Serialize for LogRecord<'a> {
         }
 
         if let Some(timestamp) = self.0.timestamp().or_else(|| self.0.observed_timestamp()) {
-            let timestamp: time::UtcDateTime = timestamp.into();
-            let timestamp = buffer.as_str_with(|buffer| timestamp.format_into(buffer, &time::format_description::well_known::Rfc3339).is_ok());
-            if let Some(timestamp) = timestamp  {
+            if let Some(timestamp) = crate::json::rfc3339(&mut buffer, timestamp) {
                 map.serialize_entry("timestamp", &timestamp)?;
             }
             buffer.clear();
@@ -123,6 +100,14 @@ impl<'a> serde::Serialize for LogRecord<'a> {
             map.serialize_entry("level", severity_text)?;
         }
 
+        if let Some(service) = self.1.service.as_deref() {
+            map.serialize_entry("service", service)?;
+        }
+        map.serialize_entry("ddsource", SOURCE)?;
+        if !self.1.tags.is_empty() {
+            map.serialize_entry("ddtags", &self.1.tags)?;
+        }
+
         if let Some(ctx) = &self.0.trace_context() {
             //Imagine not giving proper accessor to inner value...
             let trace_id = u128::from_be_bytes(ctx.trace_id.to_bytes());
@@ -147,6 +132,7 @@ impl<'a> serde::Serialize for LogRecord<'a> {
 
 pub struct IoLogExporter<IO> {
     create_dest: IO,
+    tags: LogTags,
     is_shutdown: atomic::AtomicBool
 }
 
@@ -155,6 +141,7 @@ impl<O: io::Write, IO: Fn() -> io::Result<O> + Sync + Send + 'static> IoLogExpor
     pub fn new(create_dest: IO) -> Self {
         Self {
             create_dest,
+            tags: LogTags::default(),
             is_shutdown: atomic::AtomicBool::new(false),
         }
     }
@@ -172,7 +159,7 @@ impl<O: io::Write, IO: Fn() -> io::Result<O> + Sync + Send + 'static> openteleme
             Err(error) => return Err(opentelemetry_sdk::error::OTelSdkError::InternalFailure(error.to_string())),
         };
         for (record, _) in batch.iter() {
-            let record = LogRecord(record);
+            let record = LogRecord(record, &self.tags);
             if let Err(error) = serde_json::to_writer(&mut out, &record) {
                 return Err(opentelemetry_sdk::error::OTelSdkError::InternalFailure(error.to_string()))
             }
@@ -194,7 +181,8 @@ impl<O: io::Write, IO: Fn() -> io::Result<O> + Sync + Send + 'static> openteleme
     }
 
     #[inline(always)]
-    fn set_resource(&mut self, _res: &opentelemetry_sdk::Resource) {
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        self.tags = LogTags::new(res);
     }
 }
 
@@ -215,3 +203,235 @@ pub fn stdout_exporter() -> IoLogExporter<impl Fn() -> io::Result<io::StdoutLock
 pub fn file_exporter(path: Cow<'static, str>) -> IoLogExporter<impl Fn() -> io::Result<fs::File>> {
     IoLogExporter::new(move || fs::OpenOptions::new().append(true).create(true).open(&path.as_ref()))
 }
+
+struct RotatingFile {
+    path: Cow<'static, str>,
+    rotation: crate::builder::FileRotation,
+    //Time when current file was started, loaded lazily from existing file
+    started: Mutex<Option<SystemTime>>,
+}
+
+impl RotatingFile {
+    #[inline]
+    fn rotated_path(&self, idx: usize) -> String {
+        format!("{}.{idx}", self.path)
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        if self.rotation.keep == 0 {
+            return fs::remove_file(self.path.as_ref());
+        }
+
+        for idx in (1..self.rotation.keep).rev() {
+            match fs::rename(self.rotated_path(idx), self.rotated_path(idx + 1)) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+                _ => (),
+            }
+        }
+        fs::rename(self.path.as_ref(), self.rotated_path(1))
+    }
+
+    fn open(&self) -> io::Result<fs::File> {
+        let mut started = self.started.lock().unwrap_or_else(|error| error.into_inner());
+        let now = SystemTime::now();
+        if let Ok(metadata) = fs::metadata(self.path.as_ref()) {
+            let file_started = *started.get_or_insert_with(|| metadata.created().unwrap_or(now));
+            let is_full = self.rotation.max_size.is_some_and(|max_size| metadata.len() >= max_size);
+            let is_expired = self.rotation.interval.is_some_and(|interval| now.duration_since(file_started).unwrap_or_default() >= interval);
+            if is_full || is_expired {
+                self.rotate()?;
+                *started = Some(now);
+            }
+        } else {
+            *started = Some(now);
+        }
+
+        fs::OpenOptions::new().append(true).create(true).open(self.path.as_ref())
+    }
+}
+
+///Creates file exporter, rotating file according to `rotation` before export
+pub fn file_exporter_rotating(path: Cow<'static, str>, rotation: crate::builder::FileRotation) -> IoLogExporter<impl Fn() -> io::Result<fs::File>> {
+    let file = RotatingFile {
+        path,
+        rotation,
+        started: Mutex::new(None),
+    };
+    IoLogExporter::new(move || file.open())
+}
+
+///Default port of Datadog agent TCP logs intake
+const AGENT_LOGS_PORT: u16 = 10518;
+
+///Exporter sending the same JSON records as [IoLogExporter] to Datadog agent over TCP
+///
+///Whole batch is serialized before being written in one go, using single persistent connection which is re-established after failure.
+///Writes block at most `timeout`, after which batch is failed, so slow agent results in batch processor dropping records once its queue is full
+pub struct TcpLogExporter {
+    address: String,
+    timeout: core::time::Duration,
+    tags: LogTags,
+    stream: std::sync::Mutex<Option<std::net::TcpStream>>,
+    is_shutdown: atomic::AtomicBool
+}
+
+impl TcpLogExporter {
+    ///Creates exporter for `host[:port]`, using port 10518 if not specified
+    pub fn new(address: &str, timeout: core::time::Duration) -> Self {
+        let address = address.trim_end_matches('/');
+        let has_port = match address.rsplit_once(':') {
+            Some((_, port)) => !port.contains(']'),
+            None => false,
+        };
+        Self {
+            address: match has_port {
+                true => address.to_owned(),
+                false => format!("{address}:{AGENT_LOGS_PORT}"),
+            },
+            timeout,
+            tags: LogTags::default(),
+            stream: std::sync::Mutex::new(None),
+            is_shutdown: atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn connect(&self) -> io::Result<std::net::TcpStream> {
+        use std::net::ToSocketAddrs;
+
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "address is not resolved");
+        for address in self.address.to_socket_addrs()? {
+            match std::net::TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(stream) => {
+                    stream.set_write_timeout(Some(self.timeout))?;
+                    let _ = stream.set_nodelay(true);
+                    return Ok(stream);
+                },
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+
+    fn send(&self, payload: &[u8]) -> io::Result<()> {
+        use io::Write;
+
+        let mut stream = self.stream.lock().unwrap_or_else(|error| error.into_inner());
+        let connected = match stream.as_mut() {
+            Some(connected) => connected,
+            None => stream.insert(self.connect()?),
+        };
+        let result = connected.write_all(payload).and_then(|_| connected.flush());
+        if result.is_err() {
+            //Partially written record cannot be continued, hence connection must be re-established
+            *stream = None;
+        }
+        result
+    }
+}
+
+impl opentelemetry_sdk::logs::LogExporter for TcpLogExporter {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        if self.is_shutdown.load(Ordering::Acquire) {
+            return Err(OTelSdkError::AlreadyShutdown)
+        }
+
+        let mut payload = Vec::new();
+        for (record, _) in batch.iter() {
+            if let Err(error) = serde_json::to_writer(&mut payload, &LogRecord(record, &self.tags)) {
+                return Err(OTelSdkError::InternalFailure(error.to_string()))
+            }
+            payload.push(b'\n');
+        }
+        if payload.is_empty() {
+            return Ok(());
+        }
+
+        self.send(&payload).map_err(|error| OTelSdkError::InternalFailure(format!("Failed to send logs to '{}': {error}", self.address)))
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _timeout: core::time::Duration) -> OTelSdkResult {
+        self.is_shutdown.store(true, Ordering::Release);
+        if let Some(stream) = self.stream.lock().unwrap_or_else(|error| error.into_inner()).take() {
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        self.tags = LogTags::new(res);
+    }
+}
+
+impl fmt::Debug for TcpLogExporter {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TcpLogExporter")
+           .field("address", &self.address)
+           .field("is_shutdown", &self.is_shutdown.load(Ordering::Acquire))
+           .finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+///Sampler annotating trace state with Datadog sampling priority and measured flag, which are exported as `_sampling_priority_v1` and `_dd.measured`
+///
+///Without it, agent considers priority of every span to be auto-reject
+pub struct Sampler<S> {
+    inner: S,
+    measured: bool,
+}
+
+impl<S: opentelemetry_sdk::trace::ShouldSample + Clone + 'static> Sampler<S> {
+    #[inline(always)]
+    pub const fn new(inner: S, measured: bool) -> Self {
+        Self {
+            inner,
+            measured,
+        }
+    }
+}
+
+impl<S: opentelemetry_sdk::trace::ShouldSample + Clone + 'static> opentelemetry_sdk::trace::ShouldSample for Sampler<S> {
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        use opentelemetry_datadog::DatadogTraceState;
+
+        let mut result = self.inner.should_sample(parent_context, trace_id, name, span_kind, attributes, links);
+        let is_sampled = matches!(result.decision, opentelemetry::trace::SamplingDecision::RecordAndSample);
+        result.trace_state = result.trace_state.with_priority_sampling(is_sampled);
+        if self.measured {
+            result.trace_state = result.trace_state.with_measuring(true);
+        }
+        result
+    }
+}
+
+///Requests `/info` of Datadog agent at `url`
+///
+///Request is performed by blocking `reqwest` client on separate thread, hence it works regardless of async runtime presence
+pub fn probe_agent(url: &str, timeout: core::time::Duration) -> Result<crate::builder::DatadogAgentInfo, String> {
+    use opentelemetry_http::HttpClient;
+
+    let request = opentelemetry_http::Request::get(format!("{}/info", url.trim_end_matches('/')))
+                                              .header("Accept", "application/json")
+                                              .body(opentelemetry_http::Bytes::new())
+                                              .map_err(|error| format!("Invalid agent url: {error}"))?;
+    //Blocking client cannot be used within async runtime context
+    let response = std::thread::spawn(move || {
+        let client = reqwest::blocking::Client::builder().timeout(timeout).build().map_err(|error| format!("Unable to create HTTP client: {error}"))?;
+        crate::processor::block_on(client.send_bytes(request)).map_err(|error| format!("Request failed: {error}"))
+    }).join().map_err(|_| "HTTP client panicked".to_owned())??;
+
+    let body = core::str::from_utf8(response.body()).map_err(|error| format!("Invalid agent info: {error}"))?;
+    let body: serde_json::Value = serde_json::from_str(body).map_err(|error| format!("Invalid agent info: {error}"))?;
+    let version = body["version"].as_str().unwrap_or("unknown").to_owned();
+    let endpoints = match body["endpoints"].as_array() {
+        Some(endpoints) => endpoints.iter().filter_map(|endpoint| endpoint.as_str()).map(ToOwned::to_owned).collect(),
+        None => Vec::new(),
+    };
+    Ok(crate::builder::DatadogAgentInfo {
+        version,
+        endpoints,
+    })
+}
@@ -0,0 +1,257 @@
+//! Cloud provider resource detection
+
+use core::time;
+
+use opentelemetry::KeyValue;
+
+use crate::keys;
+
+const CONNECT_TIMEOUT: time::Duration = time::Duration::from_millis(500);
+const TIMEOUT: time::Duration = time::Duration::from_secs(2);
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn client() -> Option<reqwest::Client> {
+    //Metadata endpoints are link-local, so they must never go through proxy
+    reqwest::Client::builder().no_proxy().connect_timeout(CONNECT_TIMEOUT).timeout(TIMEOUT).build().ok()
+}
+
+async fn get_text(request: reqwest::RequestBuilder) -> Option<String> {
+    let response = request.send().await.ok()?.error_for_status().ok()?;
+    response.text().await.ok().filter(|text| !text.is_empty())
+}
+
+#[cfg(feature = "aws-detector")]
+async fn get_json(request: reqwest::RequestBuilder) -> Option<serde_json::Value> {
+    let response = request.send().await.ok()?.error_for_status().ok()?;
+    let body = response.bytes().await.ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+#[cfg(feature = "aws-detector")]
+fn str_field(value: &serde_json::Value, name: &str) -> Option<String> {
+    value.get(name)?.as_str().filter(|value| !value.is_empty()).map(ToOwned::to_owned)
+}
+
+#[cfg(feature = "aws-detector")]
+pub(crate) async fn aws() -> Vec<KeyValue> {
+    let mut attrs = Vec::new();
+
+    if let Some(function) = env("AWS_LAMBDA_FUNCTION_NAME") {
+        attrs.push(KeyValue::new(keys::CLOUD_PROVIDER, "aws"));
+        attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, "aws_lambda"));
+        attrs.push(KeyValue::new(keys::FAAS_NAME, function));
+        if let Some(region) = env("AWS_REGION") {
+            attrs.push(KeyValue::new(keys::CLOUD_REGION, region));
+        }
+        if let Some(version) = env("AWS_LAMBDA_FUNCTION_VERSION") {
+            attrs.push(KeyValue::new(keys::FAAS_VERSION, version));
+        }
+        if let Some(instance) = env("AWS_LAMBDA_LOG_STREAM_NAME") {
+            attrs.push(KeyValue::new(keys::FAAS_INSTANCE, instance));
+        }
+        if let Some(memory) = env("AWS_LAMBDA_FUNCTION_MEMORY_SIZE").and_then(|memory| memory.parse::<i64>().ok()) {
+            //Lambda reports memory in MiB, while semconv expects bytes
+            attrs.push(KeyValue::new(keys::FAAS_MAX_MEMORY, memory.saturating_mul(1024 * 1024)));
+        }
+        return attrs;
+    }
+
+    let client = match client() {
+        Some(client) => client,
+        None => return attrs,
+    };
+    match env("ECS_CONTAINER_METADATA_URI_V4") {
+        Some(uri) => aws_ecs(&client, &uri, &mut attrs).await,
+        None => aws_ec2(&client, &mut attrs).await,
+    }
+    attrs
+}
+
+#[cfg(feature = "aws-detector")]
+async fn aws_ecs(client: &reqwest::Client, uri: &str, attrs: &mut Vec<KeyValue>) {
+    let task = match get_json(client.get(format!("{uri}/task"))).await {
+        Some(task) => task,
+        None => return,
+    };
+    attrs.push(KeyValue::new(keys::CLOUD_PROVIDER, "aws"));
+    attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, "aws_ecs"));
+
+    //arn:aws:ecs:<region>:<account>:task/<cluster>/<id>
+    let mut region_account = None;
+    if let Some(arn) = str_field(&task, "TaskARN") {
+        let mut parts = arn.split(':').skip(3);
+        if let (Some(region), Some(account)) = (parts.next(), parts.next()) {
+            attrs.push(KeyValue::new(keys::CLOUD_REGION, region.to_owned()));
+            attrs.push(KeyValue::new(keys::CLOUD_ACCOUNT_ID, account.to_owned()));
+            region_account = Some((region.to_owned(), account.to_owned()));
+        }
+        attrs.push(KeyValue::new(keys::AWS_ECS_TASK_ARN, arn));
+    }
+    if let Some(cluster) = str_field(&task, "Cluster") {
+        let cluster = match region_account {
+            Some((region, account)) if !cluster.starts_with("arn:") => format!("arn:aws:ecs:{region}:{account}:cluster/{cluster}"),
+            _ => cluster,
+        };
+        attrs.push(KeyValue::new(keys::AWS_ECS_CLUSTER_ARN, cluster));
+    }
+    if let Some(zone) = str_field(&task, "AvailabilityZone") {
+        attrs.push(KeyValue::new(keys::CLOUD_AVAILABILITY_ZONE, zone));
+    }
+    if let Some(launch_type) = str_field(&task, "LaunchType") {
+        attrs.push(KeyValue::new(keys::AWS_ECS_LAUNCHTYPE, launch_type.to_ascii_lowercase()));
+    }
+    if let Some(family) = str_field(&task, "Family") {
+        attrs.push(KeyValue::new(keys::AWS_ECS_TASK_FAMILY, family));
+    }
+    if let Some(revision) = str_field(&task, "Revision") {
+        attrs.push(KeyValue::new(keys::AWS_ECS_TASK_REVISION, revision));
+    }
+
+    if let Some(container) = get_json(client.get(uri)).await {
+        if let Some(id) = str_field(&container, "DockerId") {
+            attrs.push(KeyValue::new(keys::CONTAINER_ID, id));
+        }
+        if let Some(name) = str_field(&container, "Name") {
+            attrs.push(KeyValue::new(keys::CONTAINER_NAME, name));
+        }
+        if let Some(arn) = str_field(&container, "ContainerARN") {
+            attrs.push(KeyValue::new(keys::AWS_ECS_CONTAINER_ARN, arn));
+        }
+    }
+}
+
+#[cfg(feature = "aws-detector")]
+async fn aws_ec2(client: &reqwest::Client, attrs: &mut Vec<KeyValue>) {
+    const TOKEN_HEADER: &str = "X-aws-ec2-metadata-token";
+
+    let endpoint = env("AWS_EC2_METADATA_SERVICE_ENDPOINT");
+    let endpoint = endpoint.as_deref().unwrap_or("http://169.254.169.254").trim_end_matches('/');
+
+    //IMDSv2 requires session token, but IMDSv1 may still be allowed
+    let token = get_text(client.put(format!("{endpoint}/latest/api/token")).header("X-aws-ec2-metadata-token-ttl-seconds", "60")).await;
+    let get = |path: &str| {
+        let request = client.get(format!("{endpoint}/latest/{path}"));
+        match token.as_deref() {
+            Some(token) => request.header(TOKEN_HEADER, token),
+            None => request,
+        }
+    };
+
+    let document = match get_json(get("dynamic/instance-identity/document")).await {
+        Some(document) => document,
+        None => return,
+    };
+    attrs.push(KeyValue::new(keys::CLOUD_PROVIDER, "aws"));
+    //EKS nodes are EC2 instances, so pod can only tell it apart by Kubernetes environment
+    let platform = match env("KUBERNETES_SERVICE_HOST") {
+        Some(_) => "aws_eks",
+        None => "aws_ec2",
+    };
+    attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, platform));
+
+    if let Some(region) = str_field(&document, "region") {
+        attrs.push(KeyValue::new(keys::CLOUD_REGION, region));
+    }
+    if let Some(zone) = str_field(&document, "availabilityZone") {
+        attrs.push(KeyValue::new(keys::CLOUD_AVAILABILITY_ZONE, zone));
+    }
+    if let Some(account) = str_field(&document, "accountId") {
+        attrs.push(KeyValue::new(keys::CLOUD_ACCOUNT_ID, account));
+    }
+    if let Some(id) = str_field(&document, "instanceId") {
+        attrs.push(KeyValue::new(keys::HOST_ID, id));
+    }
+    if let Some(kind) = str_field(&document, "instanceType") {
+        attrs.push(KeyValue::new(keys::HOST_TYPE, kind));
+    }
+    if let Some(image) = str_field(&document, "imageId") {
+        attrs.push(KeyValue::new(keys::HOST_IMAGE_ID, image));
+    }
+    if let Some(name) = get_text(get("meta-data/hostname")).await {
+        attrs.push(KeyValue::new(keys::HOST_NAME, name));
+    }
+}
+
+#[cfg(feature = "gcp-detector")]
+pub(crate) async fn gcp() -> Vec<KeyValue> {
+    let mut attrs = Vec::new();
+
+    let client = match client() {
+        Some(client) => client,
+        None => return attrs,
+    };
+    let host = env("GCE_METADATA_HOST");
+    let host = host.as_deref().unwrap_or("metadata.google.internal");
+    let get = |path: &str| get_text(client.get(format!("http://{host}/computeMetadata/v1/{path}")).header("Metadata-Flavor", "Google"));
+    //Values such as zone are returned as `projects/<number>/zones/<zone>`
+    let last_segment = |value: String| match value.rsplit_once('/') {
+        Some((_, value)) => value.to_owned(),
+        None => value,
+    };
+
+    let project = match get("project/project-id").await {
+        Some(project) => project,
+        None => return attrs,
+    };
+    attrs.push(KeyValue::new(keys::CLOUD_PROVIDER, "gcp"));
+    attrs.push(KeyValue::new(keys::CLOUD_ACCOUNT_ID, project));
+
+    let faas = match env("FUNCTION_TARGET") {
+        Some(_) => Some("gcp_cloud_functions"),
+        None => env("K_SERVICE").map(|_| "gcp_cloud_run"),
+    };
+    if let Some(platform) = faas {
+        attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, platform));
+        if let Some(name) = env("K_SERVICE").or_else(|| env("FUNCTION_TARGET")) {
+            attrs.push(KeyValue::new(keys::FAAS_NAME, name));
+        }
+        if let Some(version) = env("K_REVISION") {
+            attrs.push(KeyValue::new(keys::FAAS_VERSION, version));
+        }
+        if let Some(instance) = get("instance/id").await {
+            attrs.push(KeyValue::new(keys::FAAS_INSTANCE, instance));
+        }
+        if let Some(region) = get("instance/region").await {
+            attrs.push(KeyValue::new(keys::CLOUD_REGION, last_segment(region)));
+        }
+        return attrs;
+    }
+
+    if env("KUBERNETES_SERVICE_HOST").is_some() {
+        attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, "gcp_kubernetes_engine"));
+        if let Some(cluster) = get("instance/attributes/cluster-name").await {
+            attrs.push(KeyValue::new(keys::K8S_CLUSTER_NAME, cluster));
+        }
+        //Location is zone for zonal clusters (e.g. `us-central1-a`) and region for regional clusters
+        if let Some(location) = get("instance/attributes/cluster-location").await {
+            match location.matches('-').count() {
+                2 => attrs.push(KeyValue::new(keys::CLOUD_AVAILABILITY_ZONE, location)),
+                _ => attrs.push(KeyValue::new(keys::CLOUD_REGION, location)),
+            }
+        }
+        return attrs;
+    }
+
+    attrs.push(KeyValue::new(keys::CLOUD_PLATFORM, "gcp_compute_engine"));
+    if let Some(zone) = get("instance/zone").await {
+        let zone = last_segment(zone);
+        if let Some((region, _)) = zone.rsplit_once('-') {
+            attrs.push(KeyValue::new(keys::CLOUD_REGION, region.to_owned()));
+        }
+        attrs.push(KeyValue::new(keys::CLOUD_AVAILABILITY_ZONE, zone));
+    }
+    if let Some(id) = get("instance/id").await {
+        attrs.push(KeyValue::new(keys::HOST_ID, id));
+    }
+    if let Some(name) = get("instance/name").await {
+        attrs.push(KeyValue::new(keys::HOST_NAME, name));
+    }
+    if let Some(kind) = get("instance/machine-type").await {
+        attrs.push(KeyValue::new(keys::HOST_TYPE, last_segment(kind)));
+    }
+
+    attrs
+}
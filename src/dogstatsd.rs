@@ -0,0 +1,466 @@
+//! DogStatsD metric exporter
+//!
+//!Metrics are converted as following:
+//!- Monotonic sums are sent as `count` of delta since previous export;
+//!- Gauges and non-monotonic sums are sent as `gauge`;
+//!- Histograms are sent as `distribution`, using midpoint of every non-empty bucket with sample rate `1/<bucket count>`,
+//!  which lets agent to weight value by number of measurements within bucket.
+//!
+//!Exporter prefers delta temporality, but when it receives cumulative data (e.g. as fallback of OTLP destination)
+//!counts and histogram buckets are converted to deltas using values of previous export.
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{self, Ordering};
+use core::time;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::metrics::Temporality;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+
+const DEFAULT_PORT: u16 = 8125;
+//Safe payload size that fits into ethernet MTU
+const UDP_MAX_PACKET: usize = 1432;
+#[cfg(unix)]
+const UDS_MAX_PACKET: usize = 8192;
+//Interval after which UDP socket is re-connected, re-resolving host of DogStatsD server (e.g. when agent pod is re-scheduled)
+const RESOLVE_INTERVAL: time::Duration = time::Duration::from_secs(60);
+
+#[derive(Debug)]
+enum Address {
+    Udp(String),
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+impl Address {
+    ///Resolves address of DogStatsD server
+    ///
+    ///`DD_DOGSTATSD_URL` takes priority, otherwise host of agent `url` is used with port `DD_DOGSTATSD_PORT` (8125 by default)
+    fn new(url: &str) -> Self {
+        let url = match std::env::var("DD_DOGSTATSD_URL") {
+            Ok(url) if !url.is_empty() => Cow::Owned(url),
+            _ => Cow::Borrowed(url),
+        };
+
+        #[cfg(unix)]
+        if let Some(path) = url.strip_prefix("unix://") {
+            return Self::Unix(path.into());
+        }
+        if let Some(address) = url.strip_prefix("udp://") {
+            return Self::Udp(address.to_owned());
+        }
+
+        let host = url.split_once("://").map_or(url.as_ref(), |(_, rest)| rest);
+        let host = host.split(['/', '?']).next().unwrap_or_default();
+        let host = match host.rsplit_once(':') {
+            //Skip port unless it is part of IPv6 address
+            Some((host, port)) if !port.contains(']') => host,
+            _ => host,
+        };
+        let host = match host.is_empty() {
+            true => "127.0.0.1",
+            false => host,
+        };
+        let port = std::env::var("DD_DOGSTATSD_PORT").ok().and_then(|port| port.parse::<u16>().ok()).unwrap_or(DEFAULT_PORT);
+        Self::Udp(format!("{host}:{port}"))
+    }
+
+    #[inline]
+    fn max_packet(&self) -> usize {
+        match self {
+            Self::Udp(_) => UDP_MAX_PACKET,
+            #[cfg(unix)]
+            Self::Unix(_) => UDS_MAX_PACKET,
+        }
+    }
+
+    fn connect(&self) -> std::io::Result<Socket> {
+        match self {
+            Self::Udp(address) => {
+                let socket = std::net::UdpSocket::bind(("0.0.0.0", 0))?;
+                socket.connect(address.as_str())?;
+                Ok(Socket::Udp(socket))
+            },
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let socket = std::os::unix::net::UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                Ok(Socket::Unix(socket))
+            }
+        }
+    }
+}
+
+enum Socket {
+    Udp(std::net::UdpSocket),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixDatagram),
+}
+
+impl Socket {
+    #[inline]
+    fn send(&self, packet: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Udp(socket) => socket.send(packet),
+            #[cfg(unix)]
+            Self::Unix(socket) => socket.send(packet),
+        }
+    }
+
+    #[inline]
+    ///Returns whether socket should be re-connected to pick up new address of the server
+    fn is_stale(&self, connected_at: Instant) -> bool {
+        match self {
+            Self::Udp(_) => connected_at.elapsed() >= RESOLVE_INTERVAL,
+            #[cfg(unix)]
+            Self::Unix(_) => false,
+        }
+    }
+}
+
+trait Value: Copy + fmt::Display {
+    fn as_f64(self) -> f64;
+}
+
+impl Value for u64 {
+    #[inline(always)]
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Value for i64 {
+    #[inline(always)]
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Value for f64 {
+    #[inline(always)]
+    fn as_f64(self) -> f64 {
+        self
+    }
+}
+
+///Writes `text` replacing characters reserved by DogStatsD protocol
+fn write_sanitized(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '|' | ':' | '@' | '#' | ',' | '\n' | '\r' => out.push('_'),
+            ch => out.push(ch),
+        }
+    }
+}
+
+fn write_tag(out: &mut String, key: &str, value: &str) {
+    if out.is_empty() {
+        out.push_str("|#");
+    } else {
+        out.push(',');
+    }
+    write_sanitized(out, key);
+    if value.is_empty() {
+        return;
+    }
+    out.push(':');
+    //Tag values may contain `:`
+    for ch in value.chars() {
+        match ch {
+            '|' | '@' | '#' | ',' | '\n' | '\r' => out.push('_'),
+            ch => out.push(ch),
+        }
+    }
+}
+
+///Creates tags from resource attributes understood by Datadog (unified service tagging) and `DD_TAGS`
+fn resource_tags(resource: &opentelemetry_sdk::Resource) -> String {
+    const MAPPING: [(&str, &str); 4] = [
+        ("service.name", "service"),
+        ("service.version", "version"),
+        ("deployment.environment.name", "env"),
+        ("deployment.environment", "env"),
+    ];
+
+    let mut tags = String::new();
+    let mut has_env = false;
+    for (key, tag) in MAPPING {
+        if tag == "env" && has_env {
+            continue;
+        }
+        if let Some(value) = resource.get(&opentelemetry::Key::from_static_str(key)) {
+            has_env |= tag == "env";
+            write_tag(&mut tags, tag, value.as_str().as_ref());
+        }
+    }
+    for tag in crate::datadog::dd_tags() {
+        let (key, value) = tag.split_once(':').unwrap_or((tag.as_str(), ""));
+        write_tag(&mut tags, key, value);
+    }
+    tags
+}
+
+struct Packets {
+    max_size: usize,
+    packets: Vec<String>,
+}
+
+impl Packets {
+    #[inline]
+    const fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            packets: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        match self.packets.last_mut() {
+            Some(packet) if packet.len() + 1 + line.len() <= self.max_size => {
+                packet.push('\n');
+                packet.push_str(line);
+            },
+            _ => self.packets.push(line.to_owned()),
+        }
+    }
+}
+
+#[derive(Default)]
+///Values of cumulative series reported by previous export, used to send deltas
+struct Cumulative {
+    sums: HashMap<String, f64>,
+    histograms: HashMap<String, Vec<u64>>,
+}
+
+impl Cumulative {
+    ///Returns increase of `series` since previous export, treating decrease as counter reset
+    fn sum_delta(&self, updates: &mut CumulativeUpdates, series: String, value: f64) -> f64 {
+        let delta = match self.sums.get(&series) {
+            Some(previous) if *previous <= value => value - previous,
+            _ => value,
+        };
+        updates.sums.push((series, value));
+        delta
+    }
+
+    ///Returns increase of every bucket of `series` since previous export, treating decrease as histogram reset
+    fn bucket_deltas(&self, updates: &mut CumulativeUpdates, series: String, counts: Vec<u64>) -> Vec<u64> {
+        let deltas = match self.histograms.get(&series) {
+            Some(previous) if previous.len() == counts.len() && previous.iter().zip(counts.iter()).all(|(previous, count)| previous <= count) => {
+                counts.iter().zip(previous.iter()).map(|(count, previous)| count - previous).collect()
+            },
+            _ => counts.clone(),
+        };
+        updates.histograms.push((series, counts));
+        deltas
+    }
+
+    ///Remembers values of `updates`, once they are sent
+    fn apply(&mut self, updates: CumulativeUpdates) {
+        self.sums.extend(updates.sums);
+        self.histograms.extend(updates.histograms);
+    }
+}
+
+#[derive(Default)]
+///Cumulative values of current export, which are only remembered once export succeeds so that retry sends the same deltas
+struct CumulativeUpdates {
+    sums: Vec<(String, f64)>,
+    histograms: Vec<(String, Vec<u64>)>,
+}
+
+fn push_metric<T: Value>(packets: &mut Packets, cumulative: (&Cumulative, &mut CumulativeUpdates), name: &str, resource_tags: &str, data: &MetricData<T>) {
+    let mut sanitized_name = String::with_capacity(name.len());
+    write_sanitized(&mut sanitized_name, name);
+    let name = sanitized_name;
+    let tags = |attrs: &mut dyn Iterator<Item = &opentelemetry::KeyValue>| {
+        let mut tags = resource_tags.to_owned();
+        for attr in attrs {
+            write_tag(&mut tags, attr.key.as_str(), attr.value.as_str().as_ref());
+        }
+        tags
+    };
+
+    let mut line = String::new();
+    match data {
+        MetricData::Gauge(gauge) => for point in gauge.data_points() {
+            line.clear();
+            let _ = write!(line, "{name}:{}|g{}", point.value(), tags(&mut point.attributes()));
+            packets.push(&line);
+        },
+        MetricData::Sum(sum) => {
+            let kind = match sum.is_monotonic() {
+                true => "c",
+                false => "g",
+            };
+            let is_cumulative = sum.is_monotonic() && sum.temporality() == Temporality::Cumulative;
+            for point in sum.data_points() {
+                let tags = tags(&mut point.attributes());
+                let value = match is_cumulative {
+                    true => cumulative.0.sum_delta(cumulative.1, format!("{name}{tags}"), point.value().as_f64()),
+                    false => point.value().as_f64(),
+                };
+                line.clear();
+                let _ = write!(line, "{name}:{value}|{kind}{tags}");
+                packets.push(&line);
+            }
+        },
+        MetricData::Histogram(histogram) => for point in histogram.data_points() {
+            let tags = tags(&mut point.attributes());
+            let min = point.min().map(Value::as_f64);
+            let max = point.max().map(Value::as_f64);
+            let bounds = point.bounds().collect::<Vec<_>>();
+            let counts = point.bucket_counts().collect::<Vec<_>>();
+            let counts = match histogram.temporality() {
+                Temporality::Cumulative => cumulative.0.bucket_deltas(cumulative.1, format!("{name}{tags}"), counts),
+                _ => counts,
+            };
+            for (idx, count) in counts.into_iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+
+                let lower = match idx {
+                    0 => min.or_else(|| bounds.first().copied()),
+                    idx => bounds.get(idx - 1).copied(),
+                };
+                let upper = bounds.get(idx).copied().or(max).or(lower);
+                let value = match (lower, upper) {
+                    (Some(lower), Some(upper)) => lower + (upper - lower) / 2.0,
+                    (None, Some(value)) | (Some(value), None) => value,
+                    (None, None) => point.sum().as_f64() / point.count() as f64,
+                };
+
+                line.clear();
+                let _ = write!(line, "{name}:{value}|d");
+                if count > 1 {
+                    let _ = write!(line, "|@{}", 1.0 / count as f64);
+                }
+                line.push_str(&tags);
+                packets.push(&line);
+            }
+        },
+        //Exponential histogram is never produced as aggregation is not configurable via this crate
+        MetricData::ExponentialHistogram(_) => (),
+    }
+}
+
+///Exporter sending metrics to DogStatsD server over UDP or Unix domain socket
+pub struct MetricExporter {
+    address: Address,
+    socket: Mutex<Option<(Socket, Instant)>>,
+    cumulative: Mutex<Cumulative>,
+    is_shutdown: atomic::AtomicBool,
+}
+
+impl MetricExporter {
+    #[inline]
+    pub fn new(url: &str) -> Self {
+        Self {
+            address: Address::new(url),
+            socket: Mutex::new(None),
+            cumulative: Mutex::new(Cumulative::default()),
+            is_shutdown: atomic::AtomicBool::new(false),
+        }
+    }
+
+    ///Sends packets to the server
+    ///
+    ///Error is only returned when no packet could be sent, as otherwise retry would send the same metrics again and double count them.
+    ///Packets that fail to be sent after the first successful one are dropped, which is acceptable for DogStatsD as it is lossy by design.
+    fn send(&self, packets: Packets) -> OTelSdkResult {
+        let mut socket = self.socket.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some((connected, connected_at)) = socket.as_ref() {
+            if connected.is_stale(*connected_at) {
+                *socket = None;
+            }
+        }
+        let connected = match socket.as_ref() {
+            Some((connected, _)) => connected,
+            None => {
+                let connected = self.address.connect().map_err(|error| OTelSdkError::InternalFailure(format!("Failed to connect to DogStatsD {:?}: {error}", self.address)))?;
+                &socket.insert((connected, Instant::now())).0
+            }
+        };
+
+        let mut sent = 0;
+        let mut last_error = None;
+        for packet in packets.packets.iter() {
+            match connected.send(packet.as_bytes()) {
+                Ok(_) => sent += 1,
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        match last_error {
+            None => Ok(()),
+            Some(error) => {
+                //Reconnect on next export
+                *socket = None;
+                if sent == 0 {
+                    Err(OTelSdkError::InternalFailure(format!("Failed to send DogStatsD packet: {error}")))
+                } else {
+                    tracing::warn!(target: "tracing_opentelemetry_setup", dropped = packets.packets.len() - sent, %error, "Failed to send some DogStatsD packets");
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MetricExporter {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DogStatsdExporter")
+           .field("address", &self.address)
+           .field("is_shutdown", &self.is_shutdown.load(Ordering::Acquire))
+           .finish()
+    }
+}
+
+impl opentelemetry_sdk::metrics::exporter::PushMetricExporter for MetricExporter {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        if self.is_shutdown.load(Ordering::Acquire) {
+            return Err(OTelSdkError::AlreadyShutdown);
+        }
+
+        let resource_tags = resource_tags(metrics.resource());
+        let mut packets = Packets::new(self.address.max_packet());
+        let mut cumulative = self.cumulative.lock().unwrap_or_else(|error| error.into_inner());
+        let mut updates = CumulativeUpdates::default();
+        for scope in metrics.scope_metrics() {
+            for metric in scope.metrics() {
+                match metric.data() {
+                    AggregatedMetrics::F64(data) => push_metric(&mut packets, (&cumulative, &mut updates), metric.name(), &resource_tags, data),
+                    AggregatedMetrics::U64(data) => push_metric(&mut packets, (&cumulative, &mut updates), metric.name(), &resource_tags, data),
+                    AggregatedMetrics::I64(data) => push_metric(&mut packets, (&cumulative, &mut updates), metric.name(), &resource_tags, data),
+                }
+            }
+        }
+
+        self.send(packets)?;
+        cumulative.apply(updates);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.is_shutdown.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        //Counts and distributions must be deltas, while up down counters are reported as gauges of their current value
+        opentelemetry_sdk::metrics::Temporality::LowMemory
+    }
+}
@@ -0,0 +1,220 @@
+//! Type erased exporters
+//!
+//!Used by [Builder](crate::builder::Builder) to let users wrap exporters with own middleware (e.g. audit logging)
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::Otlp;
+//!use tracing_opentelemetry_setup::builder::{Destination, Protocol};
+//!use tracing_opentelemetry_setup::exporter::SpanExporter;
+//!use tracing_opentelemetry_setup::opentelemetry_sdk::error::OTelSdkResult;
+//!use tracing_opentelemetry_setup::opentelemetry_sdk::trace::SpanData;
+//!
+//!#[derive(Debug)]
+//!struct Audit(SpanExporter);
+//!
+//!impl tracing_opentelemetry_setup::opentelemetry_sdk::trace::SpanExporter for Audit {
+//!    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+//!        let len = batch.len();
+//!        let result = self.0.export(batch).await;
+//!        eprintln!("Exported {len} spans: {result:?}");
+//!        result
+//!    }
+//!}
+//!
+//!let destination = Destination {
+//!    protocol: Protocol::HttpBinary,
+//!    url: "http://localhost:45081".into()
+//!};
+//!let builder = Otlp::builder(destination).map_span_exporter(|exporter| SpanExporter::new(Audit(exporter)));
+//!```
+
+use core::{fmt, time};
+use core::future::Future;
+use core::pin::Pin;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = OTelSdkResult> + Send + 'a>>;
+
+trait DynSpanExporter: Send + Sync + fmt::Debug {
+    fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> BoxFuture<'_>;
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult;
+    fn force_flush(&mut self) -> OTelSdkResult;
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource);
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> DynSpanExporter for T {
+    #[inline(always)]
+    fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> BoxFuture<'_> {
+        Box::pin(opentelemetry_sdk::trace::SpanExporter::export(self, batch))
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        opentelemetry_sdk::trace::SpanExporter::shutdown_with_timeout(self, timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        opentelemetry_sdk::trace::SpanExporter::force_flush(self)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        opentelemetry_sdk::trace::SpanExporter::set_resource(self, resource)
+    }
+}
+
+///Type erased span exporter
+#[derive(Debug)]
+pub struct SpanExporter(Box<dyn DynSpanExporter>);
+
+impl SpanExporter {
+    #[inline(always)]
+    ///Wraps `exporter`
+    pub fn new(exporter: impl opentelemetry_sdk::trace::SpanExporter + 'static) -> Self {
+        Self(Box::new(exporter))
+    }
+}
+
+impl opentelemetry_sdk::trace::SpanExporter for SpanExporter {
+    #[inline(always)]
+    fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> impl Future<Output = OTelSdkResult> + Send {
+        self.0.export(batch)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.0.set_resource(resource)
+    }
+}
+
+trait DynLogExporter: Send + Sync + fmt::Debug {
+    fn export<'a>(&'a self, batch: opentelemetry_sdk::logs::LogBatch<'a>) -> BoxFuture<'a>;
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult;
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource);
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> DynLogExporter for T {
+    #[inline(always)]
+    fn export<'a>(&'a self, batch: opentelemetry_sdk::logs::LogBatch<'a>) -> BoxFuture<'a> {
+        Box::pin(opentelemetry_sdk::logs::LogExporter::export(self, batch))
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        opentelemetry_sdk::logs::LogExporter::shutdown_with_timeout(self, timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        opentelemetry_sdk::logs::LogExporter::set_resource(self, resource)
+    }
+}
+
+///Type erased log exporter
+#[derive(Debug)]
+pub struct LogExporter(Box<dyn DynLogExporter>);
+
+impl LogExporter {
+    #[inline(always)]
+    ///Wraps `exporter`
+    pub fn new(exporter: impl opentelemetry_sdk::logs::LogExporter + 'static) -> Self {
+        Self(Box::new(exporter))
+    }
+}
+
+impl opentelemetry_sdk::logs::LogExporter for LogExporter {
+    #[inline(always)]
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        self.0.export(batch).await
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.0.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+trait DynMetricExporter: Send + Sync + fmt::Debug {
+    fn export<'a>(&'a self, metrics: &'a opentelemetry_sdk::metrics::data::ResourceMetrics) -> BoxFuture<'a>;
+    fn force_flush(&self) -> OTelSdkResult;
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult;
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality;
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter + fmt::Debug> DynMetricExporter for T {
+    #[inline(always)]
+    fn export<'a>(&'a self, metrics: &'a opentelemetry_sdk::metrics::data::ResourceMetrics) -> BoxFuture<'a> {
+        Box::pin(opentelemetry_sdk::metrics::exporter::PushMetricExporter::export(self, metrics))
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        opentelemetry_sdk::metrics::exporter::PushMetricExporter::force_flush(self)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        opentelemetry_sdk::metrics::exporter::PushMetricExporter::shutdown_with_timeout(self, timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        opentelemetry_sdk::metrics::exporter::PushMetricExporter::temporality(self)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Type erased metric exporter
+#[derive(Debug)]
+pub struct MetricExporter(Box<dyn DynMetricExporter>);
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl MetricExporter {
+    #[inline(always)]
+    ///Wraps `exporter`
+    pub fn new(exporter: impl opentelemetry_sdk::metrics::exporter::PushMetricExporter + fmt::Debug) -> Self {
+        Self(Box::new(exporter))
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl opentelemetry_sdk::metrics::exporter::PushMetricExporter for MetricExporter {
+    #[inline(always)]
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        self.0.export(metrics).await
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.0.temporality()
+    }
+}
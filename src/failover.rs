@@ -0,0 +1,206 @@
+use core::time;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+use crate::builder::FailoverSettings;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Route {
+    ///Export to primary destination
+    Primary,
+    ///Export to fallback destination
+    Fallback,
+    ///Attempt to export to primary destination, going back to fallback on failure
+    Probe,
+}
+
+#[derive(Debug)]
+struct State {
+    failures: u32,
+    ///Time of switching to fallback or last probe of primary
+    fallback_since: Option<Instant>,
+}
+
+///Exporter wrapper that switches to `fallback` once `primary` fails to export configured number of consecutive times
+///
+///While fallback is used, primary is probed with real data every `probe_interval`, and it is used again once export succeeds
+#[derive(Debug)]
+pub struct FailoverExporter<T> {
+    primary: T,
+    fallback: T,
+    settings: FailoverSettings,
+    state: Mutex<State>,
+}
+
+impl<T> FailoverExporter<T> {
+    #[inline(always)]
+    pub fn new(primary: T, fallback: T, settings: FailoverSettings) -> Self {
+        Self {
+            primary,
+            fallback,
+            settings,
+            state: Mutex::new(State {
+                failures: 0,
+                fallback_since: None,
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn route(&self) -> Route {
+        let mut state = self.state();
+        match state.fallback_since {
+            None => Route::Primary,
+            Some(since) if since.elapsed() >= self.settings.probe_interval => {
+                state.fallback_since = Some(Instant::now());
+                Route::Probe
+            },
+            Some(_) => Route::Fallback,
+        }
+    }
+
+    ///Returns whether next failure of primary would switch to fallback
+    #[inline(always)]
+    fn is_last_chance(&self) -> bool {
+        self.state().failures.saturating_add(1) >= self.settings.max_failures
+    }
+
+    ///Records result of exporting to primary, returning `true` if exporter switched to fallback
+    fn on_primary(&self, route: Route, result: &OTelSdkResult) -> bool {
+        let mut state = self.state();
+        match result {
+            Ok(()) => {
+                state.failures = 0;
+                if route == Route::Probe {
+                    state.fallback_since = None;
+                    tracing::info!(target: "tracing_opentelemetry_setup", "Primary destination is available again, switching back from fallback destination");
+                }
+                false
+            },
+            Err(error) => match route {
+                Route::Probe => true,
+                _ => {
+                    state.failures = state.failures.saturating_add(1);
+                    if state.failures >= self.settings.max_failures {
+                        state.fallback_since = Some(Instant::now());
+                        tracing::warn!(target: "tracing_opentelemetry_setup", failures = state.failures, %error, "Primary destination keeps failing, switching to fallback destination");
+                        true
+                    } else {
+                        false
+                    }
+                }
+            },
+        }
+    }
+}
+
+///Combines results of both destinations, reporting first error
+#[inline(always)]
+fn both(primary: OTelSdkResult, fallback: OTelSdkResult) -> OTelSdkResult {
+    primary.and(fallback)
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter for FailoverExporter<T> {
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        let route = self.route();
+        if route == Route::Fallback {
+            return self.fallback.export(batch).await;
+        }
+
+        //Batch is only kept when its export failure would result in switching to fallback
+        let retained = match route == Route::Probe || self.is_last_chance() {
+            true => Some(batch.clone()),
+            false => None,
+        };
+        let result = self.primary.export(batch).await;
+        match (self.on_primary(route, &result), retained) {
+            (true, Some(batch)) => self.fallback.export(batch).await,
+            _ => result,
+        }
+    }
+
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        both(self.primary.shutdown_with_timeout(timeout), self.fallback.shutdown_with_timeout(timeout))
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        both(self.primary.force_flush(), self.fallback.force_flush())
+    }
+
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.primary.set_resource(resource);
+        self.fallback.set_resource(resource);
+    }
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogExporter for FailoverExporter<T> {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        use opentelemetry_sdk::logs::LogBatch;
+
+        let route = self.route();
+        if route == Route::Fallback {
+            return self.fallback.export(batch).await;
+        }
+
+        //Batch is only kept when its export failure would result in switching to fallback
+        if route != Route::Probe && !self.is_last_chance() {
+            let result = self.primary.export(batch).await;
+            self.on_primary(route, &result);
+            return result;
+        }
+
+        //LogBatch cannot be cloned so it has to be re-created for fallback
+        let records = batch.iter().collect::<Vec<_>>();
+        let result = self.primary.export(LogBatch::new(&records)).await;
+        match self.on_primary(route, &result) {
+            true => self.fallback.export(LogBatch::new(&records)).await,
+            false => result,
+        }
+    }
+
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        both(self.primary.shutdown_with_timeout(timeout), self.fallback.shutdown_with_timeout(timeout))
+    }
+
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.primary.set_resource(resource);
+        self.fallback.set_resource(resource);
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter> opentelemetry_sdk::metrics::exporter::PushMetricExporter for FailoverExporter<T> {
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        let route = self.route();
+        if route == Route::Fallback {
+            return self.fallback.export(metrics).await;
+        }
+
+        let result = self.primary.export(metrics).await;
+        match self.on_primary(route, &result) {
+            true => self.fallback.export(metrics).await,
+            false => result,
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        both(self.primary.force_flush(), self.fallback.force_flush())
+    }
+
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        both(self.primary.shutdown_with_timeout(timeout), self.fallback.shutdown_with_timeout(timeout))
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        //Reader aggregates according to single temporality, so fallback receives data with temporality of primary.
+        //Every exporter handles data of any temporality, while DogStatsD converts cumulative data into deltas on its own
+        self.primary.temporality()
+    }
+}
@@ -0,0 +1,187 @@
+//! OTLP file exporters
+//!
+//!Every export is appended to file as single line, containing JSON encoded `Export*ServiceRequest` according to OTLP/JSON mapping
+//!(i.e. `{"resourceSpans":[...]}`, `{"resourceLogs":[...]}` or `{"resourceMetrics":[...]}`), as specified by OpenTelemetry file exporter.
+//!Such files can be later replayed to collector (e.g. via `otlpjsonfile` receiver).
+
+use std::{fs, io};
+use std::io::Write as _;
+use std::path::PathBuf;
+use core::fmt;
+use core::sync::atomic::{self, Ordering};
+use core::time;
+
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema;
+
+struct Output {
+    path: PathBuf,
+    is_shutdown: atomic::AtomicBool,
+}
+
+impl Output {
+    #[inline]
+    fn new(url: &str) -> Self {
+        Self {
+            path: url.strip_prefix("file://").unwrap_or(url).into(),
+            is_shutdown: atomic::AtomicBool::new(false),
+        }
+    }
+
+    fn write<T: serde::Serialize>(&self, request: &T) -> OTelSdkResult {
+        if self.is_shutdown.load(Ordering::Acquire) {
+            return Err(OTelSdkError::AlreadyShutdown);
+        }
+
+        let mut line = serde_json::to_vec(request).map_err(|error| OTelSdkError::InternalFailure(error.to_string()))?;
+        line.push(b'\n');
+        //Single write of whole line keeps lines intact when the same file is shared by multiple exporters
+        let result = fs::OpenOptions::new().append(true).create(true).open(&self.path).and_then(|mut out| out.write_all(&line));
+        result.map_err(|error: io::Error| OTelSdkError::InternalFailure(format!("Failed to write '{}': {error}", self.path.display())))
+    }
+
+    #[inline]
+    fn shutdown(&self) -> OTelSdkResult {
+        self.is_shutdown.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Output {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("OtlpFile")
+           .field("path", &self.path)
+           .field("is_shutdown", &self.is_shutdown.load(Ordering::Acquire))
+           .finish()
+    }
+}
+
+#[derive(Debug)]
+///Span exporter writing `ExportTraceServiceRequest` per batch
+pub struct SpanExporter {
+    output: Output,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl SpanExporter {
+    #[inline]
+    pub fn new(url: &str) -> Self {
+        Self {
+            output: Output::new(url),
+            resource: ResourceAttributesWithSchema::default(),
+        }
+    }
+}
+
+impl opentelemetry_sdk::trace::SpanExporter for SpanExporter {
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        self.output.write(&ExportTraceServiceRequest {
+            resource_spans: opentelemetry_proto::transform::trace::tonic::group_spans_by_resource_and_scope(batch, &self.resource),
+        })
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, _: time::Duration) -> OTelSdkResult {
+        self.output.shutdown()
+    }
+
+    #[inline]
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        self.resource = res.into();
+    }
+}
+
+#[derive(Debug)]
+///Log exporter writing `ExportLogsServiceRequest` per batch
+pub struct LogExporter {
+    output: Output,
+    resource: ResourceAttributesWithSchema,
+}
+
+impl LogExporter {
+    #[inline]
+    pub fn new(url: &str) -> Self {
+        Self {
+            output: Output::new(url),
+            resource: ResourceAttributesWithSchema::default(),
+        }
+    }
+}
+
+impl opentelemetry_sdk::logs::LogExporter for LogExporter {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+
+        if batch.iter().next().is_none() {
+            return Ok(());
+        }
+
+        self.output.write(&ExportLogsServiceRequest {
+            resource_logs: opentelemetry_proto::transform::logs::tonic::group_logs_by_resource_and_scope(batch, &self.resource),
+        })
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.output.shutdown()
+    }
+
+    #[inline]
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        self.resource = res.into();
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Metric exporter writing `ExportMetricsServiceRequest` per collection
+#[derive(Debug)]
+pub struct MetricExporter {
+    output: Output,
+    temporality: opentelemetry_sdk::metrics::Temporality,
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl MetricExporter {
+    #[inline]
+    pub fn new(url: &str, temporality: opentelemetry_sdk::metrics::Temporality) -> Self {
+        Self {
+            output: Output::new(url),
+            temporality,
+        }
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl opentelemetry_sdk::metrics::exporter::PushMetricExporter for MetricExporter {
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+
+        if metrics.scope_metrics().all(|scope_metrics| scope_metrics.metrics().next().is_none()) {
+            return Ok(());
+        }
+
+        self.output.write(&ExportMetricsServiceRequest::from(metrics))
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.output.shutdown()
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.temporality
+    }
+}
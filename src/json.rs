@@ -0,0 +1,134 @@
+//! JSON serialization helpers shared by log exporters
+
+use std::io;
+use core::cmp;
+
+use serde::ser::{SerializeSeq, SerializeMap};
+
+pub struct AnyValueSerde<'a>(pub &'a opentelemetry::logs::AnyValue);
+
+impl serde::Serialize for AnyValueSerde<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use opentelemetry::logs::AnyValue;
+
+        #[cold]
+        #[inline(never)]
+        fn unexpected_value<E: serde::ser::Error>(unexpected: &AnyValue) -> E {
+            E::custom(format_args!("Unsupported value: {:?}", unexpected))
+        }
+
+        match self.0 {
+            AnyValue::Boolean(value) => serializer.serialize_bool(*value),
+            AnyValue::Int(value) => serializer.serialize_i64(*value),
+            AnyValue::Double(value) => serializer.serialize_f64(*value),
+            AnyValue::String(value) => serializer.serialize_str(value.as_str()),
+            AnyValue::Bytes(value) => serializer.serialize_bytes(value),
+            AnyValue::ListAny(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values.iter() {
+                    seq.serialize_element(&AnyValueSerde(value))?
+                }
+                seq.end()
+            },
+            AnyValue::Map(values) => {
+                let mut map = serializer.serialize_map(Some(values.len()))?;
+                for (key, value) in values.iter() {
+                    map.serialize_entry(key.as_str(), &AnyValueSerde(value))?
+                }
+                map.end()
+            },
+            //They use non exhaust for no reason so have to add this branch...
+            value => Err(unexpected_value(value)),
+        }
+    }
+}
+
+pub struct Buffer {
+    inner: [u8; 1024],
+    len: usize,
+}
+
+impl Buffer {
+    pub const fn new() -> Self {
+        Self {
+            inner: [0; 1024],
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn as_str_with(&mut self, cb: impl FnOnce(&mut Self) -> bool) -> Option<&'_ str> {
+        if (cb)(self) {
+            self.as_str()
+        } else {
+            self.clear();
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[inline(always)]
+    pub fn as_str(&self) -> Option<&'_ str> {
+        core::str::from_utf8(&self.inner[..self.len]).ok()
+    }
+
+    pub fn push_bytes(&mut self, buf: &[u8]) -> usize {
+        let output = &mut self.inner[self.len..];
+        let written = cmp::min(output.len(), buf.len());
+        output[..written].copy_from_slice(buf);
+        self.len = self.len.saturating_add(written);
+        written
+    }
+}
+
+impl io::Write for Buffer {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.push_bytes(buf))
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "stdout")]
+pub struct ValueSerde<'a>(pub &'a opentelemetry::Value);
+
+#[cfg(feature = "stdout")]
+impl serde::Serialize for ValueSerde<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use opentelemetry::{Array, Value};
+
+        fn array<S: serde::Serializer, T: serde::Serialize>(serializer: S, values: &[T]) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(values.len()))?;
+            for value in values.iter() {
+                seq.serialize_element(value)?
+            }
+            seq.end()
+        }
+
+        match self.0 {
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::I64(value) => serializer.serialize_i64(*value),
+            Value::F64(value) => serializer.serialize_f64(*value),
+            Value::String(value) => serializer.serialize_str(value.as_str()),
+            Value::Array(Array::Bool(values)) => array(serializer, values),
+            Value::Array(Array::I64(values)) => array(serializer, values),
+            Value::Array(Array::F64(values)) => array(serializer, values),
+            Value::Array(Array::String(values)) => array(serializer, &values.iter().map(|value| value.as_str()).collect::<Vec<_>>()),
+            value => serializer.collect_str(value),
+        }
+    }
+}
+
+///Formats `timestamp` as RFC3339 within `buffer`
+pub fn rfc3339(buffer: &mut Buffer, timestamp: std::time::SystemTime) -> Option<&'_ str> {
+    let timestamp: time::UtcDateTime = timestamp.into();
+    buffer.as_str_with(|buffer| timestamp.format_into(buffer, &time::format_description::well_known::Rfc3339).is_ok())
+}
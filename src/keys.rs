@@ -0,0 +1,188 @@
+//! Attribute keys
+//!
+//!Static keys are free to clone, while dynamic keys (e.g. composed from header names) can be interned via [intern]
+//!to share single allocation between all spans instead of allocating key for every span.
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::keys;
+//!
+//!let header = "x-request-id";
+//!let key = keys::intern(&format!("http.request.header.{header}"));
+//!assert_eq!(key, keys::intern("http.request.header.x-request-id"));
+//!assert_eq!(keys::ERROR_TYPE.as_str(), "error.type");
+//!```
+
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock, RwLock};
+
+pub use opentelemetry::Key;
+
+///`service.name` resource attribute
+pub const SERVICE_NAME: Key = Key::from_static_str("service.name");
+///`service.instance.id` resource attribute, populated by [AttributesBuilder::with_defaults](crate::builder::AttributesBuilder::with_defaults)
+pub const SERVICE_INSTANCE_ID: Key = Key::from_static_str("service.instance.id");
+///`service.version` resource attribute, populated by [AttributesBuilder::with_build_info](crate::builder::AttributesBuilder::with_build_info)
+pub const SERVICE_VERSION: Key = Key::from_static_str("service.version");
+///`vcs.revision` resource attribute, populated by [AttributesBuilder::with_build_info](crate::builder::AttributesBuilder::with_build_info)
+pub const VCS_REVISION: Key = Key::from_static_str("vcs.revision");
+///`build.timestamp` resource attribute, populated by [AttributesBuilder::with_build_info](crate::builder::AttributesBuilder::with_build_info)
+pub const BUILD_TIMESTAMP: Key = Key::from_static_str("build.timestamp");
+///`telemetry.sdk.version` resource attribute, populated by [AttributesBuilder::with_defaults](crate::builder::AttributesBuilder::with_defaults)
+pub const TELEMETRY_SDK_VERSION: Key = Key::from_static_str("telemetry.sdk.version");
+///`error.type` attribute, populated by [Context::set_error](crate::propagation::Context::set_error)
+pub const ERROR_TYPE: Key = Key::from_static_str("error.type");
+///`error.message` attribute, populated by [Context::set_error](crate::propagation::Context::set_error)
+pub const ERROR_MESSAGE: Key = Key::from_static_str("error.message");
+///`exception.type` attribute of `exception` event, populated by [Context::record_exception](crate::propagation::Context::record_exception)
+pub const EXCEPTION_TYPE: Key = Key::from_static_str("exception.type");
+///`exception.message` attribute of `exception` event, populated by [Context::record_exception](crate::propagation::Context::record_exception)
+pub const EXCEPTION_MESSAGE: Key = Key::from_static_str("exception.message");
+///`exception.stacktrace` attribute of `exception` event, populated by [Context::record_exception](crate::propagation::Context::record_exception)
+pub const EXCEPTION_STACKTRACE: Key = Key::from_static_str("exception.stacktrace");
+///`http.request.method` attribute, populated by [Context::set_http_attributes](crate::propagation::Context::set_http_attributes)
+pub const HTTP_REQUEST_METHOD: Key = Key::from_static_str("http.request.method");
+///`http.route` attribute, populated by [Context::set_http_attributes](crate::propagation::Context::set_http_attributes)
+pub const HTTP_ROUTE: Key = Key::from_static_str("http.route");
+///`http.response.status_code` attribute, populated by [Context::set_http_attributes](crate::propagation::Context::set_http_attributes)
+pub const HTTP_RESPONSE_STATUS_CODE: Key = Key::from_static_str("http.response.status_code");
+///`rpc.grpc.status_code` attribute, populated by [ServerService](crate::propagation::tonic::ServerService)
+pub const RPC_GRPC_STATUS_CODE: Key = Key::from_static_str("rpc.grpc.status_code");
+///`db.system.name` attribute, populated by [Context::set_db_attributes](crate::propagation::Context::set_db_attributes)
+pub const DB_SYSTEM_NAME: Key = Key::from_static_str("db.system.name");
+///`db.query.text` attribute, populated by [Context::set_db_attributes](crate::propagation::Context::set_db_attributes)
+pub const DB_QUERY_TEXT: Key = Key::from_static_str("db.query.text");
+///`process.pid` resource attribute, populated by [AttributesBuilder::with_process_detection](crate::builder::AttributesBuilder::with_process_detection)
+pub const PROCESS_PID: Key = Key::from_static_str("process.pid");
+///`process.executable.name` resource attribute, populated by [AttributesBuilder::with_process_detection](crate::builder::AttributesBuilder::with_process_detection)
+pub const PROCESS_EXECUTABLE_NAME: Key = Key::from_static_str("process.executable.name");
+///`process.command_args` resource attribute, populated by [AttributesBuilder::with_process_detection](crate::builder::AttributesBuilder::with_process_detection)
+pub const PROCESS_COMMAND_ARGS: Key = Key::from_static_str("process.command_args");
+///`host.name` resource attribute, populated by [AttributesBuilder::with_host_detection](crate::builder::AttributesBuilder::with_host_detection)
+pub const HOST_NAME: Key = Key::from_static_str("host.name");
+///`host.arch` resource attribute, populated by [AttributesBuilder::with_host_detection](crate::builder::AttributesBuilder::with_host_detection)
+pub const HOST_ARCH: Key = Key::from_static_str("host.arch");
+///`os.type` resource attribute, populated by [AttributesBuilder::with_host_detection](crate::builder::AttributesBuilder::with_host_detection)
+pub const OS_TYPE: Key = Key::from_static_str("os.type");
+///`container.id` resource attribute, populated by [AttributesBuilder::with_container_detection](crate::builder::AttributesBuilder::with_container_detection)
+pub const CONTAINER_ID: Key = Key::from_static_str("container.id");
+///`k8s.pod.name` resource attribute, populated by [AttributesBuilder::with_k8s_downward_api](crate::builder::AttributesBuilder::with_k8s_downward_api)
+pub const K8S_POD_NAME: Key = Key::from_static_str("k8s.pod.name");
+///`k8s.pod.uid` resource attribute, populated by [AttributesBuilder::with_k8s_downward_api](crate::builder::AttributesBuilder::with_k8s_downward_api)
+pub const K8S_POD_UID: Key = Key::from_static_str("k8s.pod.uid");
+///`k8s.namespace.name` resource attribute, populated by [AttributesBuilder::with_k8s_downward_api](crate::builder::AttributesBuilder::with_k8s_downward_api)
+pub const K8S_NAMESPACE_NAME: Key = Key::from_static_str("k8s.namespace.name");
+///`k8s.node.name` resource attribute, populated by [AttributesBuilder::with_k8s_downward_api](crate::builder::AttributesBuilder::with_k8s_downward_api)
+pub const K8S_NODE_NAME: Key = Key::from_static_str("k8s.node.name");
+///`k8s.cluster.name` resource attribute, populated by [AttributesBuilder::with_gcp_detection](crate::builder::AttributesBuilder::with_gcp_detection)
+pub const K8S_CLUSTER_NAME: Key = Key::from_static_str("k8s.cluster.name");
+///`host.image.id` resource attribute, populated by cloud detectors
+pub const HOST_IMAGE_ID: Key = Key::from_static_str("host.image.id");
+///`container.name` resource attribute, populated by cloud detectors
+pub const CONTAINER_NAME: Key = Key::from_static_str("container.name");
+///`aws.ecs.task.arn` resource attribute, populated by cloud detectors
+pub const AWS_ECS_TASK_ARN: Key = Key::from_static_str("aws.ecs.task.arn");
+///`aws.ecs.task.family` resource attribute, populated by cloud detectors
+pub const AWS_ECS_TASK_FAMILY: Key = Key::from_static_str("aws.ecs.task.family");
+///`aws.ecs.task.revision` resource attribute, populated by cloud detectors
+pub const AWS_ECS_TASK_REVISION: Key = Key::from_static_str("aws.ecs.task.revision");
+///`aws.ecs.cluster.arn` resource attribute, populated by cloud detectors
+pub const AWS_ECS_CLUSTER_ARN: Key = Key::from_static_str("aws.ecs.cluster.arn");
+///`aws.ecs.container.arn` resource attribute, populated by cloud detectors
+pub const AWS_ECS_CONTAINER_ARN: Key = Key::from_static_str("aws.ecs.container.arn");
+///`aws.ecs.launchtype` resource attribute, populated by cloud detectors
+pub const AWS_ECS_LAUNCHTYPE: Key = Key::from_static_str("aws.ecs.launchtype");
+///`host.id` resource attribute, populated by cloud detectors
+pub const HOST_ID: Key = Key::from_static_str("host.id");
+///`host.type` resource attribute, populated by cloud detectors
+pub const HOST_TYPE: Key = Key::from_static_str("host.type");
+///`cloud.provider` resource attribute, populated by cloud detectors
+pub const CLOUD_PROVIDER: Key = Key::from_static_str("cloud.provider");
+///`cloud.platform` resource attribute, populated by cloud detectors
+pub const CLOUD_PLATFORM: Key = Key::from_static_str("cloud.platform");
+///`cloud.region` resource attribute, populated by cloud detectors
+pub const CLOUD_REGION: Key = Key::from_static_str("cloud.region");
+///`cloud.availability_zone` resource attribute, populated by cloud detectors
+pub const CLOUD_AVAILABILITY_ZONE: Key = Key::from_static_str("cloud.availability_zone");
+///`cloud.account.id` resource attribute, populated by cloud detectors
+pub const CLOUD_ACCOUNT_ID: Key = Key::from_static_str("cloud.account.id");
+///`faas.name` resource attribute, populated by cloud detectors
+pub const FAAS_NAME: Key = Key::from_static_str("faas.name");
+///`faas.version` resource attribute, populated by cloud detectors
+pub const FAAS_VERSION: Key = Key::from_static_str("faas.version");
+///`faas.instance` resource attribute, populated by cloud detectors
+pub const FAAS_INSTANCE: Key = Key::from_static_str("faas.instance");
+///`faas.max_memory` resource attribute, populated by cloud detectors
+pub const FAAS_MAX_MEMORY: Key = Key::from_static_str("faas.max_memory");
+///`operation.duration` attribute, populated by [ScopedOperation](crate::propagation::ScopedOperation)
+pub const OPERATION_DURATION: Key = Key::from_static_str("operation.duration");
+///`otel.dropped_descendant_spans` attribute, populated when span depth is limited
+pub const DROPPED_DESCENDANT_SPANS: Key = Key::from_static_str("otel.dropped_descendant_spans");
+
+///Maximum number of keys kept by [intern]
+///
+///Once limit is reached, new names are no longer interned and every call allocates new key instead,
+///which protects from unbounded memory growth when names are derived from untrusted input (e.g. arbitrary headers)
+pub const MAX_INTERNED: usize = 4096;
+
+type Interned = RwLock<HashSet<Arc<str>>>;
+
+#[inline]
+fn interned() -> &'static Interned {
+    static KEYS: OnceLock<Interned> = OnceLock::new();
+    KEYS.get_or_init(Default::default)
+}
+
+///Returns key with `name`, sharing its allocation with all previously interned keys of the same name
+///
+///Interned keys are never released, so it should be used only for bounded set of names.
+///Set of interned keys is limited by [MAX_INTERNED], after which `name` is returned as newly allocated key
+pub fn intern(name: &str) -> Key {
+    let keys = interned();
+    if let Some(key) = keys.read().unwrap_or_else(|error| error.into_inner()).get(name) {
+        return Key::from(key.clone());
+    }
+
+    let mut keys = keys.write().unwrap_or_else(|error| error.into_inner());
+    match keys.get(name) {
+        Some(key) => Key::from(key.clone()),
+        None if keys.len() >= MAX_INTERNED => Key::from(name.to_owned()),
+        None => {
+            let key: Arc<str> = Arc::from(name);
+            keys.insert(key.clone());
+            Key::from(key)
+        }
+    }
+}
+
+///Returns number of interned keys
+pub fn interned_len() -> usize {
+    interned().read().unwrap_or_else(|error| error.into_inner()).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{intern, interned_len, MAX_INTERNED};
+
+    #[test]
+    fn should_share_interned_keys_up_to_limit() {
+        let interned = intern("test.interned");
+        let same = intern(&format!("test.{}", "interned"));
+        assert_eq!(interned, same);
+        assert_eq!(interned.as_str().as_ptr(), same.as_str().as_ptr());
+
+        for idx in interned_len()..MAX_INTERNED {
+            intern(&format!("test.filler.{idx}"));
+        }
+        assert_eq!(interned_len(), MAX_INTERNED);
+
+        //Keys are still created, but no longer shared once limit is reached
+        let first = intern("test.over_limit");
+        let second = intern("test.over_limit");
+        assert_eq!(first, second);
+        assert_ne!(first.as_str().as_ptr(), second.as_str().as_ptr());
+        assert_eq!(interned_len(), MAX_INTERNED);
+
+        //Keys interned before reaching limit are still shared
+        assert_eq!(intern("test.interned").as_str().as_ptr(), interned.as_str().as_ptr());
+    }
+}
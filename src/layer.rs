@@ -6,10 +6,96 @@ pub struct OtlpLayer<S> {
     ///tracing layer
     pub trace: Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::SdkTracer>>,
     ///logging layer
-    pub logs: Option<opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge<opentelemetry_sdk::logs::SdkLoggerProvider, opentelemetry_sdk::logs::SdkLogger>>,
+    pub logs: Option<opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge<BridgeLoggerProvider, BridgeLogger>>,
     #[cfg(feature = "tracing-metrics")]
     ///metrics layer
     pub metrics: Option<tracing_opentelemetry::MetricsLayer<S, opentelemetry_sdk::metrics::SdkMeterProvider>>,
+    pub(crate) native: native::NativeAttributesLayer,
+}
+
+type SetLevelFn = Box<dyn Fn(tracing_subscriber::filter::LevelFilter) -> Result<(), tracing_subscriber::reload::Error> + Send + Sync>;
+
+///Handle to change level of global subscriber at runtime
+///
+///Returned by [init_tracing_subscriber_with_reload](crate::Otlp::init_tracing_subscriber_with_reload)
+pub struct ReloadHandle {
+    set_level: SetLevelFn,
+}
+
+impl ReloadHandle {
+    pub(crate) fn new<S: tracing::Subscriber>(handle: tracing_subscriber::reload::Handle<tracing_subscriber::filter::LevelFilter, S>) -> Self {
+        Self {
+            set_level: Box::new(move |level| handle.reload(level)),
+        }
+    }
+
+    #[inline]
+    ///Sets maximum `level` of telemetry to be recorded
+    ///
+    ///Returns error only if subscriber no longer exists
+    pub fn set_level(&self, level: impl Into<tracing_subscriber::filter::LevelFilter>) -> Result<(), tracing_subscriber::reload::Error> {
+        (self.set_level)(level.into())
+    }
+}
+
+impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>> OtlpLayer<S> {
+    #[cfg(not(feature = "tracing-metrics"))]
+    ///Applies independent filter to each signal, returning composed layer
+    ///
+    ///Unlike filtering of the whole `OtlpLayer`, it allows to have different verbosity per signal (e.g. traces at DEBUG, while logs at INFO)
+    ///
+    ///Requires `S` to be `Send + Sync` as layers are type erased
+    pub fn with_filters<T, L>(self, trace: T, logs: L) -> impl tracing_subscriber::Layer<S>
+    where
+        S: Send + Sync,
+        T: tracing_subscriber::layer::Filter<S> + Send + Sync + 'static,
+        L: tracing_subscriber::layer::Filter<S> + Send + Sync + 'static,
+    {
+        use tracing_subscriber::Layer;
+
+        let mut layers = Vec::<Box<dyn Layer<S> + Send + Sync>>::with_capacity(3);
+        #[cfg(debug_assertions)]
+        layers.push(Box::new(leak::LeakLayer));
+        if let Some(layer) = self.trace {
+            layers.push(Box::new(layer.with_filter(trace)));
+        }
+        if let Some(layer) = self.logs {
+            layers.push(Box::new(layer.with_filter(logs)));
+        }
+        //Vec doesn't propagate dispatch to its layers, hence native attributes layer must be outside of it
+        self.native.and_then(layers)
+    }
+
+    #[cfg(feature = "tracing-metrics")]
+    ///Applies independent filter to each signal, returning composed layer
+    ///
+    ///Unlike filtering of the whole `OtlpLayer`, it allows to have different verbosity per signal (e.g. traces at DEBUG, while logs at INFO)
+    ///
+    ///Requires `S` to be `Send + Sync` as layers are type erased
+    pub fn with_filters<T, L, M>(self, trace: T, logs: L, metrics: M) -> impl tracing_subscriber::Layer<S>
+    where
+        S: Send + Sync,
+        T: tracing_subscriber::layer::Filter<S> + Send + Sync + 'static,
+        L: tracing_subscriber::layer::Filter<S> + Send + Sync + 'static,
+        M: tracing_subscriber::layer::Filter<S> + Send + Sync + 'static,
+    {
+        use tracing_subscriber::Layer;
+
+        let mut layers = Vec::<Box<dyn Layer<S> + Send + Sync>>::with_capacity(4);
+        #[cfg(debug_assertions)]
+        layers.push(Box::new(leak::LeakLayer));
+        if let Some(layer) = self.trace {
+            layers.push(Box::new(layer.with_filter(trace)));
+        }
+        if let Some(layer) = self.logs {
+            layers.push(Box::new(layer.with_filter(logs)));
+        }
+        if let Some(layer) = self.metrics {
+            layers.push(Box::new(layer.with_filter(metrics)));
+        }
+        //Vec doesn't propagate dispatch to its layers, hence native attributes layer must be outside of it
+        self.native.and_then(layers)
+    }
 }
 
 macro_rules! impl_method {
@@ -27,6 +113,494 @@ macro_rules! impl_method {
     };
 }
 
+///Bridge of `tracing` events into logs
+///
+///SDK provides no way to remove attributes from record, hence excess attributes are dropped as event fields are recorded
+mod bridge {
+    use std::borrow::Cow;
+    use std::time::SystemTime;
+
+    use opentelemetry::{Key, InstrumentationScope};
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider, Severity};
+    use opentelemetry::trace::{SpanId, TraceFlags, TraceId};
+    use opentelemetry_sdk::logs::{SdkLogRecord, SdkLogger, SdkLoggerProvider};
+
+    #[derive(Clone, Debug)]
+    ///Logger provider of [OtlpLayer::logs](super::OtlpLayer::logs), limiting number of attributes recorded on each record
+    pub struct BridgeLoggerProvider {
+        inner: SdkLoggerProvider,
+        max_attributes: Option<usize>,
+    }
+
+    impl BridgeLoggerProvider {
+        #[inline(always)]
+        pub(crate) const fn new(inner: SdkLoggerProvider, max_attributes: Option<usize>) -> Self {
+            Self {
+                inner,
+                max_attributes,
+            }
+        }
+    }
+
+    impl LoggerProvider for BridgeLoggerProvider {
+        type Logger = BridgeLogger;
+
+        #[inline]
+        fn logger_with_scope(&self, scope: InstrumentationScope) -> Self::Logger {
+            BridgeLogger {
+                inner: self.inner.logger_with_scope(scope),
+                max_attributes: self.max_attributes,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    ///Logger created by [BridgeLoggerProvider]
+    pub struct BridgeLogger {
+        inner: SdkLogger,
+        max_attributes: Option<usize>,
+    }
+
+    impl Logger for BridgeLogger {
+        type LogRecord = BridgeLogRecord;
+
+        #[inline]
+        fn create_log_record(&self) -> Self::LogRecord {
+            BridgeLogRecord {
+                inner: self.inner.create_log_record(),
+                remaining_attributes: self.max_attributes.unwrap_or(usize::MAX),
+            }
+        }
+
+        #[inline]
+        fn emit(&self, record: Self::LogRecord) {
+            self.inner.emit(record.inner)
+        }
+    }
+
+    #[derive(Debug)]
+    ///Log record created by [BridgeLogger], ignoring attributes beyond the limit
+    pub struct BridgeLogRecord {
+        inner: SdkLogRecord,
+        remaining_attributes: usize,
+    }
+
+    impl LogRecord for BridgeLogRecord {
+        #[inline]
+        fn set_event_name(&mut self, name: &'static str) {
+            self.inner.set_event_name(name)
+        }
+
+        #[inline]
+        fn set_target<T: Into<Cow<'static, str>>>(&mut self, target: T) {
+            self.inner.set_target(target)
+        }
+
+        #[inline]
+        fn set_timestamp(&mut self, timestamp: SystemTime) {
+            self.inner.set_timestamp(timestamp)
+        }
+
+        #[inline]
+        fn set_observed_timestamp(&mut self, timestamp: SystemTime) {
+            self.inner.set_observed_timestamp(timestamp)
+        }
+
+        #[inline]
+        fn set_severity_text(&mut self, text: &'static str) {
+            self.inner.set_severity_text(text)
+        }
+
+        #[inline]
+        fn set_severity_number(&mut self, number: Severity) {
+            self.inner.set_severity_number(number)
+        }
+
+        #[inline]
+        fn set_body(&mut self, body: AnyValue) {
+            self.inner.set_body(body)
+        }
+
+        fn add_attributes<I, K, V>(&mut self, attributes: I) where I: IntoIterator<Item = (K, V)>, K: Into<Key>, V: Into<AnyValue> {
+            for (key, value) in attributes {
+                self.add_attribute(key, value);
+            }
+        }
+
+        fn add_attribute<K: Into<Key>, V: Into<AnyValue>>(&mut self, key: K, value: V) {
+            if let Some(remaining) = self.remaining_attributes.checked_sub(1) {
+                self.remaining_attributes = remaining;
+                self.inner.add_attribute(key, value);
+            }
+        }
+
+        #[inline]
+        fn set_trace_context(&mut self, trace_id: TraceId, span_id: SpanId, trace_flags: Option<TraceFlags>) {
+            self.inner.set_trace_context(trace_id, span_id, trace_flags)
+        }
+    }
+}
+
+pub use bridge::{BridgeLoggerProvider, BridgeLogger, BridgeLogRecord};
+
+#[cfg(debug_assertions)]
+///Detection of spans left entered, which is common source of misparented telemetry
+///
+///Detection is only performed in debug builds and reports via `WARN` event with target `tracing_opentelemetry_setup`:
+///
+///- Spans still entered when thread finishes;
+///- Spans exited on different thread than they were entered on (e.g. guard held across `.await` of a task that moved to another worker thread);
+///- Spans closed while still entered.
+///
+///Thread local destructor cannot rely on tracing subscriber, hence spans left entered on finished thread are reported on next span exit or close.
+mod leak {
+    use core::cell::RefCell;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    ///Spans left entered on finished threads, awaiting to be reported
+    static PENDING: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+    static HAS_PENDING: AtomicBool = AtomicBool::new(false);
+
+    fn report(spans: &[&'static str]) {
+        tracing::warn!(target: "tracing_opentelemetry_setup", spans = spans.join(","), "Spans left entered, telemetry recorded within them may be misparented");
+    }
+
+    fn report_pending() {
+        if !HAS_PENDING.swap(false, Ordering::AcqRel) {
+            return;
+        }
+
+        let pending = core::mem::take(&mut *PENDING.lock().unwrap_or_else(|error| error.into_inner()));
+        if !pending.is_empty() {
+            report(&pending);
+        }
+    }
+
+    ///Spans currently entered on the thread
+    struct EnteredSpans(Vec<(tracing::span::Id, &'static str)>);
+
+    impl Drop for EnteredSpans {
+        fn drop(&mut self) {
+            if self.0.is_empty() {
+                return;
+            }
+
+            PENDING.lock().unwrap_or_else(|error| error.into_inner()).extend(self.0.iter().map(|(_, name)| *name));
+            HAS_PENDING.store(true, Ordering::Release);
+        }
+    }
+
+    thread_local! {
+        static ENTERED: RefCell<EnteredSpans> = const { RefCell::new(EnteredSpans(Vec::new())) };
+    }
+
+    #[inline]
+    pub fn on_enter(id: &tracing::span::Id, name: &'static str) {
+        let _ = ENTERED.try_with(|entered| entered.borrow_mut().0.push((id.clone(), name)));
+    }
+
+    #[inline]
+    pub fn on_exit(id: &tracing::span::Id, name: &'static str) {
+        let is_entered = ENTERED.try_with(|entered| {
+            let mut entered = entered.borrow_mut();
+            match entered.0.iter().rposition(|(entered, _)| entered == id) {
+                Some(idx) => {
+                    entered.0.remove(idx);
+                    true
+                },
+                None => false,
+            }
+        });
+        if let Ok(false) = is_entered {
+            report(&[name]);
+        }
+        report_pending();
+    }
+
+    #[inline]
+    pub fn on_close(id: &tracing::span::Id) {
+        let leaked = ENTERED.try_with(|entered| {
+            let mut entered = entered.borrow_mut();
+            let mut leaked = Vec::new();
+            entered.0.retain(|(entered, name)| {
+                if entered == id {
+                    leaked.push(*name);
+                    false
+                } else {
+                    true
+                }
+            });
+            leaked
+        });
+        if let Ok(leaked) = leaked {
+            if !leaked.is_empty() {
+                report(&leaked);
+            }
+        }
+        report_pending();
+    }
+
+///Layer detecting spans left entered
+    pub struct LeakLayer;
+
+    impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>> tracing_subscriber::Layer<S> for LeakLayer {
+        #[inline(always)]
+        fn on_enter(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(span) = ctx.span(id) {
+                on_enter(id, span.name());
+            }
+        }
+
+        #[inline(always)]
+        fn on_exit(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(span) = ctx.span(id) {
+                on_exit(id, span.name());
+            }
+        }
+
+        #[inline(always)]
+        fn on_close(&self, id: tracing::span::Id, _: tracing_subscriber::layer::Context<'_, S>) {
+            on_close(&id);
+        }
+    }
+}
+
+///Preservation of integer fields that `tracing-opentelemetry` would stringify
+///
+///Span attributes of type `u64`, `i128` and `u128` are recorded by `tracing-opentelemetry` using `Debug` formatting.
+///Their original values are captured in span extensions and set on opentelemetry span as native attributes right before it is closed,
+///overriding stringified values (see `DedupAttributesProcessor`)
+pub(crate) mod native {
+    use crate::builder::IntegerOverflow;
+
+    #[derive(Copy, Clone, Debug)]
+    enum WideInt {
+        U64(u64),
+        I128(i128),
+        U128(u128),
+    }
+
+    impl WideInt {
+        fn into_value(self, overflow: IntegerOverflow) -> Option<opentelemetry::Value> {
+            let value = match self {
+                Self::U64(value) => i64::try_from(value).ok(),
+                Self::I128(value) => i64::try_from(value).ok(),
+                Self::U128(value) => i64::try_from(value).ok(),
+            };
+            match (value, overflow) {
+                (Some(value), _) => Some(opentelemetry::Value::I64(value)),
+                //tracing-opentelemetry already recorded it as decimal string
+                (None, IntegerOverflow::String) => None,
+                (None, IntegerOverflow::Double) => Some(opentelemetry::Value::F64(match self {
+                    Self::U64(value) => value as f64,
+                    Self::I128(value) => value as f64,
+                    Self::U128(value) => value as f64,
+                })),
+                (None, IntegerOverflow::Saturate) => Some(opentelemetry::Value::I64(match self {
+                    Self::I128(value) if value < 0 => i64::MIN,
+                    _ => i64::MAX,
+                })),
+            }
+        }
+    }
+
+    struct NativeAttributes(Vec<(&'static str, WideInt)>);
+
+    impl tracing::field::Visit for NativeAttributes {
+        #[inline]
+        fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+            self.push(field.name(), WideInt::U64(value));
+        }
+
+        #[inline]
+        fn record_i128(&mut self, field: &tracing::field::Field, value: i128) {
+            self.push(field.name(), WideInt::I128(value));
+        }
+
+        #[inline]
+        fn record_u128(&mut self, field: &tracing::field::Field, value: u128) {
+            self.push(field.name(), WideInt::U128(value));
+        }
+
+        #[inline(always)]
+        fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn core::fmt::Debug) {
+        }
+    }
+
+    impl NativeAttributes {
+        #[inline]
+        fn push(&mut self, name: &'static str, value: WideInt) {
+            match self.0.iter_mut().find(|(key, _)| *key == name) {
+                Some(existing) => existing.1 = value,
+                None => self.0.push((name, value)),
+            }
+        }
+    }
+
+    ///Layer capturing integer values, must be placed before trace layer
+    pub struct NativeAttributesLayer {
+        ///`None` when traces are disabled
+        overflow: Option<IntegerOverflow>,
+        dispatch: std::sync::OnceLock<tracing::dispatcher::WeakDispatch>,
+    }
+
+    impl NativeAttributesLayer {
+        #[inline(always)]
+        pub fn new(overflow: Option<IntegerOverflow>) -> Self {
+            Self {
+                overflow,
+                dispatch: std::sync::OnceLock::new(),
+            }
+        }
+
+        fn record<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>>(&self, id: &tracing::span::Id, ctx: &tracing_subscriber::layer::Context<'_, S>, cb: impl FnOnce(&mut NativeAttributes)) {
+            if self.overflow.is_none() {
+                return;
+            }
+
+            if let Some(span) = ctx.span(id) {
+                let mut extensions = span.extensions_mut();
+                match extensions.get_mut::<NativeAttributes>() {
+                    Some(attributes) => cb(attributes),
+                    None => {
+                        let mut attributes = NativeAttributes(Vec::new());
+                        cb(&mut attributes);
+                        if !attributes.0.is_empty() {
+                            extensions.insert(attributes);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>> tracing_subscriber::Layer<S> for NativeAttributesLayer {
+        #[inline]
+        fn on_register_dispatch(&self, subscriber: &tracing::Dispatch) {
+            let _ = self.dispatch.set(subscriber.downgrade());
+        }
+
+        #[inline]
+        fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            self.record(id, &ctx, |attributes| attrs.record(attributes));
+        }
+
+        #[inline]
+        fn on_record(&self, id: &tracing::span::Id, values: &tracing::span::Record<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            self.record(id, &ctx, |attributes| values.record(attributes));
+        }
+
+        fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            use opentelemetry::trace::TraceContextExt;
+
+            let (Some(overflow), Some(span)) = (self.overflow, ctx.span(&id)) else {
+                return;
+            };
+            let mut extensions = span.extensions_mut();
+            let Some(attributes) = extensions.remove::<NativeAttributes>() else {
+                return;
+            };
+            let Some(dispatch) = self.dispatch.get().and_then(|dispatch| dispatch.upgrade()) else {
+                return;
+            };
+            //Starts opentelemetry span if it is not started yet, trace layer ends it within own `on_close`
+            if let Some(cx) = tracing_opentelemetry::get_otel_context(&mut extensions, &dispatch) {
+                let otel_span = cx.span();
+                for (name, value) in attributes.0 {
+                    if let Some(value) = value.into_value(overflow) {
+                        otel_span.set_attribute(opentelemetry::KeyValue::new(name, value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "propagation")]
+///Marker of span, which requested verbose telemetry via baggage
+pub(crate) struct VerboseSpan;
+
+#[cfg(feature = "propagation")]
+///Marks `span` as verbose, if it is managed by `tracing_subscriber::Registry`
+pub(crate) fn mark_verbose(span: &tracing::Span) {
+    use tracing_subscriber::registry::LookupSpan;
+
+    span.with_subscriber(|(id, dispatch)| {
+        if let Some(span) = dispatch.downcast_ref::<tracing_subscriber::Registry>().and_then(|registry| registry.span(id)) {
+            span.extensions_mut().replace(VerboseSpan);
+        }
+    });
+}
+
+#[cfg(feature = "propagation")]
+#[derive(Copy, Clone, Debug)]
+///Level filter that raises verbosity within requests that asked for it via baggage
+///
+///Span is considered verbose when its parent is set via [Context::set_parent_from](crate::propagation::Context::set_parent_from)
+///and baggage contains [VERBOSE_BAGGAGE_KEY](crate::propagation::VERBOSE_BAGGAGE_KEY) set to `1` or `true`.
+///Within such span and its descendants `verbose` level is used instead of `base`.
+///
+///Requires subscriber to be built on top of `tracing_subscriber::Registry`
+///
+///```rust
+///use tracing_opentelemetry_setup::layer::BaggageVerbosity;
+///use tracing_opentelemetry_setup::tracing_subscriber::filter::LevelFilter;
+///use tracing_opentelemetry_setup::tracing_subscriber::Layer;
+///use tracing_opentelemetry_setup::tracing_subscriber::layer::SubscriberExt;
+///
+///let filter = BaggageVerbosity::new(LevelFilter::INFO, LevelFilter::TRACE);
+///let layer = tracing_opentelemetry_setup::tracing_subscriber::layer::Identity::new().with_filter(filter);
+///let _subscriber = tracing_opentelemetry_setup::tracing_subscriber::registry().with(layer);
+///```
+pub struct BaggageVerbosity {
+    base: tracing_subscriber::filter::LevelFilter,
+    verbose: tracing_subscriber::filter::LevelFilter,
+}
+
+#[cfg(feature = "propagation")]
+impl BaggageVerbosity {
+    #[inline(always)]
+    ///Creates new filter using `base` level by default and `verbose` level within requests that asked for it
+    pub const fn new(base: tracing_subscriber::filter::LevelFilter, verbose: tracing_subscriber::filter::LevelFilter) -> Self {
+        Self {
+            base,
+            verbose,
+        }
+    }
+}
+
+#[cfg(feature = "propagation")]
+impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>> tracing_subscriber::layer::Filter<S> for BaggageVerbosity {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>, ctx: &tracing_subscriber::layer::Context<'_, S>) -> bool {
+        if self.base >= *metadata.level() {
+            return true;
+        } else if self.verbose < *metadata.level() {
+            return false;
+        }
+
+        match ctx.lookup_current() {
+            Some(span) => span.scope().any(|span| span.extensions().get::<VerboseSpan>().is_some()),
+            None => false,
+        }
+    }
+
+    fn callsite_enabled(&self, metadata: &'static tracing::Metadata<'static>) -> tracing::subscriber::Interest {
+        if self.base >= *metadata.level() {
+            tracing::subscriber::Interest::always()
+        } else if self.verbose >= *metadata.level() {
+            tracing::subscriber::Interest::sometimes()
+        } else {
+            tracing::subscriber::Interest::never()
+        }
+    }
+
+    #[inline(always)]
+    fn max_level_hint(&self) -> Option<tracing_subscriber::filter::LevelFilter> {
+        Some(core::cmp::max(self.base, self.verbose))
+    }
+}
+
 #[inline(always)]
 fn apply_new_interest(interest: &mut tracing::subscriber::Interest, new_interest: tracing::subscriber::Interest) {
     if (interest.is_sometimes() && new_interest.is_always()) || (interest.is_never() && !new_interest.is_never()) {
@@ -35,6 +609,21 @@ fn apply_new_interest(interest: &mut tracing::subscriber::Interest, new_interest
 }
 
 impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>> tracing_subscriber::Layer<S> for OtlpLayer<S> {
+    #[inline(always)]
+    fn on_register_dispatch(&self, subscriber: &tracing::Dispatch) {
+        tracing_subscriber::Layer::<S>::on_register_dispatch(&self.native, subscriber);
+        if let Some(trace) = self.trace.as_ref() {
+            trace.on_register_dispatch(subscriber);
+        }
+        if let Some(logs) = self.logs.as_ref() {
+            tracing_subscriber::Layer::<S>::on_register_dispatch(logs, subscriber);
+        }
+        #[cfg(feature = "tracing-metrics")]
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.on_register_dispatch(subscriber);
+        }
+    }
+
     #[inline(always)]
     fn on_layer(&mut self, subscriber: &mut S) {
         impl_method!(self.as_mut().on_layer(subscriber));
@@ -42,6 +631,7 @@ impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'
 
     #[inline]
     fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        tracing_subscriber::Layer::<S>::on_new_span(&self.native, attrs, id, ctx.clone());
         impl_method!(self.as_ref().on_new_span(attrs, id, ctx.clone()));
     }
 
@@ -117,6 +707,7 @@ impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'
 
     #[inline]
     fn on_record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        tracing_subscriber::Layer::<S>::on_record(&self.native, span, values, ctx.clone());
         impl_method!(self.as_ref().on_record(span, values, ctx.clone()));
     }
 
@@ -132,16 +723,23 @@ impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'
 
     #[inline]
     fn on_enter(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        #[cfg(debug_assertions)]
+        tracing_subscriber::Layer::<S>::on_enter(&leak::LeakLayer, id, ctx.clone());
         impl_method!(self.as_ref().on_enter(id, ctx.clone()));
     }
 
     #[inline]
     fn on_exit(&self, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        #[cfg(debug_assertions)]
+        tracing_subscriber::Layer::<S>::on_exit(&leak::LeakLayer, id, ctx.clone());
         impl_method!(self.as_ref().on_exit(id, ctx.clone()));
     }
 
     #[inline]
     fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        #[cfg(debug_assertions)]
+        leak::on_close(&id);
+        tracing_subscriber::Layer::<S>::on_close(&self.native, id.clone(), ctx.clone());
         impl_method!(self.as_ref().on_close(id.clone(), ctx.clone()));
     }
 
@@ -149,4 +747,29 @@ impl<S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'
     fn on_id_change(&self, old: &tracing::span::Id, new: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
         impl_method!(self.as_ref(). on_id_change(old, new, ctx.clone()));
     }
+
+    #[inline]
+    unsafe fn downcast_raw(&self, id: core::any::TypeId) -> Option<*const ()> {
+        if id == core::any::TypeId::of::<Self>() {
+            return Some(self as *const Self as *const ());
+        }
+        //Inner layers must be reachable in order for OpenTelemetrySpanExt to work
+        if let Some(trace) = self.trace.as_ref() {
+            if let Some(ptr) = unsafe { trace.downcast_raw(id) } {
+                return Some(ptr);
+            }
+        }
+        if let Some(logs) = self.logs.as_ref() {
+            if let Some(ptr) = unsafe { tracing_subscriber::Layer::<S>::downcast_raw(logs, id) } {
+                return Some(ptr);
+            }
+        }
+        #[cfg(feature = "tracing-metrics")]
+        if let Some(metrics) = self.metrics.as_ref() {
+            if let Some(ptr) = unsafe { metrics.downcast_raw(id) } {
+                return Some(ptr);
+            }
+        }
+        None
+    }
 }
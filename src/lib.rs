@@ -8,13 +8,24 @@
 //!
 //! - `panic` - Provides panic hook implementation. Must be enabled via panic module
 //!- `propagation` - Enables propagation utilities
+//!- `tower` - Enables tower middleware extracting parent context from incoming HTTP requests. Implies `propagation`
+//!- `axum` - Enables axum integration, naming server spans after route template and providing context extractor. Implies `tower`
+//!- `reqwest-middleware` - Enables [reqwest-middleware](https://crates.io/crates/reqwest-middleware) middleware propagating context into outgoing requests. Implies `propagation`
+//!- `aws-detector` - Enables AWS resource detection via [AttributesBuilder::with_aws_detection](builder::AttributesBuilder::with_aws_detection)
+//!- `gcp-detector` - Enables GCP resource detection via [AttributesBuilder::with_gcp_detection](builder::AttributesBuilder::with_gcp_detection)
 //!- `metrics` - Enable integration with [metrics](https://crates.io/crates/metrics)
+//!- `prometheus` - Enables Prometheus pull exporter rendering metrics for `/metrics` endpoint. Implies `metrics`
 //!- `tracing-metrics` - Enable metrics usage via [tracing-opentelemetry](https://docs.rs/tracing-opentelemetry/latest/tracing_opentelemetry/struct.MetricsLayer.html)
-//!- `rt-tokio` - Tell OpenTelemetry sdk that you use tokio runtime
+//!- `rt-tokio` - Tell OpenTelemetry sdk that you use tokio runtime. Enables async shutdown and shutdown signal handler
+//!- `testing` - Enables in-process pipeline with in-memory exporters for testing and benchmarking
+//!- `fmt` - Enables human readable console output alongside OTLP via `Otlp::init_tracing_subscriber_with_fmt`
+//!- `jaeger-remote-sampler` - Enables Jaeger remote sampler. Implies `rt-tokio` and `http`
 //!
 //!### Non-standard exporters
 //!
-//!- `datadog` - Enables datadog agent exporter. Currently supports only traces & logs
+//!- `datadog` - Enables datadog agent exporter for traces & logs, and DogStatsD exporter for metrics
+//!- `stdout` - Enables human readable exporter of traces, logs and metrics for local development, or JSON logs for collectors tailing output, via `Protocol::Stdout`
+//!- `file` - Enables OTLP file exporter of traces, logs and metrics as JSON lines via `Protocol::File`
 //!
 //!### Grpc features
 //!
@@ -34,6 +45,17 @@
 //!- `http-reqwest` - Enables async reqwest client.
 //!- `http-hyper` - Enables hyper client.
 //!
+//!## Multiple instances
+//!
+//!Nothing in `Otlp` is global until you install it, so multiple instances can be used within the same process.
+//!This allows library embedded into host application to ship its telemetry to its own collector:
+//!
+//!- Host application installs its own global subscriber as usual;
+//!- Library creates own `Otlp` and uses `Otlp::create_dispatch` (or `Otlp::layers` to assemble subscriber manually) to get non-global subscriber;
+//!- Library executes its code within `tracing::dispatcher::with_default`, so that its telemetry never reaches host's subscriber.
+//!
+//!Note that [metrics](https://crates.io/crates/metrics) recorder is global, so use `Otlp::create_metrics_recorder` with `metrics::with_local_recorder` instead of `Otlp::init_metrics_recorder` in libraries.
+//!
 //!## Usage
 //!
 //!Make sure `tracing-opentelemetry-setup` is installed to your dependencies
@@ -63,18 +85,62 @@
 #![warn(missing_docs)]
 #![allow(clippy::style)]
 
+#[cfg(any(feature = "datadog", feature = "stdout"))]
+mod json;
 #[cfg(feature = "datadog")]
 mod datadog;
+#[cfg(all(feature = "datadog", any(feature = "metrics", feature = "tracing-metrics")))]
+mod dogstatsd;
+#[cfg(feature = "stdout")]
+mod stdout;
+#[cfg(feature = "file")]
+mod file;
+#[cfg(all(any(feature = "metrics", feature = "tracing-metrics"), any(feature = "grpc", feature = "http", feature = "datadog", feature = "prometheus", feature = "stdout", feature = "file", feature = "testing")))]
+mod process;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod retry;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod ready;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod pause;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod telemetry;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod processor;
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod failover;
+#[cfg(any(feature = "aws-detector", feature = "gcp-detector"))]
+mod detector;
 #[cfg(feature = "panic")]
 pub mod panic;
 #[cfg(feature = "propagation")]
 pub mod propagation;
+#[cfg(feature = "tower")]
+pub mod middleware;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
 #[cfg(feature = "metrics")]
 pub use metrics_opentelemetry::metrics;
 pub use tracing;
 pub use tracing_subscriber;
 pub use opentelemetry;
 pub use opentelemetry_sdk;
+pub use tracing_opentelemetry;
+#[cfg(feature = "http")]
+pub use opentelemetry_http;
+#[cfg(feature = "datadog")]
+pub use opentelemetry_datadog;
+#[cfg(feature = "tower")]
+pub use tower;
 pub mod layer;
 pub mod builder;
+pub mod exporter;
+pub mod keys;
+pub mod sampling;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "fmt")]
+pub mod console;
+pub mod version;
+pub mod prelude;
 pub use builder::Otlp;
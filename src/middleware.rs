@@ -0,0 +1,302 @@
+//! Tower middleware
+//!
+//!Provides [TraceLayer] that creates server span for every incoming `http::Request`, continuing trace of the caller,
+//!and [ClientTraceLayer] that creates client span for every outgoing `http::Request`, injecting its context into request headers
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::middleware::TraceLayer;
+//!use tracing_opentelemetry_setup::tower::Layer;
+//!
+//!#[derive(Clone)]
+//!struct Handler;
+//!
+//!impl tracing_opentelemetry_setup::tower::Service<http::Request<String>> for Handler {
+//!    type Response = http::Response<String>;
+//!    type Error = std::convert::Infallible;
+//!    type Future = core::future::Ready<Result<Self::Response, Self::Error>>;
+//!
+//!    fn poll_ready(&mut self, _: &mut core::task::Context<'_>) -> core::task::Poll<Result<(), Self::Error>> {
+//!        core::task::Poll::Ready(Ok(()))
+//!    }
+//!
+//!    fn call(&mut self, _: http::Request<String>) -> Self::Future {
+//!        core::future::ready(Ok(http::Response::new("OK".to_owned())))
+//!    }
+//!}
+//!
+//!let service = TraceLayer::new().layer(Handler);
+//!```
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+
+use crate::propagation::Context;
+
+///Layer wrapping service with [TraceService]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TraceLayer {
+}
+
+impl TraceLayer {
+    #[inline(always)]
+    ///Creates new layer
+    pub const fn new() -> Self {
+        Self {
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for TraceLayer {
+    type Service = TraceService<S>;
+
+    #[inline(always)]
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceService {
+            inner
+        }
+    }
+}
+
+///Service wrapping every request into server span
+///
+///Span parent is extracted from request headers using global propagator (see [Context::set_parent_from]).
+///Span is annotated with `http.request.method`, `url.path` and, once response is ready, `http.response.status_code` attributes.
+///
+///Span status is set to error when inner service fails or responds with `5xx` status code.
+///Note that `http.route` is not known to generic middleware, so it can be set within handler via [Context::record_lazy].
+///With `axum` feature, route template is taken from `MatchedPath`, naming span after it (e.g. `GET /users/{id}`), see [axum] for details
+#[derive(Clone, Debug)]
+pub struct TraceService<S> {
+    inner: S,
+}
+
+impl<S, B, R> tower::Service<http::Request<B>> for TraceService<S> where S: tower::Service<http::Request<B>, Response = http::Response<R>>, S::Error: fmt::Display {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TraceFuture<S::Future>;
+
+    #[inline(always)]
+    fn poll_ready(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let method = req.method().as_str();
+        #[cfg(feature = "axum")]
+        let route = req.extensions().get::<::axum::extract::MatchedPath>().map(|path| path.as_str());
+        #[cfg(not(feature = "axum"))]
+        let route: Option<&str> = None;
+        let name = match route {
+            Some(route) => std::borrow::Cow::Owned(format!("{method} {route}")),
+            None => std::borrow::Cow::Borrowed(method),
+        };
+        let span = tracing::info_span!(
+            "HTTP request",
+            otel.name = name.as_ref(),
+            otel.kind = "server",
+            http.request.method = method,
+            http.route = route,
+            url.path = req.uri().path(),
+        );
+        Context::new(span.clone()).set_parent_from(req.headers());
+        #[cfg(feature = "axum")]
+        let req = {
+            let mut req = req;
+            req.extensions_mut().insert(axum::RequestSpan(span.clone()));
+            req
+        };
+
+        let inner = span.in_scope(|| self.inner.call(req));
+        TraceFuture {
+            inner,
+            span,
+            is_client: false,
+        }
+    }
+}
+
+///Layer wrapping client service with [ClientTraceService]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ClientTraceLayer {
+}
+
+impl ClientTraceLayer {
+    #[inline(always)]
+    ///Creates new layer
+    pub const fn new() -> Self {
+        Self {
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for ClientTraceLayer {
+    type Service = ClientTraceService<S>;
+
+    #[inline(always)]
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientTraceService {
+            inner
+        }
+    }
+}
+
+///Client service wrapping every outgoing request into client span
+///
+///Span is created as child of current span and its context is injected into request headers using global propagator (see [Context::inject_into]).
+///Span is annotated with `http.request.method`, `server.address`, `server.port`, `url.full` and, once response is ready, `http.response.status_code` attributes.
+///Credentials within `url.full` are replaced with `REDACTED`.
+///
+///Span status is set to error when inner service fails or responds with `4xx` or `5xx` status code.
+#[derive(Clone, Debug)]
+pub struct ClientTraceService<S> {
+    inner: S,
+}
+
+impl<S, B, R> tower::Service<http::Request<B>> for ClientTraceService<S> where S: tower::Service<http::Request<B>, Response = http::Response<R>>, S::Error: fmt::Display {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TraceFuture<S::Future>;
+
+    #[inline(always)]
+    fn poll_ready(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let method = req.method().as_str();
+        let uri = req.uri();
+        let span = tracing::info_span!(
+            "HTTP client request",
+            otel.name = method,
+            otel.kind = "client",
+            http.request.method = method,
+            server.address = uri.host(),
+            server.port = uri.port_u16(),
+            url.full = tracing::field::display(FullUrl(uri)),
+        );
+        Context::new(span.clone()).inject_into(req.headers_mut());
+
+        let inner = span.in_scope(|| self.inner.call(req));
+        TraceFuture {
+            inner,
+            span,
+            is_client: true,
+        }
+    }
+}
+
+///Displays `url.full` attribute of `uri` with credentials replaced by `REDACTED`, as required by semantic conventions
+struct FullUrl<'a>(&'a http::Uri);
+
+impl fmt::Display for FullUrl<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uri = self.0;
+        if let Some(scheme) = uri.scheme() {
+            fmt.write_str(scheme.as_str())?;
+            fmt.write_str("://")?;
+        }
+        if let Some(authority) = uri.authority() {
+            match authority.as_str().rsplit_once('@') {
+                Some((_, host)) => {
+                    fmt.write_str("REDACTED:REDACTED@")?;
+                    fmt.write_str(host)?;
+                },
+                None => fmt.write_str(authority.as_str())?,
+            }
+        }
+        fmt.write_str(uri.path())?;
+        if let Some(query) = uri.query() {
+            fmt.write_str("?")?;
+            fmt.write_str(query)?;
+        }
+        Ok(())
+    }
+}
+
+pin_project_lite::pin_project! {
+    ///Future of [TraceService] and [ClientTraceService]
+    pub struct TraceFuture<F> {
+        #[pin]
+        inner: F,
+        span: tracing::Span,
+        is_client: bool,
+    }
+}
+
+impl<F, R, E: fmt::Display> Future for TraceFuture<F> where F: Future<Output = Result<http::Response<R>, E>> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.span.in_scope(|| this.inner.poll(cx)) {
+            task::Poll::Ready(result) => result,
+            task::Poll::Pending => return task::Poll::Pending,
+        };
+
+        //Span is no longer needed once response is ready, so it is closed right away
+        let context = Context::new(core::mem::replace(this.span, tracing::Span::none()));
+        match result.as_ref() {
+            Ok(response) => {
+                let status = response.status();
+                context.record_lazy(crate::keys::HTTP_RESPONSE_STATUS_CODE, || i64::from(status.as_u16()));
+                //Client errors are caused by caller, hence they indicate failure only for client
+                if status.is_server_error() || (*this.is_client && status.is_client_error()) {
+                    context.set_status(Err(status.to_string().into()));
+                }
+            },
+            Err(error) => {
+                context.record_lazy(crate::keys::ERROR_MESSAGE, || error.to_string());
+                context.set_status(Err(error.to_string().into()));
+            },
+        }
+
+        task::Poll::Ready(result)
+    }
+}
+
+#[cfg(feature = "axum")]
+pub mod axum {
+    //! Axum integration
+    //!
+    //!Add [TraceLayer](super::TraceLayer) via `Router::layer` so that it is applied to every route after routing,
+    //!which allows to name spans after route template (e.g. `GET /users/{id}`).
+    //!Handlers can access request's span via [OtelContext] extractor
+    //!
+    //!```rust
+    //!use tracing_opentelemetry_setup::middleware::TraceLayer;
+    //!use tracing_opentelemetry_setup::middleware::axum::OtelContext;
+    //!
+    //!async fn get_user(OtelContext(context): OtelContext) -> &'static str {
+    //!    context.record_lazy("user.found", || true);
+    //!    "user"
+    //!}
+    //!
+    //!let router: axum::Router = axum::Router::new().route("/users/{id}", axum::routing::get(get_user)).layer(TraceLayer::new());
+    //!```
+
+    use core::convert::Infallible;
+
+    use crate::propagation::Context;
+
+    #[derive(Clone)]
+    ///Span of request, stored within request extensions by [TraceService](super::TraceService)
+    pub(super) struct RequestSpan(pub tracing::Span);
+
+    ///Extractor of request's [Context]
+    ///
+    ///Uses span created by [TraceService](super::TraceService), falling back to current span if middleware is not used
+    pub struct OtelContext(pub Context);
+
+    impl<S: Send + Sync> ::axum::extract::FromRequestParts<S> for OtelContext {
+        type Rejection = Infallible;
+
+        async fn from_request_parts(parts: &mut http::request::Parts, _: &S) -> Result<Self, Self::Rejection> {
+            match parts.extensions.get::<RequestSpan>() {
+                Some(span) => Ok(Self(Context::new(span.0.clone()))),
+                None => Ok(Self(Context::current())),
+            }
+        }
+    }
+}
@@ -1,25 +1,42 @@
 //! Panic hook module
 
 use core::panic::Location;
+#[cfg(feature = "file")]
+use std::io::Write;
 use std::panic::PanicHookInfo;
+#[cfg(feature = "file")]
+use std::path::PathBuf;
 use std::sync::OnceLock;
 use std::backtrace::{Backtrace, BacktraceStatus};
 
-///Panic hook implementation
-pub fn panic_hook(panic: &PanicHookInfo<'_>) {
-    const DEFAULT_MESSAGE: &'static str = "panic occurred";
+const DEFAULT_MESSAGE: &'static str = "panic occurred";
+
+#[cfg(feature = "file")]
+static ABORT_RECORD_PATH: OnceLock<PathBuf> = OnceLock::new();
 
-    let location = match panic.location() {
+#[inline]
+fn panic_location<'a>(panic: &'a PanicHookInfo<'_>) -> &'a Location<'a> {
+    match panic.location() {
         Some(location) => location,
         None => Location::caller(),
-    };
-    let msg = match panic.payload().downcast_ref::<&'static str>() {
+    }
+}
+
+#[inline]
+fn panic_message<'a>(panic: &'a PanicHookInfo<'_>) -> &'a str {
+    match panic.payload().downcast_ref::<&'static str>() {
         Some(message) => message,
         None => match panic.payload().downcast_ref::<String>() {
             Some(message) => message.as_str(),
-            None => &DEFAULT_MESSAGE,
+            None => DEFAULT_MESSAGE,
         }
-    };
+    }
+}
+
+///Panic hook implementation
+pub fn panic_hook(panic: &PanicHookInfo<'_>) {
+    let location = panic_location(panic);
+    let msg = panic_message(panic);
 
     let backtrace = Backtrace::force_capture();
     if let BacktraceStatus::Captured = backtrace.status() {
@@ -40,6 +57,82 @@ pub fn panic_hook(panic: &PanicHookInfo<'_>) {
     }
 }
 
+#[cfg(feature = "file")]
+///Instrumentation scope of abort records
+const ABORT_RECORD_SCOPE: &str = "tracing_opentelemetry_setup::panic";
+
+#[cfg(feature = "file")]
+#[inline]
+fn string_attr(key: &str, value: String) -> opentelemetry_proto::tonic::common::v1::KeyValue {
+    use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, KeyValue};
+
+    KeyValue {
+        key: key.to_owned(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+#[cfg(feature = "file")]
+///Synchronously appends exception record of `panic` to file at `path` as single line of `ExportLogsServiceRequest`, using the same format as [Protocol::File](crate::builder::Protocol::File)
+fn write_abort_record(path: &PathBuf, panic: &PanicHookInfo<'_>) -> std::io::Result<()> {
+    use opentelemetry::trace::TraceContextExt;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+    use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope};
+    use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+
+    let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+    let backtrace = Backtrace::force_capture();
+
+    let mut record = LogRecord {
+        time_unix_nano: timestamp,
+        observed_time_unix_nano: timestamp,
+        severity_number: opentelemetry::logs::Severity::Fatal as i32,
+        severity_text: "FATAL".to_owned(),
+        body: Some(AnyValue {
+            value: Some(any_value::Value::StringValue("exception".to_owned())),
+        }),
+        attributes: vec![
+            string_attr("exception.type", "Rust Panic".to_owned()),
+            string_attr("exception.message", panic_message(panic).to_owned()),
+            string_attr("exception.location", panic_location(panic).to_string()),
+        ],
+        ..Default::default()
+    };
+    if let BacktraceStatus::Captured = backtrace.status() {
+        record.attributes.push(string_attr("exception.stacktrace", backtrace.to_string()));
+    }
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span = span.span_context();
+    if span.is_valid() {
+        record.trace_id = span.trace_id().to_bytes().to_vec();
+        record.span_id = span.span_id().to_bytes().to_vec();
+        record.flags = span.trace_flags().to_u8() as u32;
+    }
+    let request = ExportLogsServiceRequest {
+        resource_logs: vec![ResourceLogs {
+            scope_logs: vec![ScopeLogs {
+                scope: Some(InstrumentationScope {
+                    name: ABORT_RECORD_SCOPE.to_owned(),
+                    ..Default::default()
+                }),
+                log_records: vec![record],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let mut line = serde_json::to_vec(&request).map_err(std::io::Error::other)?;
+    line.push(b'\n');
+    let mut file = std::fs::OpenOptions::new().append(true).create(true).open(path)?;
+    file.write_all(&line)?;
+    file.sync_all()
+}
+
 ///Installs [panic_hook] once
 pub fn install_panic_hook() {
     static ONCE: OnceLock<()> = OnceLock::new();
@@ -47,8 +140,122 @@ pub fn install_panic_hook() {
     ONCE.get_or_init(|| {
         let next = std::panic::take_hook();
         std::panic::set_hook(Box::new(move |info| {
+            #[cfg(feature = "file")]
+            if let Some(path) = ABORT_RECORD_PATH.get() {
+                let _ = write_abort_record(path, info);
+            }
             panic_hook(info);
             next(info);
         }));
     });
 }
+
+#[cfg(feature = "file")]
+///Installs [panic_hook] once, additionally appending exception record to file at `path` before anything else
+///
+///Intended for binaries built with `panic = "abort"`, where process dies right after panic hook returns,
+///leaving no chance for exporters to send pending exception.
+///Record is written synchronously as single line of OTLP/JSON `ExportLogsServiceRequest`, the same format as written by [Protocol::File](crate::builder::Protocol::File),
+///containing `FATAL` log record with `exception.*` attributes and, if panic happened within span, its trace context.
+///
+///Use [replay_abort_records] on next start to ship records via configured logs exporter.
+///
+///Only first `path` is used if called multiple times
+///
+///Requires `file` feature
+///
+///```rust,no_run
+///tracing_opentelemetry_setup::panic::install_abort_panic_hook("/var/log/app/panic.jsonl");
+///```
+pub fn install_abort_panic_hook(path: impl Into<PathBuf>) {
+    let _ = ABORT_RECORD_PATH.set(path.into());
+    install_panic_hook();
+}
+
+#[cfg(feature = "file")]
+fn replay_value(value: opentelemetry_proto::tonic::common::v1::AnyValue) -> Option<opentelemetry::logs::AnyValue> {
+    use opentelemetry_proto::tonic::common::v1::any_value::Value;
+
+    match value.value? {
+        Value::StringValue(value) => Some(value.into()),
+        Value::BoolValue(value) => Some(value.into()),
+        Value::IntValue(value) => Some(value.into()),
+        Value::DoubleValue(value) => Some(value.into()),
+        Value::BytesValue(value) => Some(opentelemetry::logs::AnyValue::Bytes(Box::new(value))),
+        Value::ArrayValue(_) | Value::KvlistValue(_) => None,
+    }
+}
+
+#[cfg(feature = "file")]
+///Emits exception records, written by [install_abort_panic_hook] into file at `path`, via logs exporter of `otlp` and removes the file
+///
+///Records keep original timestamp and trace context, while resource is taken from `otlp`.
+///Returns number of replayed records, which is `0` if file doesn't exist or `otlp` has no logs exporter (in which case file is kept)
+///
+///Requires `file` feature
+///
+///```rust,no_run
+///use tracing_opentelemetry_setup::builder::{Destination, Otlp, Protocol};
+///
+///let destination = Destination {
+///    protocol: Protocol::HttpBinary,
+///    url: "http://localhost:4318".into(),
+///};
+///let otlp = Otlp::builder(destination).with_logs(None).finish();
+///tracing_opentelemetry_setup::panic::replay_abort_records(&otlp, "/var/log/app/panic.jsonl").expect("to replay panics");
+///tracing_opentelemetry_setup::panic::install_abort_panic_hook("/var/log/app/panic.jsonl");
+///```
+pub fn replay_abort_records(otlp: &crate::Otlp, path: impl AsRef<std::path::Path>) -> std::io::Result<usize> {
+    use std::io::BufRead;
+    use opentelemetry::logs::{LogRecord as _, Logger as _, LoggerProvider as _};
+    use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+
+    let path = path.as_ref();
+    let provider = match otlp.logger_provider() {
+        Some(provider) => provider,
+        None => return Ok(0),
+    };
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error),
+    };
+
+    let mut count = 0;
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request = serde_json::from_str::<ExportLogsServiceRequest>(&line).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        for scope_logs in request.resource_logs.into_iter().flat_map(|resource| resource.scope_logs) {
+            let scope = scope_logs.scope.map(|scope| scope.name).unwrap_or_else(|| ABORT_RECORD_SCOPE.to_owned());
+            let logger = provider.logger_with_scope(opentelemetry::InstrumentationScope::builder(scope).build());
+            for record in scope_logs.log_records {
+                let mut log = logger.create_log_record();
+                log.set_timestamp(std::time::UNIX_EPOCH + core::time::Duration::from_nanos(record.time_unix_nano));
+                log.set_observed_timestamp(std::time::UNIX_EPOCH + core::time::Duration::from_nanos(record.observed_time_unix_nano));
+                //Abort records are always fatal
+                log.set_severity_number(opentelemetry::logs::Severity::Fatal);
+                log.set_severity_text("FATAL");
+                if let Some(body) = record.body.and_then(replay_value) {
+                    log.set_body(body);
+                }
+                for attr in record.attributes {
+                    if let Some(value) = attr.value.and_then(replay_value) {
+                        log.add_attribute(attr.key, value);
+                    }
+                }
+                if let (Ok(trace_id), Ok(span_id)) = (<[u8; 16]>::try_from(record.trace_id.as_slice()), <[u8; 8]>::try_from(record.span_id.as_slice())) {
+                    let flags = opentelemetry::trace::TraceFlags::new(record.flags as u8);
+                    log.set_trace_context(opentelemetry::TraceId::from_bytes(trace_id), opentelemetry::SpanId::from_bytes(span_id), Some(flags));
+                }
+                logger.emit(log);
+                count += 1;
+            }
+        }
+    }
+
+    std::fs::remove_file(path)?;
+    Ok(count)
+}
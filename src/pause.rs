@@ -0,0 +1,103 @@
+use core::time;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+///Exporter wrapper that drops data while `paused` flag is set
+///
+///Without flag, data is always exported
+#[derive(Debug)]
+pub struct PauseExporter<T> {
+    inner: T,
+    paused: Option<Arc<AtomicBool>>,
+}
+
+impl<T> PauseExporter<T> {
+    #[inline(always)]
+    pub fn new(inner: T, paused: Option<Arc<AtomicBool>>) -> Self {
+        Self {
+            inner,
+            paused,
+        }
+    }
+
+    #[inline(always)]
+    fn is_paused(&self) -> bool {
+        match self.paused.as_ref() {
+            Some(paused) => paused.load(Ordering::Acquire),
+            None => false,
+        }
+    }
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter for PauseExporter<T> {
+    #[inline(always)]
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        match self.is_paused() {
+            true => Ok(()),
+            false => self.inner.export(batch).await,
+        }
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogExporter for PauseExporter<T> {
+    #[inline(always)]
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        match self.is_paused() {
+            true => Ok(()),
+            false => self.inner.export(batch).await,
+        }
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter> opentelemetry_sdk::metrics::exporter::PushMetricExporter for PauseExporter<T> {
+    #[inline(always)]
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        match self.is_paused() {
+            true => Ok(()),
+            false => self.inner.export(metrics).await,
+        }
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.inner.temporality()
+    }
+}
@@ -0,0 +1,10 @@
+//!Prelude with re-exports of commonly used items
+//!
+//!Using it instead of direct dependencies guarantees that there is no version mismatch between your application and this crate
+
+pub use crate::{tracing, tracing_subscriber, opentelemetry, opentelemetry_sdk, tracing_opentelemetry};
+pub use crate::Otlp;
+pub use crate::builder::{Attributes, Destination, Protocol, TraceSettings, SamplerHandle, LogsSettings};
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+pub use crate::builder::MetricsSettings;
+pub use tracing_opentelemetry::OpenTelemetrySpanExt;
@@ -0,0 +1,114 @@
+//! Process resource usage metrics
+//!
+//!Values are read from `/proc/self` on Linux, hence instruments observe nothing on other platforms
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::MeterProvider;
+
+const CPU_TIME: &str = "process.cpu.time";
+const MEMORY_USAGE: &str = "process.memory.usage";
+const OPEN_FILE_DESCRIPTOR_COUNT: &str = "process.open_file_descriptor.count";
+const THREAD_COUNT: &str = "process.thread.count";
+const CPU_MODE: &str = "cpu.mode";
+
+#[cfg(target_os = "linux")]
+mod imp {
+    ///Returns number of clock ticks per second (`USER_HZ`), in which CPU times of `/proc/<pid>/stat` are reported
+    fn user_hz() -> f64 {
+        static USER_HZ: std::sync::OnceLock<f64> = std::sync::OnceLock::new();
+        *USER_HZ.get_or_init(|| {
+            //SAFETY: sysconf has no preconditions
+            match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+                ticks if ticks > 0 => ticks as f64,
+                //Kernel ABI fixes USER_HZ to 100 on all common architectures
+                _ => 100.0,
+            }
+        })
+    }
+
+    ///Returns `(user, system)` CPU time in seconds
+    pub fn cpu_time() -> Option<(f64, f64)> {
+        let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+        //Executable name can contain spaces and parentheses, so fields are counted after its closing parenthesis
+        let (_, fields) = stat.rsplit_once(')')?;
+        //`utime` and `stime` are 14th and 15th fields, while `fields` starts with 3rd one
+        let mut fields = fields.split_ascii_whitespace().skip(11);
+        let user = fields.next()?.parse::<u64>().ok()?;
+        let system = fields.next()?.parse::<u64>().ok()?;
+        let user_hz = user_hz();
+        Some((user as f64 / user_hz, system as f64 / user_hz))
+    }
+
+    fn status_field(name: &str) -> Option<i64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let value = status.lines().find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))?;
+        value.split_ascii_whitespace().next()?.parse().ok()
+    }
+
+    ///Returns resident set size in bytes
+    #[inline]
+    pub fn memory_usage() -> Option<i64> {
+        status_field("VmRSS").map(|kb| kb.saturating_mul(1024))
+    }
+
+    #[inline]
+    pub fn thread_count() -> Option<i64> {
+        status_field("Threads")
+    }
+
+    pub fn open_fds() -> Option<i64> {
+        let count = std::fs::read_dir("/proc/self/fd").ok()?.count();
+        //Directory being read is open file descriptor too
+        Some(count.saturating_sub(1) as i64)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    #[inline(always)]
+    pub fn cpu_time() -> Option<(f64, f64)> {
+        None
+    }
+
+    #[inline(always)]
+    pub fn memory_usage() -> Option<i64> {
+        None
+    }
+
+    #[inline(always)]
+    pub fn thread_count() -> Option<i64> {
+        None
+    }
+
+    #[inline(always)]
+    pub fn open_fds() -> Option<i64> {
+        None
+    }
+}
+
+///Registers observable instruments reporting process resource usage, which are sampled on every collection
+pub(crate) fn register(provider: &opentelemetry_sdk::metrics::SdkMeterProvider) {
+    let meter = provider.meter(env!("CARGO_PKG_NAME"));
+
+    meter.f64_observable_counter(CPU_TIME).with_unit("s").with_description("Total CPU seconds broken down by different states").with_callback(|observer| {
+        if let Some((user, system)) = imp::cpu_time() {
+            observer.observe(user, &[KeyValue::new(CPU_MODE, "user")]);
+            observer.observe(system, &[KeyValue::new(CPU_MODE, "system")]);
+        }
+    }).build();
+    meter.i64_observable_up_down_counter(MEMORY_USAGE).with_unit("By").with_description("The amount of physical memory in use").with_callback(|observer| {
+        if let Some(memory) = imp::memory_usage() {
+            observer.observe(memory, &[]);
+        }
+    }).build();
+    meter.i64_observable_up_down_counter(OPEN_FILE_DESCRIPTOR_COUNT).with_unit("{file_descriptor}").with_description("Number of file descriptors in use by the process").with_callback(|observer| {
+        if let Some(count) = imp::open_fds() {
+            observer.observe(count, &[]);
+        }
+    }).build();
+    meter.i64_observable_up_down_counter(THREAD_COUNT).with_unit("{thread}").with_description("Process threads count").with_callback(|observer| {
+        if let Some(count) = imp::thread_count() {
+            observer.observe(count, &[]);
+        }
+    }).build();
+}
@@ -0,0 +1,615 @@
+use core::time;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+///Type erased span processor, allowing to select processor at runtime
+#[derive(Debug)]
+pub struct SpanProcessor(Box<dyn opentelemetry_sdk::trace::SpanProcessor>);
+
+impl SpanProcessor {
+    #[inline(always)]
+    pub fn new(processor: impl opentelemetry_sdk::trace::SpanProcessor + 'static) -> Self {
+        Self(Box::new(processor))
+    }
+}
+
+impl opentelemetry_sdk::trace::SpanProcessor for SpanProcessor {
+    #[inline(always)]
+    fn on_start(&self, span: &mut opentelemetry_sdk::trace::Span, cx: &opentelemetry::Context) {
+        self.0.on_start(span, cx)
+    }
+
+    #[inline(always)]
+    fn on_end(&self, span: opentelemetry_sdk::trace::SpanData) {
+        self.0.on_end(span)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.0.set_resource(resource)
+    }
+}
+
+///Type erased log processor, allowing to select processor at runtime
+#[derive(Debug)]
+pub struct LogProcessor(Box<dyn opentelemetry_sdk::logs::LogProcessor>);
+
+impl LogProcessor {
+    #[inline(always)]
+    pub fn new(processor: impl opentelemetry_sdk::logs::LogProcessor + 'static) -> Self {
+        Self(Box::new(processor))
+    }
+}
+
+impl opentelemetry_sdk::logs::LogProcessor for LogProcessor {
+    #[inline(always)]
+    fn emit(&self, data: &mut opentelemetry_sdk::logs::SdkLogRecord, instrumentation: &opentelemetry::InstrumentationScope) {
+        self.0.emit(data, instrumentation)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.0.set_resource(resource)
+    }
+}
+
+///Log processor invoking hook on every record before passing it to inner processor
+pub struct HookLogProcessor<P> {
+    hook: crate::builder::LogHook,
+    inner: P,
+}
+
+impl<P> HookLogProcessor<P> {
+    #[inline(always)]
+    pub fn new(hook: crate::builder::LogHook, inner: P) -> Self {
+        Self {
+            hook,
+            inner,
+        }
+    }
+}
+
+impl<P: core::fmt::Debug> core::fmt::Debug for HookLogProcessor<P> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("HookLogProcessor").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<P: opentelemetry_sdk::logs::LogProcessor> opentelemetry_sdk::logs::LogProcessor for HookLogProcessor<P> {
+    #[inline(always)]
+    fn emit(&self, data: &mut opentelemetry_sdk::logs::SdkLogRecord, instrumentation: &opentelemetry::InstrumentationScope) {
+        (self.hook)(data);
+        self.inner.emit(data, instrumentation)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+///Combines results of every processor, reporting first error
+fn all_of(results: Vec<OTelSdkResult>) -> OTelSdkResult {
+    results.into_iter().find(Result::is_err).unwrap_or(Ok(()))
+}
+
+///Span processor passing every span to all inner processors, allowing to export it to multiple destinations
+#[derive(Debug)]
+pub struct FanOutSpanProcessor(Vec<SpanProcessor>);
+
+impl FanOutSpanProcessor {
+    #[inline(always)]
+    pub fn new(processors: Vec<SpanProcessor>) -> Self {
+        Self(processors)
+    }
+}
+
+impl opentelemetry_sdk::trace::SpanProcessor for FanOutSpanProcessor {
+    #[inline]
+    fn on_start(&self, span: &mut opentelemetry_sdk::trace::Span, cx: &opentelemetry::Context) {
+        for processor in self.0.iter() {
+            processor.on_start(span, cx);
+        }
+    }
+
+    fn on_end(&self, span: opentelemetry_sdk::trace::SpanData) {
+        if let Some((last, processors)) = self.0.split_last() {
+            for processor in processors {
+                processor.on_end(span.clone());
+            }
+            last.on_end(span);
+        }
+    }
+
+    #[inline]
+    fn force_flush(&self) -> OTelSdkResult {
+        //Results are collected first, so that every processor is flushed regardless of errors
+        all_of(self.0.iter().map(|processor| processor.force_flush()).collect())
+    }
+
+    #[inline]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        all_of(self.0.iter().map(|processor| processor.shutdown_with_timeout(timeout)).collect())
+    }
+
+    #[inline]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        for processor in self.0.iter_mut() {
+            processor.set_resource(resource);
+        }
+    }
+}
+
+///Log processor passing every record to all inner processors, allowing to export it to multiple destinations
+#[derive(Debug)]
+pub struct FanOutLogProcessor(Vec<LogProcessor>);
+
+impl FanOutLogProcessor {
+    #[inline(always)]
+    pub fn new(processors: Vec<LogProcessor>) -> Self {
+        Self(processors)
+    }
+}
+
+impl opentelemetry_sdk::logs::LogProcessor for FanOutLogProcessor {
+    #[inline]
+    fn emit(&self, data: &mut opentelemetry_sdk::logs::SdkLogRecord, instrumentation: &opentelemetry::InstrumentationScope) {
+        for processor in self.0.iter() {
+            processor.emit(data, instrumentation);
+        }
+    }
+
+    #[inline]
+    fn force_flush(&self) -> OTelSdkResult {
+        all_of(self.0.iter().map(|processor| processor.force_flush()).collect())
+    }
+
+    #[inline]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        all_of(self.0.iter().map(|processor| processor.shutdown_with_timeout(timeout)).collect())
+    }
+
+    #[inline]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        for processor in self.0.iter_mut() {
+            processor.set_resource(resource);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SpanDepth {
+    depth: u32,
+    //Deepest kept ancestor or span itself if it is kept
+    anchor: opentelemetry::trace::SpanId,
+    dropped: u64,
+    //Number of dropped descendants that are not finished yet
+    open_dropped: u32,
+    //Finished span waiting for its dropped descendants to finish
+    pending: Option<opentelemetry_sdk::trace::SpanData>,
+}
+
+impl SpanDepth {
+    ///Attaches number of dropped descendants to finished `span`
+    fn finish(mut span: opentelemetry_sdk::trace::SpanData, dropped: u64) -> opentelemetry_sdk::trace::SpanData {
+        if dropped > 0 {
+            span.attributes.push(opentelemetry::KeyValue::new(crate::keys::DROPPED_DESCENDANT_SPANS, dropped as i64));
+        }
+        span
+    }
+}
+
+#[derive(Debug, Default)]
+///Spans of single trace, kept until every span of the trace finishes, so that children started after their parent finished still know its depth
+struct TraceDepths {
+    spans: std::collections::HashMap<opentelemetry::trace::SpanId, SpanDepth>,
+    open: u32,
+}
+
+type DepthShard = std::sync::Mutex<std::collections::HashMap<opentelemetry::trace::TraceId, TraceDepths>>;
+
+const DEPTH_SHARDS: usize = 16;
+
+///Span processor that drops spans nested deeper than `max_depth` within the same process
+///
+///Number of dropped descendants is attached to the deepest kept ancestor as `otel.dropped_descendant_spans` attribute.
+///When ancestor finishes before its dropped descendants, it is held back until all of them finish.
+///
+///Depth is tracked per trace, with traces spread over independently locked shards
+#[derive(Debug)]
+pub struct DepthLimitProcessor<P> {
+    inner: P,
+    max_depth: u32,
+    traces: [DepthShard; DEPTH_SHARDS],
+}
+
+impl<P> DepthLimitProcessor<P> {
+    #[inline(always)]
+    pub fn new(inner: P, max_depth: u32) -> Self {
+        Self {
+            inner,
+            max_depth,
+            traces: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn shard(&self, trace_id: opentelemetry::trace::TraceId) -> std::sync::MutexGuard<'_, std::collections::HashMap<opentelemetry::trace::TraceId, TraceDepths>> {
+        let idx = u128::from_be_bytes(trace_id.to_bytes()) as usize % DEPTH_SHARDS;
+        self.traces[idx].lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<P: opentelemetry_sdk::trace::SpanProcessor> opentelemetry_sdk::trace::SpanProcessor for DepthLimitProcessor<P> {
+    fn on_start(&self, span: &mut opentelemetry_sdk::trace::Span, cx: &opentelemetry::Context) {
+        use opentelemetry::trace::{Span, TraceContextExt};
+
+        let trace_id = span.span_context().trace_id();
+        let id = span.span_context().span_id();
+        let parent = match cx.has_active_span() {
+            true => Some(cx.span().span_context().span_id()),
+            false => None,
+        };
+
+        let is_kept = {
+            let mut traces = self.shard(trace_id);
+            let trace = traces.entry(trace_id).or_default();
+            trace.open = trace.open.saturating_add(1);
+            let (depth, anchor) = match parent.and_then(|parent| trace.spans.get(&parent)) {
+                Some(parent) if parent.depth >= self.max_depth => (parent.depth.saturating_add(1), parent.anchor),
+                Some(parent) => (parent.depth + 1, id),
+                None => (1, id),
+            };
+            let is_kept = depth <= self.max_depth;
+            if !is_kept {
+                if let Some(anchor) = trace.spans.get_mut(&anchor) {
+                    anchor.dropped = anchor.dropped.saturating_add(1);
+                    anchor.open_dropped = anchor.open_dropped.saturating_add(1);
+                }
+            }
+            trace.spans.insert(id, SpanDepth {
+                depth,
+                anchor,
+                dropped: 0,
+                open_dropped: 0,
+                pending: None,
+            });
+            is_kept
+        };
+
+        if is_kept {
+            self.inner.on_start(span, cx)
+        }
+    }
+
+    fn on_end(&self, span: opentelemetry_sdk::trace::SpanData) {
+        let trace_id = span.span_context.trace_id();
+        let id = span.span_context.span_id();
+        let mut finished = Vec::new();
+        {
+            let mut traces = self.shard(trace_id);
+            let trace = match traces.get_mut(&trace_id) {
+                Some(trace) => trace,
+                None => {
+                    drop(traces);
+                    return self.inner.on_end(span);
+                },
+            };
+
+            if trace.spans.contains_key(&id) {
+                trace.open = trace.open.saturating_sub(1);
+            }
+            match trace.spans.get_mut(&id) {
+                Some(depth) if depth.depth <= self.max_depth => match depth.open_dropped {
+                    0 => finished.push(SpanDepth::finish(span, depth.dropped)),
+                    _ => depth.pending = Some(span),
+                },
+                Some(depth) => {
+                    let anchor = depth.anchor;
+                    if let Some(anchor) = trace.spans.get_mut(&anchor) {
+                        anchor.open_dropped = anchor.open_dropped.saturating_sub(1);
+                        if anchor.open_dropped == 0 {
+                            if let Some(pending) = anchor.pending.take() {
+                                finished.push(SpanDepth::finish(pending, anchor.dropped));
+                            }
+                        }
+                    }
+                },
+                None => finished.push(span),
+            }
+
+            if trace.open == 0 {
+                if let Some(trace) = traces.remove(&trace_id) {
+                    finished.extend(trace.spans.into_values().filter_map(|depth| depth.pending.map(|span| SpanDepth::finish(span, depth.dropped))));
+                }
+            }
+        }
+
+        for span in finished {
+            self.inner.on_end(span)
+        }
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+///Span processor keeping only last value of every attribute key
+///
+///SDK appends attributes set on started span, while OpenTelemetry specifies that setting existing key overwrites its value.
+///This happens when `tracing` field is recorded after span is started, or when native integer attributes override stringified values
+#[derive(Debug)]
+pub struct DedupAttributesProcessor<P> {
+    inner: P,
+}
+
+impl<P> DedupAttributesProcessor<P> {
+    #[inline(always)]
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+        }
+    }
+}
+
+impl<P: opentelemetry_sdk::trace::SpanProcessor> opentelemetry_sdk::trace::SpanProcessor for DedupAttributesProcessor<P> {
+    #[inline(always)]
+    fn on_start(&self, span: &mut opentelemetry_sdk::trace::Span, cx: &opentelemetry::Context) {
+        self.inner.on_start(span, cx)
+    }
+
+    fn on_end(&self, mut span: opentelemetry_sdk::trace::SpanData) {
+        let attributes = &mut span.attributes;
+        let has_duplicates = attributes.iter().enumerate().any(|(idx, attribute)| attributes[idx + 1..].iter().any(|other| other.key == attribute.key));
+        if has_duplicates {
+            let mut idx = 0;
+            while idx < attributes.len() {
+                let key = &attributes[idx].key;
+                if attributes[idx + 1..].iter().any(|other| other.key == *key) {
+                    attributes.remove(idx);
+                } else {
+                    idx += 1;
+                }
+            }
+        }
+        self.inner.on_end(span)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+///Runs `future` to completion on current thread
+pub(crate) fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        #[inline(always)]
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::sync::Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = core::task::Context::from_waker(&waker);
+    let mut future = core::pin::pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            core::task::Poll::Ready(result) => return result,
+            core::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+///Span processor buffering spans until flushed, exporting them on the thread that requested flush
+#[derive(Debug)]
+pub struct ManualSpanProcessor<E> {
+    exporter: std::sync::Mutex<E>,
+    spans: std::sync::Mutex<Vec<opentelemetry_sdk::trace::SpanData>>,
+    max_queue_size: usize,
+}
+
+impl<E> ManualSpanProcessor<E> {
+    #[inline(always)]
+    pub fn new(exporter: E, max_queue_size: usize) -> Self {
+        Self {
+            exporter: std::sync::Mutex::new(exporter),
+            spans: Default::default(),
+            max_queue_size,
+        }
+    }
+}
+
+impl<E: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanProcessor for ManualSpanProcessor<E> {
+    #[inline(always)]
+    fn on_start(&self, _span: &mut opentelemetry_sdk::trace::Span, _cx: &opentelemetry::Context) {
+    }
+
+    fn on_end(&self, span: opentelemetry_sdk::trace::SpanData) {
+        if !span.span_context.is_sampled() {
+            return;
+        }
+
+        let mut spans = self.spans.lock().unwrap_or_else(|error| error.into_inner());
+        if spans.len() < self.max_queue_size {
+            spans.push(span);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        let spans = core::mem::take(&mut *self.spans.lock().unwrap_or_else(|error| error.into_inner()));
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let exporter = self.exporter.lock().unwrap_or_else(|error| error.into_inner());
+        block_on(exporter.export(spans))
+    }
+
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        let result = self.force_flush();
+        self.exporter.lock().unwrap_or_else(|error| error.into_inner()).shutdown_with_timeout(timeout)?;
+        result
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.exporter.get_mut().unwrap_or_else(|error| error.into_inner()).set_resource(resource)
+    }
+}
+
+type LogRecords = Vec<(opentelemetry_sdk::logs::SdkLogRecord, opentelemetry::InstrumentationScope)>;
+
+///Log processor buffering records until flushed, exporting them on the thread that requested flush
+#[derive(Debug)]
+pub struct ManualLogProcessor<E> {
+    exporter: E,
+    records: std::sync::Mutex<LogRecords>,
+    max_queue_size: usize,
+}
+
+impl<E> ManualLogProcessor<E> {
+    #[inline(always)]
+    pub fn new(exporter: E, max_queue_size: usize) -> Self {
+        Self {
+            exporter,
+            records: Default::default(),
+            max_queue_size,
+        }
+    }
+}
+
+impl<E: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogProcessor for ManualLogProcessor<E> {
+    fn emit(&self, data: &mut opentelemetry_sdk::logs::SdkLogRecord, instrumentation: &opentelemetry::InstrumentationScope) {
+        let mut records = self.records.lock().unwrap_or_else(|error| error.into_inner());
+        if records.len() < self.max_queue_size {
+            records.push((data.clone(), instrumentation.clone()));
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        let records = core::mem::take(&mut *self.records.lock().unwrap_or_else(|error| error.into_inner()));
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let records = records.iter().map(|(record, instrumentation)| (record, instrumentation)).collect::<Vec<_>>();
+        block_on(self.exporter.export(opentelemetry_sdk::logs::LogBatch::new(&records)))
+    }
+
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        let result = self.force_flush();
+        self.exporter.shutdown_with_timeout(timeout)?;
+        result
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.exporter.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Metric reader collecting and exporting metrics on the thread that requested flush
+#[derive(Debug)]
+pub struct ManualMetricReader<E> {
+    reader: opentelemetry_sdk::metrics::ManualReader,
+    exporter: E,
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<E: opentelemetry_sdk::metrics::exporter::PushMetricExporter> ManualMetricReader<E> {
+    #[inline(always)]
+    pub fn new(exporter: E) -> Self {
+        Self {
+            reader: opentelemetry_sdk::metrics::ManualReader::builder().with_temporality(exporter.temporality()).build(),
+            exporter,
+        }
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<E: opentelemetry_sdk::metrics::exporter::PushMetricExporter + core::fmt::Debug> opentelemetry_sdk::metrics::reader::MetricReader for ManualMetricReader<E> {
+    #[inline(always)]
+    fn register_pipeline(&self, pipeline: std::sync::Weak<opentelemetry_sdk::metrics::Pipeline>) {
+        self.reader.register_pipeline(pipeline)
+    }
+
+    #[inline(always)]
+    fn collect(&self, rm: &mut opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        self.reader.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        let mut metrics = opentelemetry_sdk::metrics::data::ResourceMetrics::default();
+        self.reader.collect(&mut metrics)?;
+        block_on(self.exporter.export(&metrics))
+    }
+
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        let result = self.force_flush();
+        self.reader.shutdown_with_timeout(timeout)?;
+        self.exporter.shutdown_with_timeout(timeout)?;
+        result
+    }
+
+    #[inline(always)]
+    fn temporality(&self, kind: opentelemetry_sdk::metrics::InstrumentKind) -> opentelemetry_sdk::metrics::Temporality {
+        self.reader.temporality(kind)
+    }
+}
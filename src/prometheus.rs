@@ -0,0 +1,267 @@
+//! Prometheus pull exporter
+//!
+//!Enabled via [MetricsSettings::with_prometheus](crate::builder::MetricsSettings::with_prometheus), in which case metrics are not pushed to destination.
+//!Instead they are collected on demand via [PrometheusHandle::render], which produces text exposition format to be served on `/metrics`
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::Otlp;
+//!use tracing_opentelemetry_setup::builder::{Destination, Protocol, MetricsSettings};
+//!
+//!let destination = Destination {
+//!    protocol: Protocol::HttpBinary,
+//!    url: "http://localhost:45081".into()
+//!};
+//!let mut otlp = Otlp::builder(destination).with_metrics(None, MetricsSettings::new().with_prometheus()).finish();
+//!let handle = otlp.prometheus_handle().expect("prometheus is enabled");
+//!
+//!//Serve it as response of `GET /metrics` with `text/plain; version=0.0.4` content type
+//!let body = handle.render();
+//!otlp.shutdown(None).expect("to shutdown");
+//!```
+//!
+//!Following conversions are applied:
+//!- Monotonic sums are exported as `counter` with `_total` suffix;
+//!- Non-monotonic sums and gauges are exported as `gauge`;
+//!- Histograms are exported as `histogram`;
+//!- Exponential histograms are not supported and skipped.
+//!
+//!Metric and label names are sanitized by replacing unsupported characters with `_`.
+
+use core::fmt::Write;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+
+///Prometheus content type of [PrometheusHandle::render] output
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Debug)]
+pub(crate) struct PrometheusReader(Arc<opentelemetry_sdk::metrics::ManualReader>);
+
+impl opentelemetry_sdk::metrics::reader::MetricReader for PrometheusReader {
+    #[inline(always)]
+    fn register_pipeline(&self, pipeline: std::sync::Weak<opentelemetry_sdk::metrics::Pipeline>) {
+        self.0.register_pipeline(pipeline)
+    }
+
+    #[inline(always)]
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.0.collect(rm)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: core::time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self, _: opentelemetry_sdk::metrics::InstrumentKind) -> opentelemetry_sdk::metrics::Temporality {
+        //Prometheus expects cumulative values
+        opentelemetry_sdk::metrics::Temporality::Cumulative
+    }
+}
+
+#[derive(Clone, Debug)]
+///Handle to render metrics in Prometheus text format, created by [Otlp::prometheus_handle](crate::Otlp::prometheus_handle)
+pub struct PrometheusHandle(Arc<opentelemetry_sdk::metrics::ManualReader>);
+
+pub(crate) fn reader() -> (PrometheusReader, PrometheusHandle) {
+    let reader = Arc::new(opentelemetry_sdk::metrics::ManualReader::builder().build());
+    (PrometheusReader(reader.clone()), PrometheusHandle(reader))
+}
+
+impl PrometheusHandle {
+    ///Collects current metrics and renders them in Prometheus text exposition format
+    ///
+    ///Returns empty string if metrics cannot be collected (e.g. after shutdown)
+    pub fn render(&self) -> String {
+        use opentelemetry_sdk::metrics::reader::MetricReader;
+
+        let mut metrics = ResourceMetrics::default();
+        if self.0.collect(&mut metrics).is_err() {
+            return String::new();
+        }
+
+        let mut families = BTreeMap::<String, Family>::new();
+        for scope in metrics.scope_metrics() {
+            for metric in scope.metrics() {
+                match metric.data() {
+                    AggregatedMetrics::F64(data) => add_family(&mut families, metric.name(), metric.description(), data),
+                    AggregatedMetrics::U64(data) => add_family(&mut families, metric.name(), metric.description(), data),
+                    AggregatedMetrics::I64(data) => add_family(&mut families, metric.name(), metric.description(), data),
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for (name, family) in families {
+            if !family.help.is_empty() {
+                let _ = writeln!(out, "# HELP {name} {}", family.help);
+            }
+            let _ = writeln!(out, "# TYPE {name} {}", family.kind);
+            out.push_str(&family.samples);
+        }
+        out
+    }
+}
+
+struct Family {
+    help: String,
+    kind: &'static str,
+    samples: String,
+}
+
+trait Value: Copy {
+    fn write(self, out: &mut String);
+}
+
+impl Value for u64 {
+    #[inline(always)]
+    fn write(self, out: &mut String) {
+        let _ = write!(out, "{self}");
+    }
+}
+
+impl Value for i64 {
+    #[inline(always)]
+    fn write(self, out: &mut String) {
+        let _ = write!(out, "{self}");
+    }
+}
+
+impl Value for f64 {
+    #[inline]
+    fn write(self, out: &mut String) {
+        if self.is_nan() {
+            out.push_str("NaN");
+        } else if self == f64::INFINITY {
+            out.push_str("+Inf");
+        } else if self == f64::NEG_INFINITY {
+            out.push_str("-Inf");
+        } else {
+            let _ = write!(out, "{self}");
+        }
+    }
+}
+
+fn sanitize_name(name: &str, out: &mut String) {
+    for (idx, ch) in name.chars().enumerate() {
+        match ch {
+            'a'..='z' | 'A'..='Z' | '_' | ':' => out.push(ch),
+            '0'..='9' if idx > 0 => out.push(ch),
+            '0'..='9' => {
+                out.push('_');
+                out.push(ch);
+            },
+            _ => out.push('_'),
+        }
+    }
+}
+
+fn write_label(key: &str, value: &str, out: &mut String) {
+    if !out.is_empty() {
+        out.push(',');
+    }
+    sanitize_name(key, out);
+    out.push_str("=\"");
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+fn labels<'a>(attrs: impl Iterator<Item = &'a opentelemetry::KeyValue>) -> String {
+    let mut out = String::new();
+    for attr in attrs {
+        write_label(attr.key.as_str(), &attr.value.as_str(), &mut out);
+    }
+    out
+}
+
+///Writes series name with `labels`, followed by space
+fn write_series(out: &mut String, name: &str, suffix: &str, labels: &str) {
+    out.push_str(name);
+    out.push_str(suffix);
+    if !labels.is_empty() {
+        out.push('{');
+        out.push_str(labels);
+        out.push('}');
+    }
+    out.push(' ');
+}
+
+fn add_family<T: Value>(families: &mut BTreeMap<String, Family>, name: &str, description: &str, data: &MetricData<T>) {
+    let mut family_name = String::with_capacity(name.len() + 6);
+    sanitize_name(name, &mut family_name);
+    let kind = match data {
+        MetricData::Sum(sum) if sum.is_monotonic() => {
+            if !family_name.ends_with("_total") {
+                family_name.push_str("_total");
+            }
+            "counter"
+        },
+        MetricData::Sum(_) | MetricData::Gauge(_) => "gauge",
+        MetricData::Histogram(_) => "histogram",
+        MetricData::ExponentialHistogram(_) => return,
+    };
+
+    let family = families.entry(family_name.clone()).or_insert_with(|| Family {
+        help: description.replace('\\', "\\\\").replace('\n', "\\n"),
+        kind,
+        samples: String::new(),
+    });
+    //Conflicting instruments cannot share family
+    if family.kind != kind {
+        return;
+    }
+    let out = &mut family.samples;
+
+    match data {
+        MetricData::Gauge(gauge) => for point in gauge.data_points() {
+            write_series(out, &family_name, "", &labels(point.attributes()));
+            point.value().write(out);
+            out.push('\n');
+        },
+        MetricData::Sum(sum) => for point in sum.data_points() {
+            write_series(out, &family_name, "", &labels(point.attributes()));
+            point.value().write(out);
+            out.push('\n');
+        },
+        MetricData::Histogram(histogram) => for point in histogram.data_points() {
+            let labels = labels(point.attributes());
+            let mut cumulative = 0u64;
+            let mut le = String::new();
+            for (bound, count) in point.bounds().zip(point.bucket_counts()) {
+                cumulative = cumulative.saturating_add(count);
+                le.clear();
+                bound.write(&mut le);
+                let mut bucket_labels = labels.clone();
+                write_label("le", &le, &mut bucket_labels);
+                write_series(out, &family_name, "_bucket", &bucket_labels);
+                let _ = writeln!(out, "{cumulative}");
+            }
+            let mut bucket_labels = labels.clone();
+            write_label("le", "+Inf", &mut bucket_labels);
+            write_series(out, &family_name, "_bucket", &bucket_labels);
+            let _ = writeln!(out, "{}", point.count());
+            write_series(out, &family_name, "_sum", &labels);
+            point.sum().write(out);
+            out.push('\n');
+            write_series(out, &family_name, "_count", &labels);
+            let _ = writeln!(out, "{}", point.count());
+        },
+        MetricData::ExponentialHistogram(_) => (),
+    }
+}
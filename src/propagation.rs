@@ -5,7 +5,15 @@ use tracing::Span;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use opentelemetry::trace::Status;
 use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
-use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "b3")]
+use opentelemetry_zipkin::B3Encoding;
+
+///Baggage key requesting verbose telemetry for the request when set to `1` or `true`
+///
+///See [BaggageVerbosity](crate::layer::BaggageVerbosity)
+pub const VERBOSE_BAGGAGE_KEY: &str = "debug";
 
 ///Interface to inject parent trace context
 ///
@@ -40,15 +48,15 @@ impl<T: ParentDestination> ParentDestination for Box<T> {
 }
 
 #[cfg(feature = "grpc")]
-impl ParentDestination for tonic::metadata::MetadataMap {
+impl ParentDestination for ::tonic::metadata::MetadataMap {
     #[inline(always)]
     fn set(&mut self, key: &str, value: String) {
-        let key = tonic::metadata::MetadataKey::from_bytes(key.as_bytes()).expect("value header key");
+        let key = ::tonic::metadata::MetadataKey::from_bytes(key.as_bytes()).expect("value header key");
         self.insert(key, value.try_into().expect("value header value"));
     }
 }
 
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "tower", feature = "grpc", feature = "reqwest-middleware"))]
 impl ParentDestination for http::HeaderMap {
     #[inline(always)]
     fn set(&mut self, key: &str, value: String) {
@@ -78,6 +86,36 @@ impl<K: for<'a> From<&'a str> + Ord, V: From<String>> ParentDestination for std:
     }
 }
 
+///Destination setting context via closure
+///
+///Useful for message broker headers (e.g. Kafka `OwnedHeaders`, AMQP field tables or NATS headers) without writing own [ParentDestination] implementation
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::{Context, ParentDestinationFn};
+///
+/////Stands for AMQP field table
+///let mut table = std::collections::BTreeMap::<String, Vec<u8>>::new();
+///Context::current().inject_into(&mut ParentDestinationFn::new(|key: &str, value: String| {
+///    table.insert(key.to_owned(), value.into_bytes());
+///}));
+///```
+pub struct ParentDestinationFn<F: FnMut(&str, String)>(F);
+
+impl<F: FnMut(&str, String)> ParentDestinationFn<F> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(cb: F) -> Self {
+        Self(cb)
+    }
+}
+
+impl<F: FnMut(&str, String)> ParentDestination for ParentDestinationFn<F> {
+    #[inline(always)]
+    fn set(&mut self, key: &str, value: String) {
+        (self.0)(key, value)
+    }
+}
+
 #[repr(transparent)]
 struct ParentDestinationImpl<T: ParentDestination>(T);
 
@@ -165,7 +203,7 @@ impl<T: ParentSource> ParentSource for std::rc::Rc<T> {
 }
 
 #[cfg(feature = "grpc")]
-impl ParentSource for tonic::metadata::MetadataMap {
+impl ParentSource for ::tonic::metadata::MetadataMap {
     #[inline(always)]
     fn get(&self, key: &str) -> Option<&str> {
         self.get(key).and_then(|value| value.to_str().ok())
@@ -173,13 +211,13 @@ impl ParentSource for tonic::metadata::MetadataMap {
     #[inline(always)]
     fn keys(&self) -> impl Iterator<Item = &str> {
         self.iter().map(|kv| match kv {
-            tonic::metadata::KeyAndValueRef::Ascii(key, _) => key.as_str(),
-            tonic::metadata::KeyAndValueRef::Binary(key, _) => key.as_str(),
+            ::tonic::metadata::KeyAndValueRef::Ascii(key, _) => key.as_str(),
+            ::tonic::metadata::KeyAndValueRef::Binary(key, _) => key.as_str(),
         })
     }
 }
 
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", feature = "tower", feature = "grpc", feature = "reqwest-middleware"))]
 impl ParentSource for http::HeaderMap {
     #[inline(always)]
     fn get(&self, key: &str) -> Option<&str> {
@@ -254,6 +292,56 @@ impl<'a, K: AsRef<str> + 'a, V: AsRef<str> + 'a, T: IntoIterator<Item = (&'a K,
     }
 }
 
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+///Generic source taking over byte key value pairs, as used by message broker headers (e.g. Kafka record headers)
+///
+///Keys and values that are not valid UTF-8 are ignored.
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::{Context, ParentSourceBytes};
+///
+/////Stands for Kafka record headers
+///let mut headers = Vec::<(Vec<u8>, Vec<u8>)>::new();
+///Context::current().inject_into(&mut headers);
+///
+///let headers = headers.iter().map(|(key, value)| (key.as_slice(), value.as_slice())).collect::<Vec<(&[u8], &[u8])>>();
+///Context::current().set_parent_from(ParentSourceBytes::new(headers.as_slice()));
+///```
+pub struct ParentSourceBytes<'a, K: AsRef<[u8]> + 'a, V: AsRef<[u8]> + 'a, T: IntoIterator<Item = &'a (K, V)> + Copy + 'a> {
+    inner: T,
+    _fields: marker::PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K: AsRef<[u8]> + 'a, V: AsRef<[u8]> + 'a, T: IntoIterator<Item = &'a (K, V)> + Copy + 'a> ParentSourceBytes<'a, K, V, T> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(inner: T) -> Self {
+        Self {
+            inner,
+            _fields: marker::PhantomData
+        }
+    }
+}
+
+impl<'a, K: AsRef<[u8]> + 'a, V: AsRef<[u8]> + 'a, T: IntoIterator<Item = &'a (K, V)> + Copy + 'a> ParentSource for ParentSourceBytes<'a, K, V, T> {
+    #[inline(always)]
+    fn get(&self, expected_key: &str) -> Option<&str> {
+        for (key, value) in self.inner.into_iter() {
+            if key.as_ref() == expected_key.as_bytes() {
+                return core::str::from_utf8(value.as_ref()).ok();
+            }
+        }
+
+        None
+    }
+
+    #[inline(always)]
+    fn keys(&self) -> impl Iterator<Item = &str> {
+        self.inner.into_iter().filter_map(|(key, _)| core::str::from_utf8(key.as_ref()).ok())
+    }
+}
+
 impl<K: core::borrow::Borrow<str> + core::hash::Hash + Eq, V: AsRef<str>, S: core::hash::BuildHasher> ParentSource for std::collections::HashMap<K, V, S> {
     #[inline(always)]
     fn get(&self, expected_key: &str) -> Option<&str> {
@@ -278,20 +366,260 @@ impl<K: core::borrow::Borrow<str> + Ord, V: AsRef<str>> ParentSource for std::co
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+///Trace context propagation format
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::{Context, Format};
+///
+///let mut headers = std::collections::HashMap::<String, String>::new();
+///headers.insert("traceparent".to_owned(), "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_owned());
+///let context = Context::current();
+///context.set_parent_from_with(Format::TraceContext, &headers);
+///context.inject_into_with(Format::TraceContext, &mut headers);
+///```
+pub enum Format {
+    #[default]
+    ///W3C Trace Context, using `traceparent` and `tracestate` headers
+    TraceContext,
+    #[cfg(feature = "b3")]
+    ///B3 single header format, using `b3` header
+    ///
+    ///Extraction accepts multiple headers format too
+    B3Single,
+    #[cfg(feature = "b3")]
+    ///B3 multiple headers format, using `X-B3-TraceId`, `X-B3-SpanId` and `X-B3-Sampled` headers
+    ///
+    ///Extraction accepts single header format too
+    B3Multi,
+    ///W3C Baggage, using `baggage` header
+    ///
+    ///Carries no trace context on its own and is meant to be combined with other formats
+    Baggage,
+    #[cfg(feature = "xray")]
+    ///AWS X-Ray format, using `X-Amzn-Trace-Id` header
+    XRay,
+    #[cfg(feature = "datadog")]
+    ///Datadog format, using `x-datadog-*` headers
+    Datadog,
+    ///Combination of multiple formats
+    ///
+    ///Extraction uses first format that yields valid parent, while injection uses all formats
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::{Context, Format};
+    ///
+    ///const FORMAT: Format = Format::Composite(&[Format::TraceContext, Format::Baggage]);
+    ///let mut headers = std::collections::HashMap::<String, String>::new();
+    ///headers.insert("traceparent".to_owned(), "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_owned());
+    ///headers.insert("baggage".to_owned(), "tenant=acme".to_owned());
+    ///let context = Context::current();
+    ///context.set_parent_from_with(FORMAT, &headers);
+    ///context.inject_into_with(FORMAT, &mut headers);
+    ///```
+    Composite(&'static [Format]),
+}
+
+#[cfg(feature = "b3")]
+#[inline(always)]
+fn b3(encoding: B3Encoding) -> opentelemetry_zipkin::Propagator {
+    opentelemetry_zipkin::Propagator::with_encoding(encoding)
+}
+
+impl Format {
+    ///Extracts remote span context, if present in `source`
+    fn extract_span(&self, source: &dyn Extractor) -> Option<opentelemetry::trace::SpanContext> {
+        use opentelemetry::trace::TraceContextExt;
+
+        let span = match self {
+            Self::TraceContext => TraceContextPropagator::new().extract_with_context(&opentelemetry::Context::new(), source).span().span_context().clone(),
+            Self::Baggage => return None,
+            #[cfg(feature = "b3")]
+            Self::B3Single => b3(B3Encoding::SingleHeader).extract_with_context(&opentelemetry::Context::new(), source).span().span_context().clone(),
+            #[cfg(feature = "b3")]
+            Self::B3Multi => b3(B3Encoding::MultipleHeader).extract_with_context(&opentelemetry::Context::new(), source).span().span_context().clone(),
+            #[cfg(feature = "xray")]
+            Self::XRay => opentelemetry_aws::trace::XrayPropagator::new().extract_with_context(&opentelemetry::Context::new(), source).span().span_context().clone(),
+            #[cfg(feature = "datadog")]
+            Self::Datadog => opentelemetry_datadog::DatadogPropagator::new().extract_with_context(&opentelemetry::Context::new(), source).span().span_context().clone(),
+            Self::Composite(formats) => return formats.iter().find_map(|format| format.extract_span(source)),
+        };
+        match span.is_valid() {
+            true => Some(span),
+            false => None,
+        }
+    }
+
+    fn has_baggage(&self) -> bool {
+        match self {
+            Self::Baggage => true,
+            Self::Composite(formats) => formats.iter().any(Self::has_baggage),
+            _ => false,
+        }
+    }
+
+    fn extract(&self, context: &opentelemetry::Context, source: &dyn Extractor) -> opentelemetry::Context {
+        extract_all(core::slice::from_ref(self), context, source)
+    }
+
+    fn inject(&self, context: &opentelemetry::Context, dest: &mut dyn Injector) {
+        match self {
+            Self::TraceContext => TraceContextPropagator::new().inject_context(context, dest),
+            Self::Baggage => BaggagePropagator::new().inject_context(context, dest),
+            #[cfg(feature = "b3")]
+            Self::B3Single => b3(B3Encoding::SingleHeader).inject_context(context, dest),
+            #[cfg(feature = "b3")]
+            Self::B3Multi => b3(B3Encoding::MultipleHeader).inject_context(context, dest),
+            #[cfg(feature = "xray")]
+            Self::XRay => opentelemetry_aws::trace::XrayPropagator::new().inject_context(context, dest),
+            #[cfg(feature = "datadog")]
+            Self::Datadog => opentelemetry_datadog::DatadogPropagator::new().inject_context(context, dest),
+            Self::Composite(formats) => for format in formats.iter() {
+                format.inject(context, dest);
+            },
+        }
+    }
+
+    fn fields(&self, fields: &mut Vec<String>) {
+        let names: &[&str] = match self {
+            Self::TraceContext => &["traceparent", "tracestate"],
+            Self::Baggage => &["baggage"],
+            #[cfg(feature = "b3")]
+            Self::B3Single => {
+                fields.extend(b3(B3Encoding::SingleHeader).fields().map(ToOwned::to_owned));
+                &[]
+            },
+            #[cfg(feature = "b3")]
+            Self::B3Multi => {
+                fields.extend(b3(B3Encoding::MultipleHeader).fields().map(ToOwned::to_owned));
+                &[]
+            },
+            #[cfg(feature = "xray")]
+            Self::XRay => {
+                fields.extend(opentelemetry_aws::trace::XrayPropagator::new().fields().map(ToOwned::to_owned));
+                &[]
+            },
+            #[cfg(feature = "datadog")]
+            Self::Datadog => {
+                fields.extend(opentelemetry_datadog::DatadogPropagator::new().fields().map(ToOwned::to_owned));
+                &[]
+            },
+            Self::Composite(formats) => {
+                for format in formats.iter() {
+                    format.fields(fields);
+                }
+                &[]
+            },
+        };
+        fields.extend(names.iter().map(|name| (*name).to_owned()));
+    }
+}
+
+///Extracts parent from `source` on top of `context`, using first format with valid parent and baggage, if requested
+fn extract_all(formats: &[Format], context: &opentelemetry::Context, source: &dyn Extractor) -> opentelemetry::Context {
+    use opentelemetry::trace::TraceContextExt;
+
+    let context = match formats.iter().find_map(|format| format.extract_span(source)) {
+        Some(span) => context.with_remote_span_context(span),
+        None => context.clone(),
+    };
+    match formats.iter().any(Format::has_baggage) {
+        true => BaggagePropagator::new().extract_with_context(&context, source),
+        false => context,
+    }
+}
+
+#[derive(Debug)]
+struct GlobalPropagator {
+    formats: Vec<Format>,
+    fields: Vec<String>,
+}
+
+impl TextMapPropagator for GlobalPropagator {
+    #[inline(always)]
+    fn inject_context(&self, context: &opentelemetry::Context, dest: &mut dyn Injector) {
+        for format in self.formats.iter() {
+            format.inject(context, dest);
+        }
+    }
+
+    #[inline(always)]
+    fn extract_with_context(&self, context: &opentelemetry::Context, source: &dyn Extractor) -> opentelemetry::Context {
+        extract_all(&self.formats, context, source)
+    }
+
+    #[inline(always)]
+    fn fields(&self) -> opentelemetry::propagation::text_map_propagator::FieldIter<'_> {
+        opentelemetry::propagation::text_map_propagator::FieldIter::new(&self.fields)
+    }
+}
+
+static IS_GLOBAL_PROPAGATOR: AtomicBool = AtomicBool::new(false);
+
+///Extracts context from `source` on top of `context` using `format` or default propagator if `None`
+fn extract(format: Option<Format>, context: &opentelemetry::Context, source: &dyn Extractor) -> opentelemetry::Context {
+    match format {
+        Some(format) => format.extract(context, source),
+        None if IS_GLOBAL_PROPAGATOR.load(Ordering::Acquire) => opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract_with_context(context, source)),
+        None => Format::TraceContext.extract(context, source),
+    }
+}
+
+///Installs composite propagator of `formats` as opentelemetry global propagator
+///
+///Once installed, [Context::set_parent_from] and [Context::inject_into] use global propagator instead of W3C Trace Context.
+///Extraction uses first format that yields valid parent, while injection uses all formats.
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::{init_global_propagator, Format};
+///
+///init_global_propagator(&[Format::TraceContext, Format::Baggage]);
+///```
+pub fn init_global_propagator(formats: &[Format]) {
+    let mut fields = Vec::new();
+    for format in formats {
+        format.fields(&mut fields);
+    }
+    fields.sort_unstable();
+    fields.dedup();
+
+    opentelemetry::global::set_text_map_propagator(GlobalPropagator {
+        formats: formats.to_vec(),
+        fields,
+    });
+    IS_GLOBAL_PROPAGATOR.store(true, Ordering::Release);
+}
+
+///Baggage entries set on span via [Context::set_baggage]
+struct SpanBaggage(opentelemetry::baggage::Baggage);
+
 ///Span wrapper to provide opentelemetry context propagation
 pub struct Context {
     span: Span,
+    baggage: bool,
 }
 
 impl Context {
     #[inline(always)]
     ///Creates context associated with `span`
+    ///
+    ///Baggage propagation is disabled by default, see [with_baggage](Self::with_baggage)
     pub const fn new(span: Span) -> Self {
         Self {
             span,
+            baggage: false,
         }
     }
 
+    #[inline(always)]
+    ///Specifies whether W3C `baggage` header is propagated alongside trace context
+    ///
+    ///When enabled, [set_parent_from](Self::set_parent_from) retains extracted baggage and [inject_into](Self::inject_into) injects [baggage](Self::baggage)
+    pub const fn with_baggage(mut self, enabled: bool) -> Self {
+        self.baggage = enabled;
+        self
+    }
+
     #[inline(always)]
     ///Creates context from currently execution context using `tracing::Span::current`
     pub fn current() -> Self {
@@ -304,6 +632,53 @@ impl Context {
         self.span
     }
 
+    ///Returns whether span is sampled to be exported
+    ///
+    ///Returns `None` if span is not tracked by opentelemetry (e.g. it is disabled or there is no opentelemetry layer)
+    pub fn is_sampled(&self) -> Option<bool> {
+        use opentelemetry::trace::TraceContextExt;
+
+        if self.span.is_none() {
+            return None;
+        }
+
+        let ctx = self.span.context();
+        let span_ctx = ctx.span().span_context().clone();
+        if span_ctx.is_valid() {
+            Some(span_ctx.is_sampled())
+        } else {
+            None
+        }
+    }
+
+    ///Returns whether span is recording information
+    ///
+    ///Use it to skip expensive computations of attributes that would never be exported
+    pub fn is_recording(&self) -> bool {
+        use opentelemetry::trace::TraceContextExt;
+
+        !self.span.is_none() && self.span.context().span().is_recording()
+    }
+
+    #[inline]
+    ///Sets attribute `key` with value computed by `cb` only if span is recording
+    ///
+    ///Unlike `tracing::Span::record` attribute doesn't need to be declared ahead of time
+    ///
+    ///Dynamic keys of frequently created spans should be interned via [keys::intern](crate::keys::intern)
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::Context;
+    ///
+    ///let payload = vec![0u8; 4096];
+    ///Context::current().record_lazy("payload", || format!("{payload:?}"));
+    ///```
+    pub fn record_lazy<V: Into<opentelemetry::Value>>(&self, key: impl Into<opentelemetry::Key>, cb: impl FnOnce() -> V) {
+        if self.is_recording() {
+            self.span.set_attribute(key, cb());
+        }
+    }
+
     #[inline(always)]
     ///Sets span status where `Ok` variant indicates success while `Err` contains error message
     pub fn set_status(&self, status: Result<(), std::borrow::Cow<'static, str>>) {
@@ -325,8 +700,8 @@ impl Context {
     ///Note that it requires you to declare these fields ahead of time when creating span
     pub fn set_error<E: core::error::Error>(&self, error: &E) {
         if !self.span.is_none() {
-            self.span.record("error.type", core::any::type_name::<E>());
-            self.span.record("error.message", tracing::field::display(error));
+            self.span.record(crate::keys::ERROR_TYPE.as_str(), core::any::type_name::<E>());
+            self.span.record(crate::keys::ERROR_MESSAGE.as_str(), tracing::field::display(error));
             self.span.set_status(Status::Error {
                 description: error.to_string().into()
             });
@@ -334,21 +709,775 @@ impl Context {
     }
 
     #[inline(always)]
-    ///Sets parent context from `source`
+    ///Records `error` as `exception` span event following semantic conventions
     ///
-    ///Has effect only once
+    ///Event includes attributes `exception.type`, `exception.message` and, if backtrace capture is enabled
+    ///(see [std::backtrace::Backtrace::capture]), `exception.stacktrace` of the calling location.
+    ///
+    ///`exception.type` is name of `E`, for type erased errors use [record_exception_with_type](Self::record_exception_with_type)
+    ///
+    ///Unlike [set_error](Self::set_error) it does not change span status, so both can be used together
+    pub fn record_exception<E: core::error::Error>(&self, error: &E) {
+        self.record_exception_with_type(core::any::type_name::<E>(), error)
+    }
+
+    ///Records type erased `error` as `exception` span event with `exception.type` set to `error_type`
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::Context;
+    ///
+    ///let error: Box<dyn std::error::Error> = Box::new(std::io::Error::other("upstream refused connection"));
+    ///let span = Context::new(tracing::info_span!("request"));
+    ///span.record_exception_with_type("std::io::Error", &*error);
+    ///```
+    pub fn record_exception_with_type(&self, error_type: &str, error: &dyn core::error::Error) {
+        if self.span.is_none() {
+            return;
+        }
+
+        let mut attributes = vec![
+            opentelemetry::KeyValue::new(crate::keys::EXCEPTION_TYPE, error_type.to_owned()),
+            opentelemetry::KeyValue::new(crate::keys::EXCEPTION_MESSAGE, error.to_string()),
+        ];
+        let backtrace = std::backtrace::Backtrace::capture();
+        if let std::backtrace::BacktraceStatus::Captured = backtrace.status() {
+            attributes.push(opentelemetry::KeyValue::new(crate::keys::EXCEPTION_STACKTRACE, backtrace.to_string()));
+        }
+        self.span.add_event("exception", attributes);
+    }
+
+    #[inline]
+    ///Sets span kind
+    ///
+    ///Note that it requires you to declare field `otel.kind` ahead of time when creating span
+    ///and it has effect only until span is started (i.e. entered or its context is accessed)
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::Context;
+    ///use tracing_opentelemetry_setup::opentelemetry::trace::SpanKind;
+    ///
+    ///let span = Context::new(tracing::info_span!("GET /users/{id}", otel.kind = tracing::field::Empty));
+    ///span.set_kind(SpanKind::Server);
+    ///span.set_http_attributes("GET", "/users/{id}", 200);
+    ///```
+    pub fn set_kind(&self, kind: opentelemetry::trace::SpanKind) {
+        use opentelemetry::trace::SpanKind;
+
+        if !self.span.is_none() {
+            self.span.record("otel.kind", match kind {
+                SpanKind::Client => "client",
+                SpanKind::Server => "server",
+                SpanKind::Producer => "producer",
+                SpanKind::Consumer => "consumer",
+                SpanKind::Internal => "internal",
+            });
+        }
+    }
+
+    #[inline]
+    ///Sets HTTP attributes `http.request.method`, `http.route` and `http.response.status_code` following semantic conventions
+    ///
+    ///`route` should be low cardinality template (e.g. `/users/{id}`) rather than actual path.
+    ///Attributes don't need to be declared ahead of time
+    pub fn set_http_attributes(&self, method: &str, route: &str, status: u16) {
+        if self.is_recording() {
+            self.span.set_attribute(crate::keys::HTTP_REQUEST_METHOD, method.to_owned());
+            self.span.set_attribute(crate::keys::HTTP_ROUTE, route.to_owned());
+            self.span.set_attribute(crate::keys::HTTP_RESPONSE_STATUS_CODE, i64::from(status));
+        }
+    }
+
+    #[inline]
+    ///Sets database attributes `db.system.name` (e.g. `postgresql`) and `db.query.text` following semantic conventions
+    ///
+    ///`statement` should not contain sensitive values, so prefer parameterized queries.
+    ///Attributes don't need to be declared ahead of time
+    pub fn set_db_attributes(&self, system: &str, statement: &str) {
+        if self.is_recording() {
+            self.span.set_attribute(crate::keys::DB_SYSTEM_NAME, system.to_owned());
+            self.span.set_attribute(crate::keys::DB_QUERY_TEXT, statement.to_owned());
+        }
+    }
+
+    #[inline(always)]
+    ///Sets parent context from `source` using global propagator, if installed via [init_global_propagator], or W3C Trace Context format otherwise
+    ///
+    ///Note that extracted baggage is discarded unless [with_baggage](Self::with_baggage) is enabled,
+    ///hence `MetricsSettings::with_baggage_attrs` sees no entries of incoming request otherwise.
+    ///
+    ///See [set_parent_from_with](Self::set_parent_from_with) for details
     pub fn set_parent_from(&self, source: impl ParentSource) {
+        self.set_parent_from_impl(None, source)
+    }
+
+    #[inline(always)]
+    ///Sets parent context from `source` using specified `format`
+    ///
+    ///Has effect only once
+    ///
+    ///Baggage is extracted alongside trace context, and if it requests verbose telemetry via [VERBOSE_BAGGAGE_KEY],
+    ///span is marked for use with [BaggageVerbosity](crate::layer::BaggageVerbosity).
+    ///Extracted baggage is retained by span only if [with_baggage](Self::with_baggage) is enabled
+    pub fn set_parent_from_with(&self, format: Format, source: impl ParentSource) {
+        self.set_parent_from_impl(Some(format), source)
+    }
+
+    fn set_parent_from_impl(&self, format: Option<Format>, source: impl ParentSource) {
+        use opentelemetry::baggage::BaggageExt;
+
         if !self.span.is_none() {
-            let parent = TraceContextPropagator::new().extract(&ParentSourceImpl(source));
+            let source = ParentSourceImpl(source);
+            let parent = extract(format, &opentelemetry::Context::current(), &source);
+            let mut parent = BaggagePropagator::new().extract_with_context(&parent, &source);
+            if let Some(value) = parent.baggage().get(VERBOSE_BAGGAGE_KEY) {
+                if matches!(value.as_str(), "1" | "true") {
+                    crate::layer::mark_verbose(&self.span);
+                }
+            }
+            if !self.baggage && !parent.baggage().is_empty() {
+                parent = parent.with_cleared_baggage();
+            }
             let _ = self.span.set_parent(parent);
         }
     }
 
     #[inline(always)]
-    ///Extract `self` into `dest`
+    ///Links span to remote span context extracted from `source` using global propagator, if installed via [init_global_propagator], or W3C Trace Context format otherwise
+    ///
+    ///See [add_link_from_with](Self::add_link_from_with) for details
+    pub fn add_link_from(&self, source: impl ParentSource) {
+        self.add_link_from_impl(None, source)
+    }
+
+    #[inline(always)]
+    ///Links span to remote span context extracted from `source` using specified `format`
+    ///
+    ///Unlike [set_parent_from_with](Self::set_parent_from_with), it can be called multiple times, making it suitable for consumers processing batch of messages,
+    ///where each message carries its own trace context.
+    ///Does nothing if `source` contains no valid trace context
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::Context;
+    ///
+    ///let messages = vec![std::collections::HashMap::<String, String>::new(); 2];
+    ///let batch = Context::new(tracing::info_span!("process_batch"));
+    ///for message in messages.iter() {
+    ///    batch.add_link_from(message);
+    ///}
+    ///```
+    pub fn add_link_from_with(&self, format: Format, source: impl ParentSource) {
+        self.add_link_from_impl(Some(format), source)
+    }
+
+    fn add_link_from_impl(&self, format: Option<Format>, source: impl ParentSource) {
+        use opentelemetry::trace::TraceContextExt;
+
+        if !self.span.is_none() {
+            let source = ParentSourceImpl(source);
+            let context = extract(format, &opentelemetry::Context::new(), &source);
+            self.span.add_link(context.span().span_context().clone());
+        }
+    }
+
+    ///Sets baggage entry `key` to `value`, making it visible to this span and its descendants
+    ///
+    ///Requires subscriber to be built on top of `tracing_subscriber::Registry`
+    ///
+    ///```rust
+    ///use tracing_opentelemetry_setup::propagation::Context;
+    ///
+    ///let context = Context::current().with_baggage(true);
+    ///context.set_baggage("tenant", "acme");
+    ///let mut headers = std::collections::HashMap::<String, String>::new();
+    ///context.inject_into(&mut headers);
+    ///```
+    pub fn set_baggage(&self, key: impl Into<opentelemetry::Key>, value: impl Into<opentelemetry::StringValue>) {
+        use tracing_subscriber::registry::LookupSpan;
+
+        self.span.with_subscriber(|(id, dispatch)| {
+            if let Some(span) = dispatch.downcast_ref::<tracing_subscriber::Registry>().and_then(|registry| registry.span(id)) {
+                let mut extensions = span.extensions_mut();
+                match extensions.get_mut::<SpanBaggage>() {
+                    Some(baggage) => {
+                        baggage.0.insert(key, value);
+                    },
+                    None => {
+                        let mut baggage = opentelemetry::baggage::Baggage::new();
+                        baggage.insert(key, value);
+                        extensions.insert(SpanBaggage(baggage));
+                    }
+                }
+            }
+        });
+    }
+
+    #[inline]
+    ///Returns baggage of the span, consisting of baggage extracted from parent and entries set via [set_baggage](Self::set_baggage) on span or its ancestors
+    pub fn baggage(&self) -> opentelemetry::baggage::Baggage {
+        if self.span.is_none() {
+            return opentelemetry::baggage::Baggage::new();
+        }
+        self.merge_baggage(&self.span.context())
+    }
+
+    fn merge_baggage(&self, context: &opentelemetry::Context) -> opentelemetry::baggage::Baggage {
+        use opentelemetry::baggage::BaggageExt;
+        use tracing_subscriber::registry::LookupSpan;
+
+        let mut baggage = opentelemetry::baggage::Baggage::new();
+        let mut merge = |entries: &opentelemetry::baggage::Baggage| {
+            for (key, (value, metadata)) in entries.iter() {
+                baggage.insert_with_metadata(key.clone(), value.clone(), metadata.clone());
+            }
+        };
+        merge(context.baggage());
+        self.span.with_subscriber(|(id, dispatch)| {
+            if let Some(span) = dispatch.downcast_ref::<tracing_subscriber::Registry>().and_then(|registry| registry.span(id)) {
+                for span in span.scope().from_root() {
+                    if let Some(entries) = span.extensions().get::<SpanBaggage>() {
+                        merge(&entries.0);
+                    }
+                }
+            }
+        });
+        baggage
+    }
+
+    #[inline(always)]
+    ///Extract `self` into `dest` using global propagator, if installed via [init_global_propagator], or W3C Trace Context format otherwise
+    ///
+    ///Injects `baggage` too if [with_baggage](Self::with_baggage) is enabled
     pub fn inject_into(&self, dest: &mut impl ParentDestination) {
+        self.inject_into_impl(None, dest)
+    }
+
+    #[inline(always)]
+    ///Extract `self` into `dest` using specified `format`
+    ///
+    ///Injects `baggage` too if [with_baggage](Self::with_baggage) is enabled
+    pub fn inject_into_with(&self, format: Format, dest: &mut impl ParentDestination) {
+        self.inject_into_impl(Some(format), dest)
+    }
+
+    fn inject_into_impl(&self, format: Option<Format>, dest: &mut impl ParentDestination) {
+        use opentelemetry::baggage::BaggageExt;
+
         if !self.span.is_none() {
-            TraceContextPropagator::new().inject_context(&self.span.context(), &mut ParentDestinationImpl(dest));
+            let context = self.span.context();
+            let mut dest = ParentDestinationImpl(dest);
+            match format {
+                Some(format) => format.inject(&context, &mut dest),
+                None if IS_GLOBAL_PROPAGATOR.load(Ordering::Acquire) => opentelemetry::global::get_text_map_propagator(|propagator| propagator.inject_context(&context, &mut dest)),
+                None => Format::TraceContext.inject(&context, &mut dest),
+            }
+            if self.baggage {
+                let context = context.with_baggage(self.merge_baggage(&context));
+                BaggagePropagator::new().inject_context(&context, &mut dest);
+            }
+        }
+    }
+}
+
+///Root span of scheduled job
+///
+///Created via [Context::start_job]
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::Context;
+///
+///let job = Context::start_job("cleanup", "0 * * * *");
+///let result = job.in_scope(|| std::fs::metadata("."));
+/////Pass your `Otlp` instance to flush telemetry once job is done
+///job.finish(&result, None);
+///```
+pub struct Job {
+    context: Context,
+}
+
+impl Job {
+    #[inline(always)]
+    ///Access job's context
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    #[inline(always)]
+    ///Executes `cb` within job's span
+    pub fn in_scope<R>(&self, cb: impl FnOnce() -> R) -> R {
+        self.context.span.in_scope(cb)
+    }
+
+    ///Completes job, setting its status from `result`
+    ///
+    ///Once span is closed, flushes all telemetry of `otlp`, if provided
+    pub fn finish<T, E: core::error::Error>(self, result: &Result<T, E>, otlp: Option<&crate::Otlp>) {
+        match result {
+            Ok(_) => self.context.set_status(Ok(())),
+            Err(error) => self.context.set_error(error),
+        }
+        drop(self.context);
+
+        if let Some(otlp) = otlp {
+            otlp.flush();
+        }
+    }
+}
+
+impl Context {
+    #[track_caller]
+    ///Starts root span for scheduled job with `name` triggered according to cron `schedule`
+    ///
+    ///Span is annotated with `faas.*` attributes and `code.*` attributes of the caller location
+    pub fn start_job(name: &str, schedule: &str) -> Job {
+        let location = core::panic::Location::caller();
+        let span = tracing::info_span!(
+            parent: None,
+            "job",
+            otel.name = name,
+            faas.name = name,
+            faas.trigger = "timer",
+            faas.cron = schedule,
+            code.file.path = location.file(),
+            code.line.number = location.line(),
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        );
+
+        Job {
+            context: Self::new(span),
+        }
+    }
+}
+
+///Scoped operation span, started from extracted parent
+///
+///Created via [Context::start_operation]
+///
+///Once dropped, operation duration in seconds is recorded as `operation.duration` attribute
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::Context;
+///
+///let headers = std::collections::HashMap::<String, String>::new();
+///let operation = Context::start_operation("get_user", &headers);
+///let result = operation.run(|| std::fs::metadata("."));
+///```
+pub struct ScopedOperation {
+    context: Context,
+    started: std::time::Instant,
+}
+
+impl ScopedOperation {
+    #[inline(always)]
+    ///Access operation's context
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    #[inline(always)]
+    ///Returns time elapsed since operation started
+    pub fn elapsed(&self) -> core::time::Duration {
+        self.started.elapsed()
+    }
+
+    #[inline(always)]
+    ///Executes `cb` within operation's span
+    pub fn in_scope<R>(&self, cb: impl FnOnce() -> R) -> R {
+        self.context.span.in_scope(cb)
+    }
+
+    ///Executes `cb` within operation's span, setting span status from its result
+    pub fn run<T, E: core::error::Error>(&self, cb: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        let result = self.in_scope(cb);
+        match &result {
+            Ok(_) => self.context.set_status(Ok(())),
+            Err(error) => self.context.set_error(error),
+        }
+        result
+    }
+}
+
+impl Drop for ScopedOperation {
+    #[inline]
+    fn drop(&mut self) {
+        let started = self.started;
+        self.context.record_lazy(crate::keys::OPERATION_DURATION, || started.elapsed().as_secs_f64());
+    }
+}
+
+impl Context {
+    #[track_caller]
+    ///Starts operation span with `name`, using `parent` as source of parent context
+    ///
+    ///Span is annotated with `code.*` attributes of the caller location
+    pub fn start_operation(name: &str, parent: impl ParentSource) -> ScopedOperation {
+        let location = core::panic::Location::caller();
+        let span = tracing::info_span!(
+            "operation",
+            otel.name = name,
+            code.file.path = location.file(),
+            code.line.number = location.line(),
+            error.type = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        );
+        let context = Self::new(span);
+        context.set_parent_from(parent);
+
+        ScopedOperation {
+            context,
+            started: std::time::Instant::now(),
+        }
+    }
+}
+
+///Closure wrapper that carries context of its creator to the place where it is executed
+///
+///Captures current `tracing::Span` and opentelemetry context, re-attaching both when called.
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::ContextCarryingFn;
+///
+///let span = tracing::info_span!("parent");
+///let cb = span.in_scope(|| ContextCarryingFn::new(|| tracing::info!("executed within parent")));
+///std::thread::spawn(move || cb.call()).join().expect("to finish");
+///```
+pub struct ContextCarryingFn<F> {
+    span: Span,
+    context: opentelemetry::Context,
+    cb: F,
+}
+
+impl<R, F: FnOnce() -> R> ContextCarryingFn<F> {
+    #[inline]
+    ///Wraps `cb`, capturing current context
+    pub fn new(cb: F) -> Self {
+        Self {
+            span: Span::current(),
+            context: opentelemetry::Context::current(),
+            cb,
+        }
+    }
+
+    #[inline]
+    ///Executes `cb` within captured context
+    pub fn call(self) -> R {
+        let _guard = self.context.attach();
+        self.span.in_scope(self.cb)
+    }
+}
+
+#[inline]
+///Instruments `future` with `span`, which parent is set from `source` using global propagator (see [Context::set_parent_from])
+///
+///Shortcut for creating span, setting its remote parent and instrumenting handler's future with it.
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::with_remote_parent;
+///
+///async fn handle(headers: &std::collections::HashMap<String, String>) -> u16 {
+///    with_remote_parent(headers, tracing::info_span!("handle"), async {
+///        tracing::info!("handling request");
+///        200
+///    }).await
+///}
+///```
+pub fn with_remote_parent<F: core::future::Future>(source: impl ParentSource, span: Span, future: F) -> tracing::instrument::Instrumented<F> {
+    use tracing::instrument::Instrument;
+
+    let context = Context::new(span);
+    context.set_parent_from(source);
+    future.instrument(context.into_tracing_span())
+}
+
+///Future wrapper that carries context of its creator to the place where it is polled, created by [carry_context]
+pub type ContextCarryingFuture<F> = tracing::instrument::Instrumented<opentelemetry::trace::WithContext<F>>;
+
+#[inline]
+///Wraps `future`, capturing current `tracing::Span` and opentelemetry context (including baggage), re-attaching both on every poll
+///
+///```rust
+///use tracing_opentelemetry_setup::propagation::carry_context;
+///
+///let span = tracing::info_span!("parent");
+///let future = span.in_scope(|| carry_context(async { tracing::info!("executed within parent") }));
+///```
+pub fn carry_context<F: core::future::Future>(future: F) -> ContextCarryingFuture<F> {
+    use tracing::instrument::Instrument;
+    use opentelemetry::trace::FutureExt;
+
+    //Opentelemetry context is attached after entering span, so that it is not shadowed by span's own context
+    future.with_current_context().instrument(Span::current())
+}
+
+#[cfg(feature = "rt-tokio")]
+#[inline]
+///Spawns tokio task executing `future` within context of the caller
+///
+///Unlike `tokio::spawn` spans created in new task retain parent of the caller and opentelemetry context, including baggage, remains attached
+pub fn spawn_traced_task<F: core::future::Future + Send + 'static>(future: F) -> tokio::task::JoinHandle<F::Output> where F::Output: Send + 'static {
+    tokio::spawn(carry_context(future))
+}
+
+#[inline]
+///Spawns new OS thread executing `cb` within context of the caller
+///
+///Unlike `std::thread::spawn` spans created in new thread retain parent of the caller
+pub fn spawn_traced<R: Send + 'static, F: FnOnce() -> R + Send + 'static>(cb: F) -> std::thread::JoinHandle<R> {
+    let cb = ContextCarryingFn::new(cb);
+    std::thread::spawn(move || cb.call())
+}
+
+#[cfg(feature = "grpc")]
+pub mod tonic {
+    //! gRPC integration
+    //!
+    //!Provides [client_interceptor] injecting context into outgoing requests and [ServerLayer] creating server span for every incoming request
+    //!
+    //!```rust,no_run
+    //!use tracing_opentelemetry_setup::propagation::tonic::{client_interceptor, ServerLayer};
+    //!
+    //!# async fn run() {
+    //!let channel = tonic::transport::Channel::from_static("http://localhost:50051").connect_lazy();
+    //!let channel = tonic::service::interceptor::InterceptedService::new(channel, client_interceptor());
+    //!
+    //!//Pass it to `tonic::transport::Server::builder().layer()`
+    //!let server_layer = ServerLayer::new();
+    //!# }
+    //!```
+
+    use core::fmt;
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task;
+
+    use super::Context;
+
+    #[inline(always)]
+    ///Creates interceptor injecting current context into metadata of outgoing requests, see [ClientInterceptor]
+    pub const fn client_interceptor() -> ClientInterceptor {
+        ClientInterceptor {
+        }
+    }
+
+    ///Interceptor injecting current context into request metadata using global propagator (see [Context::inject_into])
+    ///
+    ///Make sure to call client within span, describing the call, so that server's span becomes its child
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct ClientInterceptor {
+    }
+
+    impl ::tonic::service::Interceptor for ClientInterceptor {
+        #[inline]
+        fn call(&mut self, mut request: ::tonic::Request<()>) -> Result<::tonic::Request<()>, ::tonic::Status> {
+            Context::current().inject_into(request.metadata_mut());
+            Ok(request)
+        }
+    }
+
+    ///Layer wrapping gRPC server with [ServerService]
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct ServerLayer {
+    }
+
+    impl ServerLayer {
+        #[inline(always)]
+        ///Creates new layer
+        pub const fn new() -> Self {
+            Self {
+            }
+        }
+    }
+
+    impl<S> tower::Layer<S> for ServerLayer {
+        type Service = ServerService<S>;
+
+        #[inline(always)]
+        fn layer(&self, inner: S) -> Self::Service {
+            ServerService {
+                inner
+            }
+        }
+    }
+
+    ///Service wrapping every gRPC request into server span named after its method (e.g. `helloworld.Greeter/SayHello`)
+    ///
+    ///Span parent is extracted from request metadata using global propagator (see [Context::set_parent_from]).
+    ///Span is annotated with `rpc.system`, `rpc.service`, `rpc.method` and, if response carries status within headers, `rpc.grpc.status_code` attributes.
+    ///
+    ///Span status is set to error when inner service fails or responds with status indicating server error (e.g. `INTERNAL` or `UNAVAILABLE`).
+    ///Note that status of streaming responses is sent within trailers, hence it is not recorded
+    #[derive(Clone, Debug)]
+    pub struct ServerService<S> {
+        inner: S,
+    }
+
+    impl<S, B, R> tower::Service<http::Request<B>> for ServerService<S> where S: tower::Service<http::Request<B>, Response = http::Response<R>>, S::Error: fmt::Display {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = ServerFuture<S::Future>;
+
+        #[inline(always)]
+        fn poll_ready(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let name = req.uri().path().trim_start_matches('/');
+            let (service, method) = name.split_once('/').unwrap_or((name, ""));
+            let span = tracing::info_span!(
+                "gRPC request",
+                otel.name = name,
+                otel.kind = "server",
+                rpc.system = "grpc",
+                rpc.service = service,
+                rpc.method = method,
+            );
+            Context::new(span.clone()).set_parent_from(req.headers());
+
+            let inner = span.in_scope(|| self.inner.call(req));
+            ServerFuture {
+                inner,
+                span,
+            }
+        }
+    }
+
+    pin_project_lite::pin_project! {
+        ///Future of [ServerService]
+        pub struct ServerFuture<F> {
+            #[pin]
+            inner: F,
+            span: tracing::Span,
+        }
+    }
+
+    impl<F, R, E: fmt::Display> Future for ServerFuture<F> where F: Future<Output = Result<http::Response<R>, E>> {
+        type Output = F::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+            let this = self.project();
+            let result = match this.span.in_scope(|| this.inner.poll(cx)) {
+                task::Poll::Ready(result) => result,
+                task::Poll::Pending => return task::Poll::Pending,
+            };
+
+            //Span is no longer needed once response is ready, so it is closed right away
+            let context = Context::new(core::mem::replace(this.span, tracing::Span::none()));
+            match result.as_ref() {
+                Ok(response) => {
+                    let status = response.headers().get("grpc-status").and_then(|status| status.to_str().ok()).and_then(|status| status.parse::<i32>().ok());
+                    if let Some(status) = status {
+                        context.record_lazy(crate::keys::RPC_GRPC_STATUS_CODE, || i64::from(status));
+                        let code = ::tonic::Code::from_i32(status);
+                        if let ::tonic::Code::Unknown | ::tonic::Code::DeadlineExceeded | ::tonic::Code::Unimplemented | ::tonic::Code::Internal | ::tonic::Code::Unavailable | ::tonic::Code::DataLoss = code {
+                            context.set_status(Err(code.description().into()));
+                        }
+                    }
+                },
+                Err(error) => {
+                    context.record_lazy(crate::keys::ERROR_MESSAGE, || error.to_string());
+                    context.set_status(Err(error.to_string().into()));
+                },
+            }
+
+            task::Poll::Ready(result)
+        }
+    }
+}
+
+#[cfg(feature = "reqwest-middleware")]
+pub mod reqwest {
+    //! Reqwest integration
+    //!
+    //!Provides [TracingMiddleware] for [reqwest-middleware](https://crates.io/crates/reqwest-middleware) client
+    //!
+    //!```rust
+    //!use tracing_opentelemetry_setup::propagation::reqwest::TracingMiddleware;
+    //!
+    //!let client = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).with(TracingMiddleware::new()).build();
+    //!```
+
+    use core::future::Future;
+    use core::pin::Pin;
+
+    use tracing::Instrument;
+
+    use super::Context;
+
+    ///Middleware wrapping every outgoing request into client span
+    ///
+    ///Span is created as child of current span and its context, including baggage if enabled, is injected into request headers using global propagator (see [Context::inject_into]).
+    ///Span is annotated with `http.request.method`, `server.address`, `server.port`, `url.full` and, once response is received, `http.response.status_code` attributes.
+    ///
+    ///Span status is set to error when request fails or response has `4xx` or `5xx` status code.
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct TracingMiddleware {
+        baggage: bool,
+    }
+
+    impl TracingMiddleware {
+        #[inline(always)]
+        ///Creates new middleware
+        pub const fn new() -> Self {
+            Self {
+                baggage: false,
+            }
+        }
+
+        #[inline(always)]
+        ///Specifies whether W3C `baggage` header is injected alongside trace context, see [Context::with_baggage]
+        pub const fn with_baggage(mut self, enabled: bool) -> Self {
+            self.baggage = enabled;
+            self
+        }
+
+        async fn handle_impl(&self, mut req: ::reqwest::Request, extensions: &mut http::Extensions, next: reqwest_middleware::Next<'_>) -> reqwest_middleware::Result<::reqwest::Response> {
+            let method = req.method().as_str();
+            let url = req.url();
+            //Credentials must never be recorded
+            let url_full = match url.username().is_empty() && url.password().is_none() {
+                true => std::borrow::Cow::Borrowed(url),
+                false => {
+                    let mut url = url.clone();
+                    let _ = url.set_username("");
+                    let _ = url.set_password(None);
+                    std::borrow::Cow::Owned(url)
+                },
+            };
+            let span = tracing::info_span!(
+                "HTTP client request",
+                otel.name = method,
+                otel.kind = "client",
+                http.request.method = method,
+                server.address = url.host_str(),
+                server.port = url.port_or_known_default(),
+                url.full = url_full.as_str(),
+            );
+            let context = Context::new(span.clone()).with_baggage(self.baggage);
+            context.inject_into(req.headers_mut());
+
+            let result = next.run(req, extensions).instrument(span).await;
+            match result.as_ref() {
+                Ok(response) => {
+                    let status = response.status();
+                    context.record_lazy(crate::keys::HTTP_RESPONSE_STATUS_CODE, || i64::from(status.as_u16()));
+                    if status.is_client_error() || status.is_server_error() {
+                        context.set_status(Err(status.to_string().into()));
+                    }
+                },
+                Err(error) => {
+                    context.record_lazy(crate::keys::ERROR_MESSAGE, || error.to_string());
+                    context.set_status(Err(error.to_string().into()));
+                },
+            }
+            result
+        }
+    }
+
+    impl reqwest_middleware::Middleware for TracingMiddleware {
+        #[inline(always)]
+        fn handle<'life0, 'life1, 'life2, 'async_trait>(&'life0 self, req: ::reqwest::Request, extensions: &'life1 mut http::Extensions, next: reqwest_middleware::Next<'life2>) -> Pin<Box<dyn Future<Output = reqwest_middleware::Result<::reqwest::Response>> + Send + 'async_trait>> where 'life0: 'async_trait, 'life1: 'async_trait, 'life2: 'async_trait, Self: 'async_trait {
+            Box::pin(self.handle_impl(req, extensions, next))
         }
     }
 }
@@ -0,0 +1,92 @@
+use core::time;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+///Exporter wrapper that raises `ready` flag once any export succeeds
+#[derive(Debug)]
+pub struct ReadyExporter<T> {
+    inner: T,
+    ready: Arc<AtomicBool>,
+}
+
+impl<T> ReadyExporter<T> {
+    #[inline(always)]
+    pub fn new(inner: T, ready: Arc<AtomicBool>) -> Self {
+        Self {
+            inner,
+            ready,
+        }
+    }
+
+    #[inline(always)]
+    fn on_result(&self, result: OTelSdkResult) -> OTelSdkResult {
+        if result.is_ok() {
+            self.ready.store(true, Ordering::Release);
+        }
+        result
+    }
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter for ReadyExporter<T> {
+    #[inline(always)]
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        self.on_result(self.inner.export(batch).await)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogExporter for ReadyExporter<T> {
+    #[inline(always)]
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        self.on_result(self.inner.export(batch).await)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter> opentelemetry_sdk::metrics::exporter::PushMetricExporter for ReadyExporter<T> {
+    #[inline(always)]
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        self.on_result(self.inner.export(metrics).await)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.inner.temporality()
+    }
+}
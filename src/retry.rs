@@ -0,0 +1,322 @@
+use core::{cmp, fmt, time};
+use std::time::Instant;
+
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+
+use crate::builder::RetrySettings;
+
+fn jitter(backoff: time::Duration) -> time::Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    //RandomState is seeded randomly so it is good enough source of jitter
+    let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    let half = backoff / 2;
+    let range = half.as_nanos() as u64;
+    half + time::Duration::from_nanos(random % range.saturating_add(1))
+}
+
+struct Backoff {
+    settings: RetrySettings,
+    timeout: time::Duration,
+    started: Instant,
+    attempt: u32,
+    backoff: time::Duration,
+}
+
+impl Backoff {
+    #[inline(always)]
+    fn new(settings: RetrySettings, timeout: time::Duration) -> Self {
+        Self {
+            settings,
+            timeout,
+            started: Instant::now(),
+            attempt: 1,
+            backoff: settings.initial_backoff,
+        }
+    }
+
+    #[inline(always)]
+    fn is_last_attempt(&self) -> bool {
+        self.attempt >= self.settings.max_attempts
+    }
+
+    ///Waits before next attempt, returning `false` if `error` should not be retried
+    fn wait(&mut self, error: &OTelSdkError) -> bool {
+        if let OTelSdkError::AlreadyShutdown = error {
+            return false;
+        }
+
+        let delay = jitter(self.backoff);
+        if self.started.elapsed().saturating_add(delay) >= self.timeout {
+            return false;
+        }
+
+        //Exporters are run by processors on dedicated thread so blocking is fine here
+        std::thread::sleep(delay);
+        self.attempt = self.attempt.saturating_add(1);
+        self.backoff = cmp::min(self.backoff.saturating_mul(2), self.settings.max_backoff);
+        true
+    }
+}
+
+///Exporter wrapper that retries failed exports with exponential backoff
+pub struct RetryExporter<T> {
+    inner: T,
+    settings: Option<RetrySettings>,
+    timeout: time::Duration,
+}
+
+impl<T> RetryExporter<T> {
+    #[inline(always)]
+    pub fn new(inner: T, settings: Option<RetrySettings>, timeout: time::Duration) -> Self {
+        Self {
+            inner,
+            settings,
+            timeout,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RetryExporter<T> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RetryExporter")
+           .field("inner", &self.inner)
+           .field("settings", &self.settings)
+           .finish()
+    }
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter for RetryExporter<T> {
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        let settings = match self.settings {
+            Some(settings) => settings,
+            None => return self.inner.export(batch).await,
+        };
+
+        let mut backoff = Backoff::new(settings, self.timeout);
+        loop {
+            if backoff.is_last_attempt() {
+                return self.inner.export(batch).await;
+            }
+
+            match self.inner.export(batch.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(error) => if !backoff.wait(&error) {
+                    return Err(error);
+                },
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogExporter for RetryExporter<T> {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        use opentelemetry_sdk::logs::LogBatch;
+
+        let settings = match self.settings {
+            Some(settings) => settings,
+            None => return self.inner.export(batch).await,
+        };
+
+        //LogBatch cannot be cloned so we have to re-create it for every attempt
+        let records = batch.iter().collect::<Vec<_>>();
+        let mut backoff = Backoff::new(settings, self.timeout);
+        loop {
+            match self.inner.export(LogBatch::new(&records)).await {
+                Ok(()) => return Ok(()),
+                Err(error) => if backoff.is_last_attempt() || !backoff.wait(&error) {
+                    return Err(error);
+                },
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter> opentelemetry_sdk::metrics::exporter::PushMetricExporter for RetryExporter<T> {
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        let settings = match self.settings {
+            Some(settings) => settings,
+            None => return self.inner.export(metrics).await,
+        };
+
+        let mut backoff = Backoff::new(settings, self.timeout);
+        loop {
+            match self.inner.export(metrics).await {
+                Ok(()) => return Ok(()),
+                Err(error) => if backoff.is_last_attempt() || !backoff.wait(&error) {
+                    return Err(error);
+                },
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.inner.temporality()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use core::time;
+    use std::sync::Arc;
+
+    use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+    use opentelemetry_sdk::trace::SpanExporter as _;
+
+    use super::{Backoff, RetryExporter};
+    use crate::ready::ReadyExporter;
+    use crate::builder::RetrySettings;
+    use crate::processor::block_on;
+
+    ///Fails first `failures` exports with `error`
+    #[derive(Debug)]
+    struct FlakyExporter {
+        attempts: Arc<AtomicU32>,
+        failures: u32,
+        error: fn() -> OTelSdkError,
+    }
+
+    impl opentelemetry_sdk::trace::SpanExporter for FlakyExporter {
+        async fn export(&self, _: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            match attempt <= self.failures {
+                true => Err((self.error)()),
+                false => Ok(()),
+            }
+        }
+    }
+
+    fn failure() -> OTelSdkError {
+        OTelSdkError::InternalFailure("unavailable".to_owned())
+    }
+
+    fn settings(max_attempts: u32) -> RetrySettings {
+        RetrySettings {
+            max_attempts,
+            initial_backoff: time::Duration::from_millis(1),
+            max_backoff: time::Duration::from_millis(2),
+        }
+    }
+
+    fn export(settings: Option<RetrySettings>, timeout: time::Duration, failures: u32, error: fn() -> OTelSdkError) -> (OTelSdkResult, u32, bool) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let ready = Arc::new(AtomicBool::new(false));
+        let inner = FlakyExporter {
+            attempts: attempts.clone(),
+            failures,
+            error,
+        };
+        let exporter = ReadyExporter::new(RetryExporter::new(inner, settings, timeout), ready.clone());
+        let result = block_on(exporter.export(Vec::new()));
+        (result, attempts.load(Ordering::SeqCst), ready.load(Ordering::SeqCst))
+    }
+
+    #[test]
+    fn should_not_retry_without_settings() {
+        let (result, attempts, ready) = export(None, time::Duration::from_secs(5), 1, failure);
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        assert!(!ready);
+    }
+
+    #[test]
+    fn should_retry_until_success() {
+        let (result, attempts, ready) = export(Some(settings(3)), time::Duration::from_secs(5), 2, failure);
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+        assert!(ready);
+    }
+
+    #[test]
+    fn should_stop_after_max_attempts() {
+        let (result, attempts, ready) = export(Some(settings(3)), time::Duration::from_secs(5), u32::MAX, failure);
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+        assert!(!ready);
+    }
+
+    #[test]
+    fn should_stop_retrying_once_timeout_would_be_exceeded() {
+        let settings = RetrySettings {
+            max_attempts: 10,
+            initial_backoff: time::Duration::from_secs(10),
+            max_backoff: time::Duration::from_secs(10),
+        };
+        let (result, attempts, _) = export(Some(settings), time::Duration::from_secs(1), u32::MAX, failure);
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn should_not_retry_after_shutdown() {
+        let (result, attempts, _) = export(Some(settings(3)), time::Duration::from_secs(5), u32::MAX, || OTelSdkError::AlreadyShutdown);
+        assert!(matches!(result, Err(OTelSdkError::AlreadyShutdown)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn should_grow_backoff_exponentially_up_to_max() {
+        let settings = RetrySettings {
+            max_attempts: 5,
+            initial_backoff: time::Duration::from_millis(1),
+            max_backoff: time::Duration::from_millis(3),
+        };
+        let mut backoff = Backoff::new(settings, time::Duration::from_secs(5));
+        assert!(!backoff.is_last_attempt());
+        let mut backoffs = Vec::new();
+        for _ in 0..4 {
+            assert!(backoff.wait(&failure()));
+            backoffs.push(backoff.backoff);
+        }
+        assert_eq!(backoffs, [2, 3, 3, 3].map(time::Duration::from_millis));
+        assert!(backoff.is_last_attempt());
+    }
+
+    #[test]
+    fn should_jitter_within_upper_half_of_backoff() {
+        let backoff = time::Duration::from_millis(100);
+        for _ in 0..100 {
+            let delay = super::jitter(backoff);
+            assert!(delay >= backoff / 2 && delay <= backoff, "{delay:?}");
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Sampling utilities
+
+use core::time;
+use std::borrow::Cow;
+
+///Tail sampling configuration
+///
+///Spans are buffered per trace until local root span ends or `window` elapses since first span of the trace ended.
+///Expired traces are checked by background thread every half of `window`.
+///Then whole trace is exported only if it contains error or span with duration exceeding latency threshold, otherwise it is dropped.
+///
+///Flushing telemetry forces decision on all buffered traces.
+///
+///Note that only spans sampled by head sampler are seen, so it is recommended to use sample rate `1.0` together with tail sampling
+///
+///```rust
+///use tracing_opentelemetry_setup::builder::TraceSettings;
+///use tracing_opentelemetry_setup::sampling::TailSampling;
+///
+///let tail = TailSampling::new(core::time::Duration::from_secs(30)).with_latency_threshold(core::time::Duration::from_millis(500));
+///let settings = TraceSettings::new(1.0).with_tail_sampling(tail);
+///```
+#[derive(Copy, Clone, Debug)]
+pub struct TailSampling {
+    #[cfg_attr(not(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing")), allow(unused))]
+    pub(crate) window: time::Duration,
+    pub(crate) latency_threshold: Option<time::Duration>,
+    pub(crate) max_traces: usize,
+}
+
+impl TailSampling {
+    ///Default max number of traces buffered at once
+    pub const DEFAULT_MAX_TRACES: usize = 10_000;
+
+    #[inline(always)]
+    ///Creates new configuration buffering traces for no longer than `window`
+    ///
+    ///By default only traces with errors are exported
+    pub const fn new(window: time::Duration) -> Self {
+        Self {
+            window,
+            latency_threshold: None,
+            max_traces: Self::DEFAULT_MAX_TRACES,
+        }
+    }
+
+    #[inline(always)]
+    ///Exports traces containing span with duration equal or above `latency_threshold`
+    pub const fn with_latency_threshold(mut self, latency_threshold: time::Duration) -> Self {
+        self.latency_threshold = Some(latency_threshold);
+        self
+    }
+
+    #[inline(always)]
+    ///Sets max number of traces buffered at once. Defaults to [DEFAULT_MAX_TRACES](Self::DEFAULT_MAX_TRACES)
+    ///
+    ///Once limit is reached, spans of new traces are evaluated individually without buffering
+    pub const fn with_max_traces(mut self, max_traces: usize) -> Self {
+        self.max_traces = max_traces;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+///Matcher of spans for sampling rule
+pub enum SamplingMatcher {
+    ///Matches spans which name starts with specified prefix
+    SpanName(Cow<'static, str>),
+    ///Matches spans which `tracing` target starts with specified prefix
+    ///
+    ///Relies on `target` attribute, populated by default by `tracing-opentelemetry`
+    Target(Cow<'static, str>),
+}
+
+impl SamplingMatcher {
+    #[inline(always)]
+    ///Creates matcher of spans with name starting with `prefix`
+    pub fn span_name(prefix: impl Into<Cow<'static, str>>) -> Self {
+        Self::SpanName(prefix.into())
+    }
+
+    #[inline(always)]
+    ///Creates matcher of spans with `tracing` target starting with `prefix`
+    pub fn target(prefix: impl Into<Cow<'static, str>>) -> Self {
+        Self::Target(prefix.into())
+    }
+
+    #[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+    fn is_match(&self, name: &str, attributes: &[opentelemetry::KeyValue]) -> bool {
+        match self {
+            Self::SpanName(prefix) => name.starts_with(prefix.as_ref()),
+            Self::Target(prefix) => attributes.iter().any(|attr| attr.key.as_str() == "target" && attr.value.as_str().starts_with(prefix.as_ref())),
+        }
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+#[derive(Clone, Debug)]
+///Sampler applying ratio of the first matching rule, falling back to `fallback` if no rule matches
+pub(crate) struct RuleSampler<T> {
+    pub rules: Vec<(SamplingMatcher, f64)>,
+    pub fallback: T,
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+impl<T: opentelemetry_sdk::trace::ShouldSample + Clone + 'static> opentelemetry_sdk::trace::ShouldSample for RuleSampler<T> {
+    fn should_sample(&self, parent_context: Option<&opentelemetry::Context>, trace_id: opentelemetry::TraceId, name: &str, span_kind: &opentelemetry::trace::SpanKind, attributes: &[opentelemetry::KeyValue], links: &[opentelemetry::trace::Link]) -> opentelemetry::trace::SamplingResult {
+        for (matcher, rate) in self.rules.iter() {
+            if matcher.is_match(name, attributes) {
+                let sampler = opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(*rate);
+                return sampler.should_sample(parent_context, trace_id, name, span_kind, attributes, links);
+            }
+        }
+
+        self.fallback.should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+pub(crate) use processor::TailSamplingProcessor;
+
+#[cfg(any(feature = "grpc", feature = "http", feature = "datadog", feature = "stdout", feature = "file", feature = "testing"))]
+mod processor {
+    use core::time;
+    use std::collections::HashMap;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex, mpsc};
+    use std::time::Instant;
+
+    use opentelemetry::trace::TraceId;
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::trace::SpanData;
+
+    use super::TailSampling;
+
+    struct TraceBuffer {
+        started: Instant,
+        spans: Vec<SpanData>,
+        is_interesting: bool,
+    }
+
+    ///Decision state shared with background sweeper
+    struct Shared<P> {
+        inner: P,
+        settings: TailSampling,
+        traces: Mutex<HashMap<TraceId, TraceBuffer>>,
+    }
+
+    impl<P: opentelemetry_sdk::trace::SpanProcessor> Shared<P> {
+        fn is_interesting(&self, span: &SpanData) -> bool {
+            if let opentelemetry::trace::Status::Error { .. } = span.status {
+                return true;
+            }
+
+            match self.settings.latency_threshold {
+                Some(threshold) => span.end_time.duration_since(span.start_time).unwrap_or(time::Duration::ZERO) >= threshold,
+                None => false,
+            }
+        }
+
+        fn complete(&self, trace: TraceBuffer) {
+            if trace.is_interesting {
+                for span in trace.spans {
+                    self.inner.on_end(span);
+                }
+            }
+        }
+
+        #[inline]
+        fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<TraceId, TraceBuffer>> {
+            self.traces.lock().unwrap_or_else(|error| error.into_inner())
+        }
+
+        ///Completes traces buffered for longer than window
+        fn sweep(&self) {
+            let now = Instant::now();
+            let window = self.settings.window;
+            let expired = {
+                let mut traces = self.lock();
+                let expired = traces.iter().filter(|(_, trace)| now.duration_since(trace.started) >= window).map(|(trace_id, _)| *trace_id).collect::<Vec<_>>();
+                expired.into_iter().filter_map(|trace_id| traces.remove(&trace_id)).collect::<Vec<_>>()
+            };
+            for trace in expired {
+                self.complete(trace);
+            }
+        }
+
+        fn drain(&self) {
+            let traces = core::mem::take(&mut *self.lock());
+            for (_, trace) in traces {
+                self.complete(trace);
+            }
+        }
+    }
+
+    enum Sweeper {
+        Idle,
+        Running(mpsc::Sender<()>, std::thread::JoinHandle<()>),
+        Stopped,
+    }
+
+    ///Span processor buffering traces and passing only interesting ones to `inner`
+    ///
+    ///Expired traces are completed by background thread, started once first trace is buffered and stopped on shutdown
+    pub struct TailSamplingProcessor<P> {
+        shared: Arc<Shared<P>>,
+        is_sweeping: AtomicBool,
+        sweeper: Mutex<Sweeper>,
+    }
+
+    impl<P: opentelemetry_sdk::trace::SpanProcessor + 'static> TailSamplingProcessor<P> {
+        #[inline(always)]
+        pub fn new(inner: P, settings: TailSampling) -> Self {
+            Self {
+                shared: Arc::new(Shared {
+                    inner,
+                    settings,
+                    traces: Mutex::new(HashMap::new()),
+                }),
+                is_sweeping: AtomicBool::new(false),
+                sweeper: Mutex::new(Sweeper::Idle),
+            }
+        }
+
+        #[inline]
+        fn lock_sweeper(&self) -> std::sync::MutexGuard<'_, Sweeper> {
+            self.sweeper.lock().unwrap_or_else(|error| error.into_inner())
+        }
+
+        fn start_sweeper(&self) {
+            if self.is_sweeping.swap(true, Ordering::AcqRel) {
+                return;
+            }
+
+            let mut sweeper = self.lock_sweeper();
+            if !matches!(*sweeper, Sweeper::Idle) {
+                return;
+            }
+
+            let interval = (self.shared.settings.window / 2).max(time::Duration::from_millis(10));
+            let (sender, receiver) = mpsc::channel::<()>();
+            let shared = self.shared.clone();
+            let thread = std::thread::Builder::new().name("otlp-tail-sampling".to_owned()).spawn(move || {
+                //Stops once processor is shut down or dropped
+                while let Err(mpsc::RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+                    shared.sweep();
+                }
+            });
+            *sweeper = match thread {
+                Ok(thread) => Sweeper::Running(sender, thread),
+                Err(error) => {
+                    tracing::warn!(target: "tracing_opentelemetry_setup", %error, "Unable to spawn tail sampling thread, expired traces are completed only on flush");
+                    Sweeper::Stopped
+                },
+            };
+        }
+    }
+
+    impl<P: opentelemetry_sdk::trace::SpanProcessor> core::fmt::Debug for TailSamplingProcessor<P> {
+        #[inline(always)]
+        fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            fmt.debug_struct("TailSamplingProcessor")
+               .field("inner", &self.shared.inner)
+               .field("settings", &self.shared.settings)
+               .finish()
+        }
+    }
+
+    impl<P: opentelemetry_sdk::trace::SpanProcessor + 'static> opentelemetry_sdk::trace::SpanProcessor for TailSamplingProcessor<P> {
+        #[inline(always)]
+        fn on_start(&self, span: &mut opentelemetry_sdk::trace::Span, cx: &opentelemetry::Context) {
+            self.shared.inner.on_start(span, cx)
+        }
+
+        fn on_end(&self, span: SpanData) {
+            let shared = &self.shared;
+            let trace_id = span.span_context.trace_id();
+            let is_local_root = span.parent_span_id == opentelemetry::trace::SpanId::INVALID || span.parent_span_is_remote;
+            let is_interesting = shared.is_interesting(&span);
+
+            let completed = {
+                let mut traces = shared.lock();
+                match traces.remove(&trace_id) {
+                    Some(mut trace) => {
+                        trace.is_interesting |= is_interesting;
+                        trace.spans.push(span);
+                        if is_local_root {
+                            Some(trace)
+                        } else {
+                            traces.insert(trace_id, trace);
+                            None
+                        }
+                    },
+                    None => {
+                        let trace = TraceBuffer {
+                            started: Instant::now(),
+                            spans: vec![span],
+                            is_interesting,
+                        };
+                        if is_local_root || traces.len() >= shared.settings.max_traces {
+                            Some(trace)
+                        } else {
+                            traces.insert(trace_id, trace);
+                            drop(traces);
+                            self.start_sweeper();
+                            None
+                        }
+                    },
+                }
+            };
+
+            if let Some(trace) = completed {
+                shared.complete(trace);
+            }
+        }
+
+        #[inline]
+        fn force_flush(&self) -> OTelSdkResult {
+            self.shared.drain();
+            self.shared.inner.force_flush()
+        }
+
+        fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+            self.is_sweeping.store(true, Ordering::Release);
+            let sweeper = core::mem::replace(&mut *self.lock_sweeper(), Sweeper::Stopped);
+            if let Sweeper::Running(sender, thread) = sweeper {
+                drop(sender);
+                let _ = thread.join();
+            }
+            self.shared.drain();
+            self.shared.inner.shutdown_with_timeout(timeout)
+        }
+
+        #[inline(always)]
+        fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+            //Resource is set when provider is built, before any trace is buffered and sweeper is started
+            if let Some(shared) = Arc::get_mut(&mut self.shared) {
+                shared.inner.set_resource(resource)
+            }
+        }
+    }
+}
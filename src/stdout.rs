@@ -0,0 +1,433 @@
+//! Human readable exporters for local development
+//!
+//!Every span, log record and metric data point is written as single line prefixed with signal name.
+//!Log records can be written as JSON object per line instead, for collectors tailing container output.
+//!
+//!`opentelemetry-stdout` is not used as it only prints multi-line dumps to stdout via `println!`,
+//!which can be neither redirected into `stderr`/file nor grepped by signal.
+
+use std::{fs, io};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use core::fmt::{self, Write};
+use core::sync::atomic::{self, Ordering};
+use core::time;
+
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+
+enum Output {
+    Stdout,
+    Stderr,
+    ///File is opened on first write and kept open afterwards
+    File(PathBuf, Mutex<Option<fs::File>>),
+}
+
+impl Output {
+    #[inline]
+    fn new(url: &str) -> Self {
+        match url.strip_prefix("file://") {
+            Some(path) => Self::File(path.into(), Mutex::new(None)),
+            None if url.starts_with("stderr://") => Self::Stderr,
+            None => Self::Stdout,
+        }
+    }
+
+    fn write(&self, text: &str) -> OTelSdkResult {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let result = match self {
+            Self::Stdout => {
+                let mut out = io::stdout().lock();
+                out.write_all(text.as_bytes()).and_then(|_| out.flush())
+            },
+            Self::Stderr => {
+                let mut out = io::stderr().lock();
+                out.write_all(text.as_bytes()).and_then(|_| out.flush())
+            },
+            Self::File(path, file) => {
+                let mut file = file.lock().unwrap_or_else(|error| error.into_inner());
+                let result = match file.as_mut() {
+                    Some(out) => out.write_all(text.as_bytes()),
+                    None => fs::OpenOptions::new().append(true).create(true).open(path).and_then(|mut out| {
+                        out.write_all(text.as_bytes())?;
+                        *file = Some(out);
+                        Ok(())
+                    }),
+                };
+                //Re-open file on next write in case it is no longer valid
+                if result.is_err() {
+                    *file = None;
+                }
+                result
+            },
+        };
+        result.map_err(|error| OTelSdkError::InternalFailure(error.to_string()))
+    }
+}
+
+impl fmt::Debug for Output {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdout => fmt.write_str("Stdout"),
+            Self::Stderr => fmt.write_str("Stderr"),
+            Self::File(path, _) => fmt.debug_tuple("File").field(path).finish(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    output: Output,
+    is_shutdown: atomic::AtomicBool,
+}
+
+impl State {
+    #[inline]
+    fn new(url: &str) -> Self {
+        Self {
+            output: Output::new(url),
+            is_shutdown: atomic::AtomicBool::new(false),
+        }
+    }
+
+    #[inline]
+    fn write(&self, text: &str) -> OTelSdkResult {
+        if self.is_shutdown.load(Ordering::Acquire) {
+            return Err(OTelSdkError::AlreadyShutdown);
+        }
+        self.output.write(text)
+    }
+
+    #[inline]
+    fn shutdown(&self) -> OTelSdkResult {
+        self.is_shutdown.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+struct Timestamp(std::time::SystemTime);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since_epoch = self.0.duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        write!(fmt, "{}.{:06}", since_epoch.as_secs(), since_epoch.subsec_micros())
+    }
+}
+
+fn write_attrs<'a>(out: &mut String, attrs: impl IntoIterator<Item = &'a opentelemetry::KeyValue>) {
+    for attr in attrs {
+        let _ = write!(out, " {}={}", attr.key, attr.value);
+    }
+}
+
+fn write_any(out: &mut String, value: &opentelemetry::logs::AnyValue) {
+    use opentelemetry::logs::AnyValue;
+
+    let _ = match value {
+        AnyValue::Boolean(value) => write!(out, "{value}"),
+        AnyValue::Int(value) => write!(out, "{value}"),
+        AnyValue::Double(value) => write!(out, "{value}"),
+        AnyValue::String(value) => write!(out, "{value}"),
+        value => write!(out, "{value:?}"),
+    };
+}
+
+///Span exporter writing every span as `[trace] <name> ...` line
+#[derive(Debug)]
+pub struct SpanExporter {
+    state: State,
+}
+
+impl SpanExporter {
+    #[inline]
+    pub fn new(url: &str) -> Self {
+        Self {
+            state: State::new(url),
+        }
+    }
+}
+
+impl opentelemetry_sdk::trace::SpanExporter for SpanExporter {
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        let mut out = String::new();
+        for span in batch.iter() {
+            let duration = span.end_time.duration_since(span.start_time).unwrap_or_default();
+            let _ = write!(
+                out,
+                "[trace] {} trace_id={} span_id={} parent_span_id={} kind={:?} duration={:?} status={:?}",
+                span.name,
+                span.span_context.trace_id(),
+                span.span_context.span_id(),
+                span.parent_span_id,
+                span.span_kind,
+                duration,
+                span.status,
+            );
+            write_attrs(&mut out, span.attributes.iter());
+            out.push('\n');
+
+            for event in span.events.iter() {
+                let _ = write!(out, "[trace]   event {} at={}", event.name, Timestamp(event.timestamp));
+                write_attrs(&mut out, event.attributes.iter());
+                out.push('\n');
+            }
+        }
+        self.state.write(&out)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, _: time::Duration) -> OTelSdkResult {
+        self.state.shutdown()
+    }
+}
+
+///Log exporter writing every record as `[log] <timestamp> <level> <body> ...` line
+#[derive(Debug)]
+pub struct LogExporter {
+    state: State,
+}
+
+impl LogExporter {
+    #[inline]
+    pub fn new(url: &str) -> Self {
+        Self {
+            state: State::new(url),
+        }
+    }
+}
+
+impl opentelemetry_sdk::logs::LogExporter for LogExporter {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        let mut out = String::new();
+        for (record, _) in batch.iter() {
+            out.push_str("[log]");
+            if let Some(timestamp) = record.timestamp().or_else(|| record.observed_timestamp()) {
+                let _ = write!(out, " {}", Timestamp(timestamp));
+            }
+            let _ = write!(out, " {}", record.severity_text().unwrap_or("-"));
+            if let Some(body) = record.body() {
+                out.push(' ');
+                write_any(&mut out, body);
+            }
+            for (key, value) in record.attributes_iter() {
+                let _ = write!(out, " {key}=");
+                write_any(&mut out, value);
+            }
+            if let Some(ctx) = record.trace_context() {
+                let _ = write!(out, " trace_id={} span_id={}", ctx.trace_id, ctx.span_id);
+            }
+            out.push('\n');
+        }
+        self.state.write(&out)
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.state.shutdown()
+    }
+}
+
+const SERVICE_NAME: opentelemetry::Key = opentelemetry::Key::from_static_str("service.name");
+const SERVICE_VERSION: opentelemetry::Key = opentelemetry::Key::from_static_str("service.version");
+const DEPLOYMENT_ENVIRONMENT: [opentelemetry::Key; 2] = [
+    opentelemetry::Key::from_static_str("deployment.environment.name"),
+    opentelemetry::Key::from_static_str("deployment.environment"),
+];
+///Version of Elastic Common Schema used by [JsonLogExporter]
+const ECS_VERSION: &str = "8.11.0";
+
+struct EcsRecord<'a> {
+    record: &'a opentelemetry_sdk::logs::SdkLogRecord,
+    scope: &'a opentelemetry::InstrumentationScope,
+    resource: &'a opentelemetry_sdk::Resource,
+}
+
+impl serde::Serialize for EcsRecord<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        use crate::json::{AnyValueSerde, Buffer, ValueSerde};
+
+        let mut buffer = Buffer::new();
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(timestamp) = self.record.timestamp().or_else(|| self.record.observed_timestamp()) {
+            if let Some(timestamp) = crate::json::rfc3339(&mut buffer, timestamp) {
+                map.serialize_entry("@timestamp", timestamp)?;
+            }
+            buffer.clear();
+        }
+        if let Some(severity_text) = self.record.severity_text() {
+            map.serialize_entry("log.level", severity_text)?;
+        }
+        match self.record.target() {
+            Some(target) => map.serialize_entry("log.logger", target.as_ref())?,
+            None => map.serialize_entry("log.logger", self.scope.name())?,
+        }
+        if let Some(body) = self.record.body() {
+            map.serialize_entry("message", &AnyValueSerde(body))?;
+        }
+        map.serialize_entry("ecs.version", ECS_VERSION)?;
+        if let Some(ctx) = self.record.trace_context() {
+            map.serialize_entry("trace.id", &ctx.trace_id.to_string())?;
+            map.serialize_entry("span.id", &ctx.span_id.to_string())?;
+        }
+        if let Some(service) = self.resource.get(&SERVICE_NAME) {
+            map.serialize_entry("service.name", &ValueSerde(&service))?;
+        }
+        if let Some(version) = self.resource.get(&SERVICE_VERSION) {
+            map.serialize_entry("service.version", &ValueSerde(&version))?;
+        }
+        if let Some(env) = DEPLOYMENT_ENVIRONMENT.iter().find_map(|key| self.resource.get(key)) {
+            map.serialize_entry("service.environment", &ValueSerde(&env))?;
+        }
+        for (key, value) in self.record.attributes_iter() {
+            map.serialize_entry(key.as_str(), &AnyValueSerde(value))?;
+        }
+        map.end()
+    }
+}
+
+///Log exporter writing every record as JSON object per line, or every batch as OTLP/JSON `ExportLogsServiceRequest` line, according to format
+#[derive(Debug)]
+pub struct JsonLogExporter {
+    state: State,
+    format: crate::builder::StdoutFormat,
+    resource: opentelemetry_sdk::Resource,
+    otlp_resource: opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema,
+}
+
+impl JsonLogExporter {
+    #[inline]
+    pub fn new(url: &str, format: crate::builder::StdoutFormat) -> Self {
+        Self {
+            state: State::new(url),
+            format,
+            resource: opentelemetry_sdk::Resource::builder_empty().build(),
+            otlp_resource: Default::default(),
+        }
+    }
+}
+
+impl opentelemetry_sdk::logs::LogExporter for JsonLogExporter {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        use crate::builder::StdoutFormat;
+
+        let mut out = Vec::new();
+        match self.format {
+            StdoutFormat::Ecs => for (record, scope) in batch.iter() {
+                if let Err(error) = serde_json::to_writer(&mut out, &EcsRecord { record, scope, resource: &self.resource }) {
+                    return Err(OTelSdkError::InternalFailure(error.to_string()));
+                }
+                out.push(b'\n');
+            },
+            StdoutFormat::Otlp | StdoutFormat::Text => if batch.iter().next().is_some() {
+                let request = opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest {
+                    resource_logs: opentelemetry_proto::transform::logs::tonic::group_logs_by_resource_and_scope(batch, &self.otlp_resource),
+                };
+                if let Err(error) = serde_json::to_writer(&mut out, &request) {
+                    return Err(OTelSdkError::InternalFailure(error.to_string()));
+                }
+                out.push(b'\n');
+            },
+        }
+        //Output of serde_json is always valid UTF-8
+        self.state.write(core::str::from_utf8(&out).unwrap_or_default())
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.state.shutdown()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        self.resource = res.clone();
+        self.otlp_resource = res.into();
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+///Metric exporter writing every data point as `[metric] <name> ...` line
+#[derive(Debug)]
+pub struct MetricExporter {
+    state: State,
+    temporality: opentelemetry_sdk::metrics::Temporality,
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl MetricExporter {
+    #[inline]
+    pub fn new(url: &str, temporality: opentelemetry_sdk::metrics::Temporality) -> Self {
+        Self {
+            state: State::new(url),
+            temporality,
+        }
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+fn write_metric<T: fmt::Display + Copy>(out: &mut String, name: &str, data: &opentelemetry_sdk::metrics::data::MetricData<T>) {
+    use opentelemetry_sdk::metrics::data::MetricData;
+
+    match data {
+        MetricData::Gauge(gauge) => for point in gauge.data_points() {
+            let _ = write!(out, "[metric] {name} gauge value={}", point.value());
+            write_attrs(out, point.attributes());
+            out.push('\n');
+        },
+        MetricData::Sum(sum) => for point in sum.data_points() {
+            let _ = write!(out, "[metric] {name} sum value={} monotonic={}", point.value(), sum.is_monotonic());
+            write_attrs(out, point.attributes());
+            out.push('\n');
+        },
+        MetricData::Histogram(histogram) => for point in histogram.data_points() {
+            let bounds = point.bounds().collect::<Vec<_>>();
+            let counts = point.bucket_counts().collect::<Vec<_>>();
+            let _ = write!(out, "[metric] {name} histogram count={} sum={} bounds={bounds:?} counts={counts:?}", point.count(), point.sum());
+            write_attrs(out, point.attributes());
+            out.push('\n');
+        },
+        MetricData::ExponentialHistogram(histogram) => for point in histogram.data_points() {
+            let _ = write!(out, "[metric] {name} exponential_histogram count={} sum={} scale={}", point.count(), point.sum(), point.scale());
+            write_attrs(out, point.attributes());
+            out.push('\n');
+        },
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl opentelemetry_sdk::metrics::exporter::PushMetricExporter for MetricExporter {
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        use opentelemetry_sdk::metrics::data::AggregatedMetrics;
+
+        let mut out = String::new();
+        for scope in metrics.scope_metrics() {
+            for metric in scope.metrics() {
+                match metric.data() {
+                    AggregatedMetrics::F64(data) => write_metric(&mut out, metric.name(), data),
+                    AggregatedMetrics::U64(data) => write_metric(&mut out, metric.name(), data),
+                    AggregatedMetrics::I64(data) => write_metric(&mut out, metric.name(), data),
+                }
+            }
+        }
+        self.state.write(&out)
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, _: time::Duration) -> OTelSdkResult {
+        self.state.shutdown()
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.temporality
+    }
+}
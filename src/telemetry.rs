@@ -0,0 +1,132 @@
+use core::time;
+
+use opentelemetry_sdk::error::OTelSdkResult;
+
+///Target of spans created around exports when self telemetry is enabled
+pub const SELF_TELEMETRY_TARGET: &str = "tracing_opentelemetry_setup::export";
+///Name of spans created around exports when self telemetry is enabled
+const SELF_TELEMETRY_SPAN: &str = "otel.export";
+
+///Exporter wrapper that wraps every export into `otel.export` span, if `enabled`
+#[derive(Debug)]
+pub struct SelfTelemetryExporter<T> {
+    inner: T,
+    enabled: bool,
+}
+
+impl<T> SelfTelemetryExporter<T> {
+    #[inline(always)]
+    pub fn new(inner: T, enabled: bool) -> Self {
+        Self {
+            inner,
+            enabled,
+        }
+    }
+
+    ///Creates export span
+    ///
+    ///Processors export within scope with suppressed telemetry, which prevents export from producing more telemetry.
+    ///Export span is created as root outside of this scope, but it is never entered so that export itself remains suppressed
+    fn export_span(signal: &'static str, batch_size: usize) -> tracing::Span {
+        let _scope = opentelemetry::Context::new().attach();
+        tracing::info_span!(
+            target: SELF_TELEMETRY_TARGET,
+            parent: None,
+            SELF_TELEMETRY_SPAN,
+            otel.signal = signal,
+            otel.batch_size = batch_size,
+            otel.status_code = tracing::field::Empty,
+            error.message = tracing::field::Empty,
+        )
+    }
+
+    #[inline]
+    fn on_export(span: &tracing::Span, result: &OTelSdkResult) {
+        match result {
+            Ok(()) => span.record("otel.status_code", "OK"),
+            Err(error) => span.record("otel.status_code", "ERROR").record("error.message", tracing::field::display(error)),
+        };
+    }
+}
+
+impl<T: opentelemetry_sdk::trace::SpanExporter> opentelemetry_sdk::trace::SpanExporter for SelfTelemetryExporter<T> {
+    async fn export(&self, batch: Vec<opentelemetry_sdk::trace::SpanData>) -> OTelSdkResult {
+        //Batch of only export spans must not produce new export span, otherwise they would be exported forever
+        if !self.enabled || batch.iter().all(|span| span.name == SELF_TELEMETRY_SPAN) {
+            return self.inner.export(batch).await;
+        }
+
+        let span = Self::export_span("traces", batch.len());
+        let result = self.inner.export(batch).await;
+        Self::on_export(&span, &result);
+        result
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&mut self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+impl<T: opentelemetry_sdk::logs::LogExporter> opentelemetry_sdk::logs::LogExporter for SelfTelemetryExporter<T> {
+    async fn export(&self, batch: opentelemetry_sdk::logs::LogBatch<'_>) -> OTelSdkResult {
+        if !self.enabled {
+            return self.inner.export(batch).await;
+        }
+
+        let span = Self::export_span("logs", batch.iter().count());
+        let result = self.inner.export(batch).await;
+        Self::on_export(&span, &result);
+        result
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        self.inner.set_resource(resource)
+    }
+}
+
+#[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+impl<T: opentelemetry_sdk::metrics::exporter::PushMetricExporter> opentelemetry_sdk::metrics::exporter::PushMetricExporter for SelfTelemetryExporter<T> {
+    async fn export(&self, metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics) -> OTelSdkResult {
+        if !self.enabled {
+            return self.inner.export(metrics).await;
+        }
+
+        let batch_size = metrics.scope_metrics().map(|scope| scope.metrics().count()).sum();
+        let span = Self::export_span("metrics", batch_size);
+        let result = self.inner.export(metrics).await;
+        Self::on_export(&span, &result);
+        result
+    }
+
+    #[inline(always)]
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    #[inline(always)]
+    fn shutdown_with_timeout(&self, timeout: time::Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    #[inline(always)]
+    fn temporality(&self) -> opentelemetry_sdk::metrics::Temporality {
+        self.inner.temporality()
+    }
+}
@@ -0,0 +1,200 @@
+//! In-process pipeline for testing and benchmarking
+//!
+//!Pipeline is built by regular [Builder](crate::builder::Builder) with [Protocol::InMemory](crate::builder::Protocol::InMemory) destination,
+//!hence it uses the same processors and settings as real exporters, but exports data into memory, allowing to measure overhead of instrumentation without network
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::Otlp;
+//!use tracing_opentelemetry_setup::builder::ExportMode;
+//!use tracing_opentelemetry_setup::tracing;
+//!
+//!let pipeline = Otlp::builder_in_memory().with_export_mode(ExportMode::Batch).finish_in_memory();
+//!tracing::dispatcher::with_default(pipeline.dispatch(), || {
+//!    tracing::info_span!("span").in_scope(|| tracing::info!("event"));
+//!});
+//!pipeline.flush();
+//!assert_eq!(pipeline.finished_spans().len(), 1);
+//!assert_eq!(pipeline.emitted_logs_count(), 1);
+//!```
+//!
+//!Captured data can be inspected to verify what instrumentation emits:
+//!
+//!```rust
+//!use tracing_opentelemetry_setup::Otlp;
+//!use tracing_opentelemetry_setup::tracing;
+//!
+//!let pipeline = Otlp::builder_in_memory().finish_in_memory();
+//!tracing::dispatcher::with_default(pipeline.dispatch(), || {
+//!    tracing::info_span!("handle_request", user.id = 42).in_scope(|| tracing::info!("request handled"));
+//!});
+//!pipeline.assert_span_attr("handle_request", "user.id", 42);
+//!assert!(pipeline.find_log("request handled").is_some());
+//!```
+
+use std::borrow::Cow;
+
+use opentelemetry_sdk::logs::{InMemoryLogExporter, SdkLogRecord};
+use opentelemetry_sdk::trace::InMemorySpanExporter;
+
+use crate::Otlp;
+use crate::builder::{Builder, Destination, ExportMode, Protocol, TraceSettings};
+
+#[derive(Clone, Default)]
+///Exporters shared by every [Protocol::InMemory] destination of the builder
+pub(crate) struct InMemoryExporters {
+    pub(crate) spans: InMemorySpanExporter,
+    pub(crate) logs: InMemoryLogExporter,
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    metrics: std::sync::Arc<std::sync::OnceLock<opentelemetry_sdk::metrics::InMemoryMetricExporter>>,
+}
+
+impl InMemoryExporters {
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    ///Returns metric exporter, creating it with `temporality` on first use
+    pub(crate) fn metrics(&self, temporality: opentelemetry_sdk::metrics::Temporality) -> opentelemetry_sdk::metrics::InMemoryMetricExporter {
+        self.metrics.get_or_init(|| opentelemetry_sdk::metrics::InMemoryMetricExporterBuilder::new().with_temporality(temporality).build()).clone()
+    }
+}
+
+///In-process pipeline exporting into memory
+pub struct Pipeline {
+    otlp: Otlp,
+    dispatch: tracing::Dispatch,
+    exporters: InMemoryExporters,
+}
+
+impl Pipeline {
+    #[inline(always)]
+    ///Access underlying `Otlp` instance
+    pub fn otlp(&self) -> &Otlp {
+        &self.otlp
+    }
+
+    #[inline(always)]
+    ///Access dispatcher that feeds data into pipeline
+    pub fn dispatch(&self) -> &tracing::Dispatch {
+        &self.dispatch
+    }
+
+    #[inline(always)]
+    ///Flushes all buffered data into memory
+    pub fn flush(&self) {
+        self.otlp.flush();
+    }
+
+    #[inline]
+    ///Returns all spans exported so far
+    pub fn finished_spans(&self) -> Vec<opentelemetry_sdk::trace::SpanData> {
+        self.exporters.spans.get_finished_spans().unwrap_or_default()
+    }
+
+    ///Returns first exported span with specified `name`
+    pub fn find_span(&self, name: &str) -> Option<opentelemetry_sdk::trace::SpanData> {
+        self.finished_spans().into_iter().find(|span| span.name == name)
+    }
+
+    #[track_caller]
+    ///Asserts that span `name` has been exported with attribute `key` equal to `expected`
+    ///
+    ///Note that integer fields are captured as `i64`, hence `expected` integer must be `i64` too
+    pub fn assert_span_attr(&self, name: &str, key: &str, expected: impl Into<opentelemetry::Value>) {
+        let expected = expected.into();
+        let span = match self.find_span(name) {
+            Some(span) => span,
+            None => {
+                let names = self.finished_spans().into_iter().map(|span| span.name).collect::<Vec<_>>();
+                panic!("span '{name}' is not exported, exported spans: {names:?}");
+            }
+        };
+        match span.attributes.iter().find(|attr| attr.key.as_str() == key) {
+            Some(attr) => assert_eq!(attr.value, expected, "span '{name}' has unexpected value of '{key}'"),
+            None => panic!("span '{name}' has no attribute '{key}', attributes: {:?}", span.attributes),
+        }
+    }
+
+    #[inline]
+    ///Returns number of logs exported so far
+    pub fn emitted_logs_count(&self) -> usize {
+        self.exporters.logs.get_emitted_logs().map(|logs| logs.len()).unwrap_or_default()
+    }
+
+    ///Returns all log records exported so far
+    pub fn captured_logs(&self) -> Vec<SdkLogRecord> {
+        self.exporters.logs.get_emitted_logs().map(|logs| logs.into_iter().map(|log| log.record).collect()).unwrap_or_default()
+    }
+
+    ///Returns first exported log record with string `body`
+    pub fn find_log(&self, body: &str) -> Option<SdkLogRecord> {
+        self.captured_logs().into_iter().find(|record| matches!(record.body(), Some(opentelemetry::logs::AnyValue::String(text)) if text.as_str() == body))
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    #[inline]
+    ///Returns all metrics exported so far, every [flush](Self::flush) produces new collection
+    pub fn captured_metrics(&self) -> Vec<opentelemetry_sdk::metrics::data::ResourceMetrics> {
+        self.exporters.metrics.get().and_then(|metrics| metrics.get_finished_metrics().ok()).unwrap_or_default()
+    }
+
+    #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+    ///Looks up metric `name` within the latest collection, passing it to `cb`
+    ///
+    ///Returns `None` if metric has not been collected
+    pub fn find_metric<R>(&self, name: &str, cb: impl FnOnce(&opentelemetry_sdk::metrics::data::Metric) -> R) -> Option<R> {
+        let metrics = self.captured_metrics();
+        let latest = metrics.last()?;
+        let metric = latest.scope_metrics().flat_map(|scope| scope.metrics()).find(|metric| metric.name() == name)?;
+        Some(cb(metric))
+    }
+
+    #[inline]
+    ///Discards all data exported so far
+    pub fn reset(&self) {
+        self.exporters.spans.reset();
+        self.exporters.logs.reset();
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if let Some(metrics) = self.exporters.metrics.get() {
+            metrics.reset();
+        }
+    }
+}
+
+impl Otlp {
+    ///Starts building in-memory [Pipeline] to verify emitted telemetry in tests
+    ///
+    ///Builder is pre-configured with [ExportMode::Simple], exporting every span and log immediately.
+    ///Any setting can be adjusted before calling [Builder::finish_in_memory]
+    pub fn builder_in_memory() -> Builder<'static> {
+        let builder = Self::builder(Destination {
+            protocol: Protocol::InMemory,
+            url: Cow::Borrowed(""),
+        });
+        builder.with_export_mode(ExportMode::Simple)
+    }
+}
+
+impl Builder<'_> {
+    ///Finalizes building in-memory [Pipeline], capturing data of every [Protocol::InMemory] destination
+    ///
+    ///Signals that are not configured explicitly are enabled with default settings, sampling every trace
+    pub fn finish_in_memory(mut self) -> Pipeline {
+        if !self.has_logs() {
+            self = self.with_logs(None);
+        }
+        if !self.has_trace() {
+            self = self.with_trace(None, TraceSettings::new(1.0));
+        }
+        #[cfg(any(feature = "metrics", feature = "tracing-metrics"))]
+        if !self.has_metrics() {
+            self = self.with_metrics(None, crate::builder::MetricsSettings::new());
+        }
+
+        let exporters = self.in_memory_exporters().clone();
+        let otlp = self.finish();
+        let dispatch = otlp.create_dispatch("testing");
+        Pipeline {
+            otlp,
+            dispatch,
+            exporters,
+        }
+    }
+}
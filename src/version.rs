@@ -0,0 +1,57 @@
+//!Guard against linking multiple versions of opentelemetry crates
+//!
+//!When application depends on different version of `opentelemetry` (or related crates) than this crate,
+//!Rust treats them as unrelated crates: global propagator/provider set via one is invisible to another, and span extensions cannot find opentelemetry data.
+//!This results in silently broken traces rather than compilation errors.
+//!
+//!To detect it, call [assert_same_version](crate::assert_same_version) at the start of your application,
+//!listing crates you depend on directly. Alternatively use crates re-exported via [prelude](crate::prelude) only.
+
+use core::any::TypeId;
+
+///Version of `opentelemetry` crates used by this crate
+pub const OPENTELEMETRY_VERSION: &str = "0.31";
+///Version of `tracing-opentelemetry` crate used by this crate
+pub const TRACING_OPENTELEMETRY_VERSION: &str = "0.32";
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_same(name: &str, expected_version: &str, app: TypeId, this: TypeId) {
+    if app != this {
+        panic!("Application links version of '{name}' that differs from one used by tracing-opentelemetry-setup ({expected_version}). Telemetry would be silently lost. Either align dependency version or use crate re-exported by tracing-opentelemetry-setup");
+    }
+}
+
+#[macro_export]
+///Verifies that listed crates, as seen by caller, are the same as used by this crate, panicking with diagnostic otherwise
+///
+///Supported crates: `opentelemetry`, `opentelemetry_sdk`, `tracing`, `tracing_subscriber`, `tracing_opentelemetry`.
+///Each listed crate must be direct dependency of the caller.
+///
+///```rust
+///use tracing_opentelemetry_setup::assert_same_version;
+///
+///assert_same_version!(tracing, tracing_subscriber);
+///```
+macro_rules! assert_same_version {
+    (@check opentelemetry) => {
+        $crate::version::assert_same("opentelemetry", $crate::version::OPENTELEMETRY_VERSION, core::any::TypeId::of::<::opentelemetry::Context>(), core::any::TypeId::of::<$crate::opentelemetry::Context>())
+    };
+    (@check opentelemetry_sdk) => {
+        $crate::version::assert_same("opentelemetry_sdk", $crate::version::OPENTELEMETRY_VERSION, core::any::TypeId::of::<::opentelemetry_sdk::Resource>(), core::any::TypeId::of::<$crate::opentelemetry_sdk::Resource>())
+    };
+    (@check tracing_opentelemetry) => {
+        $crate::version::assert_same("tracing_opentelemetry", $crate::version::TRACING_OPENTELEMETRY_VERSION, core::any::TypeId::of::<::tracing_opentelemetry::OtelData>(), core::any::TypeId::of::<$crate::tracing_opentelemetry::OtelData>())
+    };
+    (@check tracing) => {
+        $crate::version::assert_same("tracing", "0.1", core::any::TypeId::of::<::tracing::Span>(), core::any::TypeId::of::<$crate::tracing::Span>())
+    };
+    (@check tracing_subscriber) => {
+        $crate::version::assert_same("tracing_subscriber", "0.3", core::any::TypeId::of::<::tracing_subscriber::Registry>(), core::any::TypeId::of::<$crate::tracing_subscriber::Registry>())
+    };
+    ($($krate:ident),+ $(,)?) => {{
+        $(
+            $crate::assert_same_version!(@check $krate);
+        )+
+    }};
+}
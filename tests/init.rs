@@ -0,0 +1,14 @@
+#![cfg(feature = "testing")]
+//!Initialization of global subscriber can only succeed once per process, hence it is tested in its own binary
+
+#[test]
+fn should_fail_to_init_tracing_subscriber_twice() {
+    let pipeline = tracing_opentelemetry_setup::builder::Otlp::builder_in_memory().finish_in_memory();
+    pipeline.otlp().try_init_tracing_subscriber("first", tracing_subscriber::registry()).expect("to set global subscriber");
+    pipeline.otlp().try_init_tracing_subscriber("second", tracing_subscriber::registry()).expect_err("global subscriber is already set");
+
+    //First subscriber remains in place
+    tracing::info!("after second init");
+    pipeline.flush();
+    assert!(pipeline.find_log("after second init").is_some());
+}
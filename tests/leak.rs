@@ -0,0 +1,43 @@
+#![cfg(all(debug_assertions, feature = "testing"))]
+//!Spans left entered on finished threads are reported via global subscriber, hence it is tested in its own binary
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[test]
+fn should_report_spans_left_entered() {
+    let pipeline = tracing_opentelemetry_setup::builder::Otlp::builder_in_memory().finish_in_memory();
+    tracing::dispatcher::set_global_default(pipeline.dispatch().clone()).expect("to set global subscriber");
+    #[cfg(not(feature = "tracing-metrics"))]
+    let filtered = pipeline.otlp().layers().with_filters(LevelFilter::TRACE, LevelFilter::TRACE);
+    #[cfg(feature = "tracing-metrics")]
+    let filtered = pipeline.otlp().layers().with_filters(LevelFilter::TRACE, LevelFilter::TRACE, LevelFilter::TRACE);
+    let filtered = tracing::Dispatch::new(tracing_subscriber::registry().with(filtered));
+
+    std::thread::spawn(|| {
+        core::mem::forget(tracing::info_span!("leaked_on_thread").entered());
+        let _properly_exited = tracing::info_span!("properly_exited").entered();
+    }).join().expect("to finish thread");
+    std::thread::spawn(move || tracing::dispatcher::with_default(&filtered, || {
+        core::mem::forget(tracing::info_span!("leaked_on_filtered_thread").entered());
+    })).join().expect("to finish thread");
+    //Spans left entered on finished thread are reported on next exit
+    tracing::info_span!("next_span").in_scope(|| {});
+
+    let span = tracing::info_span!("exited_elsewhere");
+    let id = span.id().expect("to have id");
+    let dispatch = pipeline.dispatch().clone();
+    dispatch.enter(&id);
+    std::thread::spawn(move || dispatch.exit(&id)).join().expect("to finish thread");
+
+    pipeline.flush();
+    let leaked = pipeline.captured_logs().into_iter().filter(|record| matches!(record.body(), Some(opentelemetry::logs::AnyValue::String(text)) if text.as_str().starts_with("Spans left entered")))
+                                                    .filter_map(|record| record.attributes_iter().find(|(key, _)| key.as_str() == "spans").map(|(_, value)| format!("{value:?}")))
+                                                    .collect::<Vec<_>>();
+    let is_reported = |name: &str| leaked.iter().any(|spans| spans.contains(name));
+    assert!(is_reported("leaked_on_thread"), "{leaked:?}");
+    assert!(is_reported("leaked_on_filtered_thread"), "{leaked:?}");
+    assert!(is_reported("exited_elsewhere"), "{leaked:?}");
+    assert!(!is_reported("properly_exited"), "{leaked:?}");
+    assert!(!is_reported("next_span"), "{leaked:?}");
+}
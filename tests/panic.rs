@@ -0,0 +1,51 @@
+#![cfg(all(feature = "panic", feature = "file"))]
+//!Panic hook is global, hence it is tested in its own binary
+
+#[test]
+fn should_write_abort_record_on_panic_and_replay_it() {
+    struct TempDir(std::path::PathBuf);
+
+    impl Drop for TempDir {
+        #[inline(always)]
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    let dir = TempDir(std::env::temp_dir().join(format!("tracing_opentelemetry_setup_panic_{}", std::process::id())));
+    std::fs::create_dir_all(&dir.0).expect("to create temp dir");
+    let record_file = dir.0.join("panic.jsonl");
+    let output_file = dir.0.join("replay.jsonl");
+
+    tracing_opentelemetry_setup::panic::install_abort_panic_hook(&record_file);
+    let result = std::thread::spawn(|| panic!("abort \"record\"")).join();
+    assert!(result.is_err());
+
+    let content = std::fs::read_to_string(&record_file).expect("to read file");
+    let record = serde_json::from_str::<serde_json::Value>(content.lines().next().expect("to have record")).expect("valid json");
+    let record = &record["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+    assert_eq!(record["severityText"], "FATAL", "{content}");
+    let attrs = record["attributes"].as_array().expect("attributes");
+    let attr = |key: &str| attrs.iter().find(|attr| attr["key"] == key).map(|attr| attr["value"]["stringValue"].clone());
+    assert_eq!(attr("exception.type").expect("type"), "Rust Panic", "{content}");
+    assert_eq!(attr("exception.message").expect("message"), "abort \"record\"", "{content}");
+    assert!(attr("exception.location").expect("location").as_str().expect("string").contains("panic.rs"), "{content}");
+
+    let destination = tracing_opentelemetry_setup::builder::Destination {
+        url: output_file.to_str().expect("utf-8 path").to_owned().into(),
+        protocol: tracing_opentelemetry_setup::builder::Protocol::File,
+    };
+    let mut otlp = tracing_opentelemetry_setup::builder::Otlp::builder(destination).with_logs(None).finish();
+    let replayed = tracing_opentelemetry_setup::panic::replay_abort_records(&otlp, &record_file).expect("to replay");
+    otlp.shutdown(None).expect("success");
+    assert_eq!(replayed, 1);
+    assert!(!record_file.exists());
+
+    let output = std::fs::read_to_string(&output_file).expect("to read output");
+    let output = serde_json::from_str::<serde_json::Value>(output.lines().next().expect("to have output")).expect("valid json");
+    let replayed = &output["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+    assert_eq!(replayed["severityNumber"], 21, "{output}");
+    assert_eq!(replayed["timeUnixNano"], record["timeUnixNano"], "{output}");
+    assert!(replayed["attributes"].as_array().expect("attributes").iter().any(|attr| attr["value"]["stringValue"] == "abort \"record\""), "{output}");
+    assert!(output["resourceLogs"][0]["resource"]["attributes"].as_array().expect("resource").iter().any(|attr| attr["key"] == "service.name"), "{output}");
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "testing")]
+//!Reloading level requires global subscriber, hence it is tested in its own binary
+
+#[test]
+fn should_apply_reloaded_level() {
+    let pipeline = tracing_opentelemetry_setup::builder::Otlp::builder_in_memory().finish_in_memory();
+    let handle = pipeline.otlp().init_tracing_subscriber_with_reload("reload", tracing_subscriber::registry(), tracing::Level::INFO);
+
+    tracing::debug!("debug before reload");
+    tracing::info!("info before reload");
+    handle.set_level(tracing::Level::WARN).expect("subscriber is alive");
+    tracing::info!("info after reload");
+    tracing::warn!("warn after reload");
+    handle.set_level(tracing::Level::DEBUG).expect("subscriber is alive");
+    tracing::debug!("debug after second reload");
+
+    pipeline.flush();
+    assert!(pipeline.find_log("debug before reload").is_none());
+    assert!(pipeline.find_log("info before reload").is_some());
+    assert!(pipeline.find_log("info after reload").is_none());
+    assert!(pipeline.find_log("warn after reload").is_some());
+    assert!(pipeline.find_log("debug after second reload").is_some());
+}